@@ -21,14 +21,14 @@ use blockfrost_openapi::models::{
     block_content_addresses_inner::BlockContentAddressesInner, drep::Drep,
     drep_delegators_inner::DrepDelegatorsInner, drep_metadata::DrepMetadata,
     drep_updates_inner::DrepUpdatesInner, drep_votes_inner::DrepVotesInner,
-    epoch_content::EpochContent, epoch_param_content::EpochParamContent,
+    dreps_inner::DrepsInner, epoch_content::EpochContent, epoch_param_content::EpochParamContent,
     epoch_stake_content_inner::EpochStakeContentInner,
     epoch_stake_pool_content_inner::EpochStakePoolContentInner, genesis_content::GenesisContent,
     network::Network, network_eras_inner::NetworkErasInner, pool::Pool,
     pool_delegators_inner::PoolDelegatorsInner, pool_history_inner::PoolHistoryInner,
     pool_list_extended_inner::PoolListExtendedInner, pool_list_retire_inner::PoolListRetireInner,
     pool_metadata::PoolMetadata, pool_updates_inner::PoolUpdatesInner, proposal::Proposal,
-    proposal_parameters_parameters::ProposalParametersParameters,
+    proposal_metadata::ProposalMetadata, proposal_parameters::ProposalParameters,
     proposal_votes_inner::ProposalVotesInner, proposal_withdrawals_inner::ProposalWithdrawalsInner,
     proposals_inner::ProposalsInner, script::Script, script_cbor::ScriptCbor,
     script_datum::ScriptDatum, script_datum_cbor::ScriptDatumCbor, script_json::ScriptJson,
@@ -86,11 +86,12 @@ pub type AssetsAddressesResponse = Vec<AssetAddressesInner>;
 pub type BlocksSingleResponse = BlockContent;
 pub type BlocksResponse = Vec<BlockContent>;
 pub type BlocksAddressesExtendedResponse = Vec<AddressContentExtended>;
-pub type BlocksAddressesContentResponse = BlockContentAddressesInner;
+pub type BlocksAddressesContentResponse = Vec<BlockContentAddressesInner>;
 
 // epochs
 pub type EpochsParamResponse = EpochParamContent;
 pub type EpochsResponse = EpochContent;
+pub type EpochsListResponse = Vec<EpochContent>;
 pub type EpochsStakeResponse = Vec<EpochStakeContentInner>;
 pub type EpochStakePoolResponse = Vec<EpochStakePoolContentInner>;
 
@@ -99,13 +100,15 @@ pub type NetworkResponse = Network;
 pub type NetworkErasResponse = Vec<NetworkErasInner>;
 
 // governance
+pub type DrepsResponse = Vec<DrepsInner>;
 pub type DrepsSingleResponse = Drep;
 pub type DrepsDelegatorsResponse = Vec<DrepDelegatorsInner>;
 pub type DrepsMetadataResponse = DrepMetadata;
 pub type DrepsUpdatesResponse = Vec<DrepUpdatesInner>;
 pub type DrepsVotesResponse = Vec<DrepVotesInner>;
 pub type DrepsProposalsResponse = Vec<ProposalsInner>;
-pub type DrepsProposalParametersResponse = ProposalParametersParameters;
+pub type DrepsProposalParametersResponse = ProposalParameters;
+pub type DrepsProposalMetadataResponse = ProposalMetadata;
 pub type DrepsProposalVotesResponse = Vec<ProposalVotesInner>;
 pub type DrepsSingleProposalResponse = Proposal;
 pub type DrepsProposalWithdrawalsResponse = Vec<ProposalWithdrawalsInner>;
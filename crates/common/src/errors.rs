@@ -179,6 +179,11 @@ impl BlockfrostError {
         Self::custom_400("Invalid or malformed asset format.".to_string())
     }
 
+    /// error for assets/policy endpoints
+    pub fn invalid_policy_id() -> Self {
+        Self::custom_400("Invalid or malformed policy format.".to_string())
+    }
+
     /// error for pools endpoints
     pub fn invalid_pool_id() -> Self {
         Self::custom_400("Invalid or malformed pool id format.".to_string())
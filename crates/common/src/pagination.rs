@@ -233,6 +233,26 @@ impl ApplyPagination for Url {
     }
 }
 
+/// Paginates an already sorted (in ascending order) list in memory, e.g. when
+/// the results come from the node instead of the data node.
+impl<T> ApplyPagination for Vec<T> {
+    fn apply_pagination(&mut self, pagination: &Pagination) {
+        if pagination.order == Order::Desc {
+            self.reverse();
+        }
+
+        let count = pagination.count.max(0) as usize;
+        let start = (pagination.page.max(1) as usize - 1).saturating_mul(count);
+
+        if start >= self.len() {
+            self.clear();
+        } else {
+            self.drain(..start);
+            self.truncate(count);
+        }
+    }
+}
+
 fn valid_value(val: i32) -> bool {
     (0..=i32::MAX).contains(&val)
 }
@@ -394,4 +414,40 @@ mod tests {
 
         assert_eq!(url.as_str(), expected);
     }
+
+    #[rstest]
+    #[case(1, 3, Order::Asc, vec![1, 2, 3])]
+    #[case(2, 3, Order::Asc, vec![4, 5, 6])]
+    #[case(4, 3, Order::Asc, vec![10])]
+    #[case(5, 3, Order::Asc, vec![])]
+    #[case(1, 4, Order::Desc, vec![10, 9, 8, 7])]
+    #[case(3, 4, Order::Desc, vec![2, 1])]
+    fn test_apply_pagination_vec(
+        #[case] page: i32,
+        #[case] count: i32,
+        #[case] order: Order,
+        #[case] expected: Vec<i32>,
+    ) {
+        use crate::pagination::{ApplyPagination, Pagination};
+
+        let mut items: Vec<i32> = (1..=10).collect();
+
+        let pagination = Pagination {
+            page,
+            count,
+            order,
+            from: ParamParts {
+                height: None,
+                index: None,
+            },
+            to: ParamParts {
+                height: None,
+                index: None,
+            },
+        };
+
+        items.apply_pagination(&pagination);
+
+        assert_eq!(items, expected);
+    }
 }
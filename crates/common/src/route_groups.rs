@@ -0,0 +1,208 @@
+//! Families of API endpoints. A platform serves a set of them depending on its
+//! mode, and advertises it, so that clients (and the gateway) know which
+//! requests it can actually answer.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RouteGroup {
+    /// `/health`, `/health/clock`
+    Health,
+    /// `/genesis`
+    Genesis,
+    /// `/tx/submit`, `/tx/submit/batch`, `/tx/{hash}/status`
+    TxSubmit,
+    /// `/utils/*`
+    Utils,
+    /// `/mempool/*`
+    Mempool,
+    /// `/ogmios`
+    Ogmios,
+    /// `/blocks/stream`
+    ChainStream,
+    /// Current ledger state answered by the node, or by the data node when
+    /// it’s healthy, e.g. `/epochs/latest/parameters` or `/pools`.
+    LedgerState,
+    Accounts,
+    Addresses,
+    Assets,
+    Blocks,
+    Epochs,
+    Network,
+    Pools,
+    Txs,
+    Governance,
+    Metadata,
+    Scripts,
+}
+
+/// What a [`RouteGroup`] needs to be served.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Backend {
+    /// Only the platform itself, e.g. its configuration.
+    None,
+    Node,
+    DataNode,
+}
+
+impl RouteGroup {
+    pub const ALL: [RouteGroup; 19] = [
+        RouteGroup::Health,
+        RouteGroup::Genesis,
+        RouteGroup::TxSubmit,
+        RouteGroup::Utils,
+        RouteGroup::Mempool,
+        RouteGroup::Ogmios,
+        RouteGroup::ChainStream,
+        RouteGroup::LedgerState,
+        RouteGroup::Accounts,
+        RouteGroup::Addresses,
+        RouteGroup::Assets,
+        RouteGroup::Blocks,
+        RouteGroup::Epochs,
+        RouteGroup::Network,
+        RouteGroup::Pools,
+        RouteGroup::Txs,
+        RouteGroup::Governance,
+        RouteGroup::Metadata,
+        RouteGroup::Scripts,
+    ];
+
+    pub fn backend(&self) -> Backend {
+        match self {
+            RouteGroup::Health | RouteGroup::Genesis => Backend::None,
+            RouteGroup::TxSubmit
+            | RouteGroup::Utils
+            | RouteGroup::Mempool
+            | RouteGroup::Ogmios
+            | RouteGroup::ChainStream
+            | RouteGroup::LedgerState => Backend::Node,
+            RouteGroup::Accounts
+            | RouteGroup::Addresses
+            | RouteGroup::Assets
+            | RouteGroup::Blocks
+            | RouteGroup::Epochs
+            | RouteGroup::Network
+            | RouteGroup::Pools
+            | RouteGroup::Txs
+            | RouteGroup::Governance
+            | RouteGroup::Metadata
+            | RouteGroup::Scripts => Backend::DataNode,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RouteGroup::Health => "health",
+            RouteGroup::Genesis => "genesis",
+            RouteGroup::TxSubmit => "tx_submit",
+            RouteGroup::Utils => "utils",
+            RouteGroup::Mempool => "mempool",
+            RouteGroup::Ogmios => "ogmios",
+            RouteGroup::ChainStream => "chain_stream",
+            RouteGroup::LedgerState => "ledger_state",
+            RouteGroup::Accounts => "accounts",
+            RouteGroup::Addresses => "addresses",
+            RouteGroup::Assets => "assets",
+            RouteGroup::Blocks => "blocks",
+            RouteGroup::Epochs => "epochs",
+            RouteGroup::Network => "network",
+            RouteGroup::Pools => "pools",
+            RouteGroup::Txs => "txs",
+            RouteGroup::Governance => "governance",
+            RouteGroup::Metadata => "metadata",
+            RouteGroup::Scripts => "scripts",
+        }
+    }
+
+    /// The group of a request path, without any prefix or query, e.g.
+    /// `/pools/pool1…/relays`. It’s `None` for paths outside of all groups,
+    /// like `/` or `/metrics`, which every platform serves.
+    pub fn of_path(path: &str) -> Option<RouteGroup> {
+        let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+
+        let group = match segments.as_slice() {
+            ["health"] | ["health", "clock"] => RouteGroup::Health,
+            ["genesis"] => RouteGroup::Genesis,
+            ["tx", "submit"] | ["tx", "submit", "batch"] | ["tx", _, "status"] => {
+                RouteGroup::TxSubmit
+            },
+            ["utils", ..] => RouteGroup::Utils,
+            ["mempool", ..] => RouteGroup::Mempool,
+            ["ogmios"] => RouteGroup::Ogmios,
+            ["blocks", "stream"] => RouteGroup::ChainStream,
+            ["accounts", _]
+            | ["addresses", _, "utxos"]
+            | ["epochs", "latest"]
+            | ["epochs", _, "parameters"]
+            | ["governance", "dreps", _]
+            | ["governance", "proposals"]
+            | ["pools"]
+            | ["pools", _, "relays"] => RouteGroup::LedgerState,
+            ["accounts", ..] => RouteGroup::Accounts,
+            ["addresses", ..] => RouteGroup::Addresses,
+            ["assets", ..] => RouteGroup::Assets,
+            ["blocks", ..] => RouteGroup::Blocks,
+            ["epochs", ..] => RouteGroup::Epochs,
+            ["network", ..] => RouteGroup::Network,
+            ["pools", ..] => RouteGroup::Pools,
+            ["txs", ..] => RouteGroup::Txs,
+            ["governance", ..] => RouteGroup::Governance,
+            ["metadata", ..] => RouteGroup::Metadata,
+            ["scripts", ..] => RouteGroup::Scripts,
+            _ => return None,
+        };
+
+        Some(group)
+    }
+}
+
+impl fmt::Display for RouteGroup {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("/health/clock", Some(RouteGroup::Health))]
+    #[case("/tx/submit", Some(RouteGroup::TxSubmit))]
+    #[case("/tx/abcd/status", Some(RouteGroup::TxSubmit))]
+    #[case("/utils/tx/evaluate/utxos", Some(RouteGroup::Utils))]
+    #[case("/blocks/stream", Some(RouteGroup::ChainStream))]
+    #[case("/blocks/latest", Some(RouteGroup::Blocks))]
+    #[case("/accounts/stake1u", Some(RouteGroup::LedgerState))]
+    #[case("/accounts/stake1u/rewards", Some(RouteGroup::Accounts))]
+    #[case("/addresses/addr1/utxos", Some(RouteGroup::LedgerState))]
+    #[case("/addresses/addr1/utxos/lovelace", Some(RouteGroup::Addresses))]
+    #[case("/epochs/latest/parameters", Some(RouteGroup::LedgerState))]
+    #[case("/epochs/latest", Some(RouteGroup::LedgerState))]
+    #[case("/epochs/latest/blocks", Some(RouteGroup::Epochs))]
+    #[case("/governance/dreps", Some(RouteGroup::Governance))]
+    #[case("/governance/dreps/drep1", Some(RouteGroup::LedgerState))]
+    #[case("/pools/", Some(RouteGroup::LedgerState))]
+    #[case("/pools/extended", Some(RouteGroup::Pools))]
+    #[case("/", None)]
+    #[case("/metrics", None)]
+    #[case("/unknown/path", None)]
+    fn test_of_path(#[case] path: &str, #[case] expected: Option<RouteGroup>) {
+        assert_eq!(RouteGroup::of_path(path), expected);
+    }
+
+    #[test]
+    fn test_serialization_matches_as_str() {
+        for group in RouteGroup::ALL {
+            assert_eq!(
+                serde_json::to_value(group).unwrap(),
+                serde_json::json!(group.as_str())
+            );
+        }
+    }
+}
@@ -1,7 +1,9 @@
 use crate::client::DataNode;
 use bf_api_provider::types::{
-    AccountsAddressesResponse, AccountsDelegationsResponse, AccountsRegistrationsResponse,
-    AccountsResponse, AccountsRewardsResponse, AccountsUtxosResponse, AccountsWithdrawalsResponse,
+    AccountsAddressesResponse, AccountsAddressesTotalResponse, AccountsAssetsResponse,
+    AccountsDelegationsResponse, AccountsHistoryResponse, AccountsMirResponse,
+    AccountsRegistrationsResponse, AccountsResponse, AccountsRewardsResponse,
+    AccountsUtxosResponse, AccountsWithdrawalsResponse,
 };
 use bf_common::{pagination::Pagination, types::ApiResult};
 
@@ -81,4 +83,43 @@ impl DataNodeAccounts<'_> {
 
         self.inner.client.get(&path, Some(pagination)).await
     }
+
+    pub async fn history(
+        &self,
+        stake_address: &str,
+        pagination: &Pagination,
+    ) -> ApiResult<AccountsHistoryResponse> {
+        let path = format!("accounts/{stake_address}/history");
+
+        self.inner.client.get(&path, Some(pagination)).await
+    }
+
+    pub async fn mirs(
+        &self,
+        stake_address: &str,
+        pagination: &Pagination,
+    ) -> ApiResult<AccountsMirResponse> {
+        let path = format!("accounts/{stake_address}/mirs");
+
+        self.inner.client.get(&path, Some(pagination)).await
+    }
+
+    pub async fn addresses_assets(
+        &self,
+        stake_address: &str,
+        pagination: &Pagination,
+    ) -> ApiResult<AccountsAssetsResponse> {
+        let path = format!("accounts/{stake_address}/addresses/assets");
+
+        self.inner.client.get(&path, Some(pagination)).await
+    }
+
+    pub async fn addresses_total(
+        &self,
+        stake_address: &str,
+    ) -> ApiResult<AccountsAddressesTotalResponse> {
+        let path = format!("accounts/{stake_address}/addresses/total");
+
+        self.inner.client.get(&path, None).await
+    }
 }
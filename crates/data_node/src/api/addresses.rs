@@ -1,7 +1,7 @@
 use crate::client::DataNode;
 use bf_api_provider::types::{
-    AddressesResponse, AddressesTransactionsResponse, AddressesUtxosAssetResponse,
-    AddressesUtxosResponse,
+    AddressesContentExtendedResponse, AddressesContentTotalResponse, AddressesResponse,
+    AddressesTransactionsResponse, AddressesUtxosAssetResponse, AddressesUtxosResponse,
 };
 use bf_common::{pagination::Pagination, types::ApiResult};
 
@@ -22,6 +22,18 @@ impl DataNodeAddresses<'_> {
         self.inner.client.get(&path, None).await
     }
 
+    pub async fn extended(&self, address: &str) -> ApiResult<AddressesContentExtendedResponse> {
+        let path = format!("addresses/{address}/extended");
+
+        self.inner.client.get(&path, None).await
+    }
+
+    pub async fn total(&self, address: &str) -> ApiResult<AddressesContentTotalResponse> {
+        let path = format!("addresses/{address}/total");
+
+        self.inner.client.get(&path, None).await
+    }
+
     pub async fn utxos(
         &self,
         address: &str,
@@ -1,6 +1,7 @@
 use crate::client::DataNode;
 use bf_api_provider::types::{
-    AssetsAddressesResponse, AssetsSingleResponse, AssetsTransactionsResponse,
+    AssetsAddressesResponse, AssetsHistoryResponse, AssetsPolicyResponse, AssetsResponse,
+    AssetsSingleResponse, AssetsTransactionsResponse,
 };
 use bf_common::{pagination::Pagination, types::ApiResult};
 
@@ -15,6 +16,10 @@ impl DataNode {
 }
 
 impl DataNodeAssets<'_> {
+    pub async fn list(&self, pagination: &Pagination) -> ApiResult<AssetsResponse> {
+        self.inner.client.get("assets", Some(pagination)).await
+    }
+
     pub async fn asset(&self, asset_id: &str) -> ApiResult<AssetsSingleResponse> {
         let path = format!("assets/{asset_id}");
 
@@ -40,4 +45,24 @@ impl DataNodeAssets<'_> {
 
         self.inner.client.get(&path, Some(pagination)).await
     }
+
+    pub async fn history(
+        &self,
+        asset_id: &str,
+        pagination: &Pagination,
+    ) -> ApiResult<AssetsHistoryResponse> {
+        let path = format!("assets/{asset_id}/history");
+
+        self.inner.client.get(&path, Some(pagination)).await
+    }
+
+    pub async fn policy(
+        &self,
+        policy_id: &str,
+        pagination: &Pagination,
+    ) -> ApiResult<AssetsPolicyResponse> {
+        let path = format!("assets/policy/{policy_id}");
+
+        self.inner.client.get(&path, Some(pagination)).await
+    }
 }
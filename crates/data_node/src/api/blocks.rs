@@ -1,5 +1,7 @@
 use crate::client::DataNode;
-use bf_api_provider::types::{BlocksResponse, BlocksSingleResponse};
+use bf_api_provider::types::{
+    BlocksAddressesContentResponse, BlocksResponse, BlocksSingleResponse,
+};
 use bf_common::{pagination::Pagination, types::ApiResult};
 
 pub struct DataNodeBlocks<'a> {
@@ -37,6 +39,16 @@ impl DataNodeBlocks<'_> {
         self.inner.client.get(&path, Some(pagination)).await
     }
 
+    pub async fn addresses(
+        &self,
+        hash_or_number: &str,
+        pagination: &Pagination,
+    ) -> ApiResult<BlocksAddressesContentResponse> {
+        let path = format!("blocks/{hash_or_number}/addresses");
+
+        self.inner.client.get(&path, Some(pagination)).await
+    }
+
     pub async fn previous(
         &self,
         hash_or_number: &str,
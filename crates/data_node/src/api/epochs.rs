@@ -1,5 +1,8 @@
 use crate::client::DataNode;
-use bf_api_provider::types::EpochsParamResponse;
+use bf_api_provider::types::{
+    EpochStakePoolResponse, EpochsListResponse, EpochsParamResponse, EpochsResponse,
+    EpochsStakeResponse,
+};
 use bf_common::{pagination::Pagination, types::ApiResult};
 
 pub struct DataNodeEpochs<'a> {
@@ -13,6 +16,57 @@ impl DataNode {
 }
 
 impl DataNodeEpochs<'_> {
+    pub async fn latest(&self) -> ApiResult<EpochsResponse> {
+        self.inner.client.get("epochs/latest", None).await
+    }
+
+    pub async fn by_number(&self, number: &i32) -> ApiResult<EpochsResponse> {
+        let path = format!("epochs/{number}");
+
+        self.inner.client.get(&path, None).await
+    }
+
+    pub async fn next(
+        &self,
+        number: &i32,
+        pagination: &Pagination,
+    ) -> ApiResult<EpochsListResponse> {
+        let path = format!("epochs/{number}/next");
+
+        self.inner.client.get(&path, Some(pagination)).await
+    }
+
+    pub async fn previous(
+        &self,
+        number: &i32,
+        pagination: &Pagination,
+    ) -> ApiResult<EpochsListResponse> {
+        let path = format!("epochs/{number}/previous");
+
+        self.inner.client.get(&path, Some(pagination)).await
+    }
+
+    pub async fn stakes(
+        &self,
+        number: &i32,
+        pagination: &Pagination,
+    ) -> ApiResult<EpochsStakeResponse> {
+        let path = format!("epochs/{number}/stakes");
+
+        self.inner.client.get(&path, Some(pagination)).await
+    }
+
+    pub async fn stakes_by_pool(
+        &self,
+        number: &i32,
+        pool_id: &str,
+        pagination: &Pagination,
+    ) -> ApiResult<EpochStakePoolResponse> {
+        let path = format!("epochs/{number}/stakes/{pool_id}");
+
+        self.inner.client.get(&path, Some(pagination)).await
+    }
+
     pub async fn parameters(&self, number: &i32) -> ApiResult<EpochsParamResponse> {
         let path = format!("epochs/{number}/parameters");
 
@@ -31,4 +85,15 @@ impl DataNodeEpochs<'_> {
 
         self.inner.client.get(&path, Some(pagination)).await
     }
+
+    pub async fn blocks_by_pool(
+        &self,
+        number: &i32,
+        pool_id: &str,
+        pagination: &Pagination,
+    ) -> ApiResult<Vec<String>> {
+        let path = format!("epochs/{number}/blocks/{pool_id}");
+
+        self.inner.client.get(&path, Some(pagination)).await
+    }
 }
@@ -1,6 +1,11 @@
 use crate::client::DataNode;
-use bf_api_provider::types::DrepsSingleResponse;
-use bf_common::types::ApiResult;
+use bf_api_provider::types::{
+    DrepsDelegatorsResponse, DrepsMetadataResponse, DrepsProposalMetadataResponse,
+    DrepsProposalParametersResponse, DrepsProposalVotesResponse, DrepsProposalWithdrawalsResponse,
+    DrepsProposalsResponse, DrepsResponse, DrepsSingleProposalResponse, DrepsSingleResponse,
+    DrepsUpdatesResponse, DrepsVotesResponse,
+};
+use bf_common::{pagination::Pagination, types::ApiResult};
 
 pub struct DataNodeGovernance<'a> {
     pub(crate) inner: &'a DataNode,
@@ -13,8 +18,110 @@ impl DataNode {
 }
 
 impl DataNodeGovernance<'_> {
+    pub async fn dreps(&self, pagination: &Pagination) -> ApiResult<DrepsResponse> {
+        self.inner
+            .client
+            .get("governance/dreps", Some(pagination))
+            .await
+    }
+
     pub async fn drep(&self, drep_id: &str) -> ApiResult<DrepsSingleResponse> {
         let path = format!("governance/dreps/{drep_id}");
         self.inner.client.get(&path, None).await
     }
+
+    pub async fn drep_delegators(
+        &self,
+        drep_id: &str,
+        pagination: &Pagination,
+    ) -> ApiResult<DrepsDelegatorsResponse> {
+        let path = format!("governance/dreps/{drep_id}/delegators");
+
+        self.inner.client.get(&path, Some(pagination)).await
+    }
+
+    pub async fn drep_metadata(&self, drep_id: &str) -> ApiResult<DrepsMetadataResponse> {
+        let path = format!("governance/dreps/{drep_id}/metadata");
+
+        self.inner.client.get(&path, None).await
+    }
+
+    pub async fn drep_updates(
+        &self,
+        drep_id: &str,
+        pagination: &Pagination,
+    ) -> ApiResult<DrepsUpdatesResponse> {
+        let path = format!("governance/dreps/{drep_id}/updates");
+
+        self.inner.client.get(&path, Some(pagination)).await
+    }
+
+    pub async fn drep_votes(
+        &self,
+        drep_id: &str,
+        pagination: &Pagination,
+    ) -> ApiResult<DrepsVotesResponse> {
+        let path = format!("governance/dreps/{drep_id}/votes");
+
+        self.inner.client.get(&path, Some(pagination)).await
+    }
+
+    pub async fn proposals(&self, pagination: &Pagination) -> ApiResult<DrepsProposalsResponse> {
+        self.inner
+            .client
+            .get("governance/proposals", Some(pagination))
+            .await
+    }
+
+    pub async fn proposal(
+        &self,
+        tx_hash: &str,
+        cert_index: u32,
+    ) -> ApiResult<DrepsSingleProposalResponse> {
+        let path = format!("governance/proposals/{tx_hash}/{cert_index}");
+
+        self.inner.client.get(&path, None).await
+    }
+
+    pub async fn proposal_parameters(
+        &self,
+        tx_hash: &str,
+        cert_index: u32,
+    ) -> ApiResult<DrepsProposalParametersResponse> {
+        let path = format!("governance/proposals/{tx_hash}/{cert_index}/parameters");
+
+        self.inner.client.get(&path, None).await
+    }
+
+    pub async fn proposal_withdrawals(
+        &self,
+        tx_hash: &str,
+        cert_index: u32,
+        pagination: &Pagination,
+    ) -> ApiResult<DrepsProposalWithdrawalsResponse> {
+        let path = format!("governance/proposals/{tx_hash}/{cert_index}/withdrawals");
+
+        self.inner.client.get(&path, Some(pagination)).await
+    }
+
+    pub async fn proposal_votes(
+        &self,
+        tx_hash: &str,
+        cert_index: u32,
+        pagination: &Pagination,
+    ) -> ApiResult<DrepsProposalVotesResponse> {
+        let path = format!("governance/proposals/{tx_hash}/{cert_index}/votes");
+
+        self.inner.client.get(&path, Some(pagination)).await
+    }
+
+    pub async fn proposal_metadata(
+        &self,
+        tx_hash: &str,
+        cert_index: u32,
+    ) -> ApiResult<DrepsProposalMetadataResponse> {
+        let path = format!("governance/proposals/{tx_hash}/{cert_index}/metadata");
+
+        self.inner.client.get(&path, None).await
+    }
 }
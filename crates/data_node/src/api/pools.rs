@@ -1,7 +1,8 @@
 use crate::client::DataNode;
 use bf_api_provider::types::{
     PoolsDelegatorsResponse, PoolsHistoryResponse, PoolsListExtendedResponse,
-    PoolsMetadataResponse, PoolsSingleResponse,
+    PoolsMetadataResponse, PoolsProposalVotesResponse, PoolsRetiresResponse, PoolsSingleResponse,
+    PoolsUpdatesResponse, TxsPoolCertsInnerRelaysResponse,
 };
 use bf_common::{pagination::Pagination, types::ApiResult};
 
@@ -16,6 +17,10 @@ impl DataNode {
 }
 
 impl DataNodePools<'_> {
+    pub async fn list(&self, pagination: &Pagination) -> ApiResult<Vec<String>> {
+        self.inner.client.get("pools", Some(pagination)).await
+    }
+
     pub async fn extended(&self, pagination: &Pagination) -> ApiResult<PoolsListExtendedResponse> {
         self.inner
             .client
@@ -54,4 +59,50 @@ impl DataNodePools<'_> {
 
         self.inner.client.get(&path, Some(pagination)).await
     }
+
+    pub async fn retired(&self, pagination: &Pagination) -> ApiResult<PoolsRetiresResponse> {
+        self.inner
+            .client
+            .get("pools/retired", Some(pagination))
+            .await
+    }
+
+    pub async fn retiring(&self, pagination: &Pagination) -> ApiResult<PoolsRetiresResponse> {
+        self.inner
+            .client
+            .get("pools/retiring", Some(pagination))
+            .await
+    }
+
+    pub async fn relays(&self, pool_id: &str) -> ApiResult<TxsPoolCertsInnerRelaysResponse> {
+        let path = format!("pools/{pool_id}/relays");
+
+        self.inner.client.get(&path, None).await
+    }
+
+    pub async fn blocks(&self, pool_id: &str, pagination: &Pagination) -> ApiResult<Vec<String>> {
+        let path = format!("pools/{pool_id}/blocks");
+
+        self.inner.client.get(&path, Some(pagination)).await
+    }
+
+    pub async fn updates(
+        &self,
+        pool_id: &str,
+        pagination: &Pagination,
+    ) -> ApiResult<PoolsUpdatesResponse> {
+        let path = format!("pools/{pool_id}/updates");
+
+        self.inner.client.get(&path, Some(pagination)).await
+    }
+
+    pub async fn votes(
+        &self,
+        pool_id: &str,
+        pagination: &Pagination,
+    ) -> ApiResult<PoolsProposalVotesResponse> {
+        let path = format!("pools/{pool_id}/votes");
+
+        self.inner.client.get(&path, Some(pagination)).await
+    }
 }
@@ -1,9 +1,9 @@
 use crate::client::DataNode;
 use bf_api_provider::types::{
-    ScriptsCborResponse, ScriptsDatumCborResponse, ScriptsDatumResponse, ScriptsJsonResponse,
-    ScriptsSingleResponse,
+    ScriptsCborResponse, ScriptsDatumCborResponse, ScriptsDatumResponse, ScriptsInnerResponse,
+    ScriptsJsonResponse, ScriptsRedeemersInnerResponse, ScriptsSingleResponse,
 };
-use bf_common::types::ApiResult;
+use bf_common::{pagination::Pagination, types::ApiResult};
 
 pub struct DataNodeScripts<'a> {
     pub(crate) inner: &'a DataNode,
@@ -16,6 +16,10 @@ impl DataNode {
 }
 
 impl DataNodeScripts<'_> {
+    pub async fn list(&self, pagination: &Pagination) -> ApiResult<ScriptsInnerResponse> {
+        self.inner.client.get("scripts", Some(pagination)).await
+    }
+
     pub async fn by(&self, script_hash: &str) -> ApiResult<ScriptsSingleResponse> {
         let path = format!("scripts/{script_hash}");
 
@@ -34,6 +38,16 @@ impl DataNodeScripts<'_> {
         self.inner.client.get(&path, None).await
     }
 
+    pub async fn redeemers(
+        &self,
+        script_hash: &str,
+        pagination: &Pagination,
+    ) -> ApiResult<ScriptsRedeemersInnerResponse> {
+        let path = format!("scripts/{script_hash}/redeemers");
+
+        self.inner.client.get(&path, Some(pagination)).await
+    }
+
     pub async fn datum(&self, datum_hash: &str) -> ApiResult<ScriptsDatumResponse> {
         let path = format!("scripts/datum/{datum_hash}");
 
@@ -1,8 +1,9 @@
 use crate::client::DataNode;
 use bf_api_provider::types::{
-    TxsCborResponse, TxsDelegationsResponse, TxsMetadataCborResponse, TxsMetadataResponse,
-    TxsMirsResponse, TxsPoolCertsResponse, TxsPoolRetiresResponse, TxsRedeemersResponse,
-    TxsSingleResponse, TxsStakeAddrResponse, TxsUtxosResponse, TxsWithdrawalsResponse,
+    TxsCborResponse, TxsContentRequiredSignersInner, TxsDelegationsResponse,
+    TxsMetadataCborResponse, TxsMetadataResponse, TxsMirsResponse, TxsPoolCertsResponse,
+    TxsPoolRetiresResponse, TxsRedeemersResponse, TxsSingleResponse, TxsStakeAddrResponse,
+    TxsUtxosResponse, TxsWithdrawalsResponse,
 };
 use bf_common::{pagination::Pagination, types::ApiResult};
 
@@ -117,4 +118,10 @@ impl DataNodeTxs<'_> {
         let path = format!("txs/{hash}/stakes");
         self.inner.client.get(&path, Some(pagination)).await
     }
+
+    pub async fn required_signers(&self, hash: &str) -> ApiResult<TxsContentRequiredSignersInner> {
+        let path = format!("txs/{hash}/required_signers");
+
+        self.inner.client.get(&path, None).await
+    }
 }
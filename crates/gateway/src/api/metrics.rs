@@ -294,6 +294,7 @@ mod tests {
             do_finish,
             requests_in_progress: Arc::new(Mutex::new(HashMap::new())),
             network_rtt: Arc::new(Mutex::new(None)),
+            latency_ewma: Arc::new(Mutex::new(None)),
             connected_since: std::time::Instant::now(),
             requests_sent: Arc::new(atomic::AtomicU64::new(0)),
             responses_received: Arc::new(atomic::AtomicU64::new(0)),
@@ -312,6 +313,7 @@ mod tests {
             healthy: true,
             version: Some("1.2.3".to_string()),
             has_data_node: Some(false),
+            capabilities: None,
         });
         lb.active_relays.lock().await.insert(uuid, relay);
 
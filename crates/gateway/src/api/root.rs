@@ -59,6 +59,7 @@ mod tests {
                 project_id: "previewXXX".to_string(),
                 nft_asset: "asset".to_string(),
             },
+            load_balancer: Default::default(),
             hydra_platform: None,
             hydra_bridge: None,
         }
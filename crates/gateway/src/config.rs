@@ -1,3 +1,4 @@
+use crate::relay_selection::SelectionStrategy;
 use crate::types::Network;
 use anyhow::{Result, bail};
 use clap::Parser;
@@ -83,6 +84,8 @@ pub struct ConfigInput {
     pub server: ServerInput,
     pub database: DbInput,
     pub blockfrost: BlockfrostInput,
+    #[serde(default)]
+    pub load_balancer: LoadBalancer,
     pub hydra_platform: Option<HydraConfig>,
     pub hydra_bridge: Option<HydraConfig>,
 }
@@ -92,6 +95,7 @@ pub struct Config {
     pub server: Server,
     pub database: Db,
     pub blockfrost: Blockfrost,
+    pub load_balancer: LoadBalancer,
     pub hydra_platform: Option<HydraConfig>,
     pub hydra_bridge: Option<HydraConfig>,
 }
@@ -102,6 +106,48 @@ pub struct Blockfrost {
     pub nft_asset: String,
 }
 
+#[derive(Debug, Deserialize, Clone)]
+pub struct LoadBalancer {
+    /// How `/any/*` requests pick one of the connected relays.
+    #[serde(default)]
+    pub strategy: SelectionStrategy,
+    /// How many times a `GET /any/*` request is retried on another relay,
+    /// after the previous one disconnected or timed out.
+    #[serde(default = "default_retry_budget")]
+    pub retry_budget: u32,
+    /// Time limit for a single attempt of a `GET /any/*` request, before it’s
+    /// retried on another relay. By default, it’s the whole deadline, so that
+    /// slow endpoints aren’t cut short, and only disconnects are retried.
+    #[serde(default = "default_attempt_timeout_seconds")]
+    pub attempt_timeout_seconds: u64,
+    /// Overall time limit for all attempts of a single `GET /any/*` request.
+    #[serde(default = "default_retry_deadline_seconds")]
+    pub retry_deadline_seconds: u64,
+}
+
+impl Default for LoadBalancer {
+    fn default() -> Self {
+        LoadBalancer {
+            strategy: SelectionStrategy::default(),
+            retry_budget: default_retry_budget(),
+            attempt_timeout_seconds: default_attempt_timeout_seconds(),
+            retry_deadline_seconds: default_retry_deadline_seconds(),
+        }
+    }
+}
+
+fn default_retry_budget() -> u32 {
+    2
+}
+
+fn default_attempt_timeout_seconds() -> u64 {
+    60
+}
+
+fn default_retry_deadline_seconds() -> u64 {
+    60
+}
+
 #[derive(Debug, Deserialize, Clone)]
 pub struct HydraConfig {
     pub cardano_signing_key: PathBuf,
@@ -148,6 +194,8 @@ pub fn load_config(path: PathBuf) -> Config {
 
     let network = network_from_project_id(&project_id).expect("invalid Blockfrost project_id");
 
+    validate_load_balancer(&toml_config.load_balancer);
+
     let peer_urls = toml_config.server.peer_urls;
     for u in &peer_urls {
         validate_server_url(u);
@@ -179,6 +227,7 @@ pub fn load_config(path: PathBuf) -> Config {
             project_id,
             nft_asset: toml_config.blockfrost.nft_asset,
         },
+        load_balancer: toml_config.load_balancer,
         hydra_platform: toml_config.hydra_platform,
         hydra_bridge: toml_config.hydra_bridge,
     };
@@ -211,6 +260,23 @@ fn validate_server_url(url: &url::Url) {
     );
 }
 
+fn validate_load_balancer(lb: &LoadBalancer) {
+    assert!(
+        lb.attempt_timeout_seconds > 0,
+        "load_balancer.attempt_timeout_seconds must be greater than 0"
+    );
+    assert!(
+        lb.retry_deadline_seconds > 0,
+        "load_balancer.retry_deadline_seconds must be greater than 0"
+    );
+    assert!(
+        lb.attempt_timeout_seconds <= lb.retry_deadline_seconds,
+        "load_balancer.attempt_timeout_seconds ({}) must not exceed retry_deadline_seconds ({})",
+        lb.attempt_timeout_seconds,
+        lb.retry_deadline_seconds,
+    );
+}
+
 /// Parse a raw string into a [`url::Url`] and validate it.
 /// Used for environment variable overrides of server URLs.
 fn parse_server_url(raw: &str, env_var: &str) -> url::Url {
@@ -295,6 +361,7 @@ fn override_with_env(config: Config) -> Config {
             project_id,
             nft_asset,
         },
+        load_balancer: config.load_balancer,
         hydra_platform: config.hydra_platform,
         hydra_bridge: config.hydra_bridge,
     }
@@ -354,4 +421,40 @@ mod tests {
         let db: DbInput = toml::from_str(toml).expect("valid pool_max_size must parse");
         assert_eq!(db.pool_max_size.get(), 6);
     }
+
+    #[test]
+    fn load_balancer_defaults() {
+        let lb: LoadBalancer = toml::from_str("").expect("empty section must parse");
+        assert_eq!(lb.strategy, SelectionStrategy::RoundRobin);
+        assert_eq!(lb.retry_budget, 2);
+        assert_eq!(lb.attempt_timeout_seconds, 60);
+        assert_eq!(lb.retry_deadline_seconds, 60);
+        validate_load_balancer(&lb);
+
+        let lb: LoadBalancer =
+            toml::from_str("strategy = 'ewma_latency'").expect("valid strategy must parse");
+        assert_eq!(lb.strategy, SelectionStrategy::EwmaLatency);
+    }
+
+    #[test]
+    #[should_panic(expected = "load_balancer.attempt_timeout_seconds must be greater than 0")]
+    fn load_balancer_rejects_zero_attempt_timeout() {
+        let lb: LoadBalancer = toml::from_str("attempt_timeout_seconds = 0").unwrap();
+        validate_load_balancer(&lb);
+    }
+
+    #[test]
+    #[should_panic(expected = "load_balancer.retry_deadline_seconds must be greater than 0")]
+    fn load_balancer_rejects_zero_retry_deadline() {
+        let lb: LoadBalancer = toml::from_str("retry_deadline_seconds = 0").unwrap();
+        validate_load_balancer(&lb);
+    }
+
+    #[test]
+    #[should_panic(expected = "must not exceed retry_deadline_seconds")]
+    fn load_balancer_rejects_attempt_timeout_over_deadline() {
+        let lb: LoadBalancer =
+            toml::from_str("attempt_timeout_seconds = 30\nretry_deadline_seconds = 20").unwrap();
+        validate_load_balancer(&lb);
+    }
 }
@@ -11,6 +11,7 @@ pub mod middlewares;
 pub mod models;
 pub mod payload;
 pub mod rate_limit;
+pub mod relay_selection;
 pub mod schema;
 pub mod sdk_bridge_ws;
 pub mod types;
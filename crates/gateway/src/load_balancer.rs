@@ -1,6 +1,8 @@
 use crate::errors::APIError;
 use crate::hydra_server_platform;
+use crate::relay_selection::{RelayLoad, SelectionStrategy, update_ewma};
 use crate::types::AssetName;
+use bf_common::route_groups::{Backend, RouteGroup};
 use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, HashMap};
 use std::sync::{Arc, atomic};
@@ -16,6 +18,7 @@ const REQUEST_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(60);
 const WS_PING_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(15);
 const HEALTH_CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(30);
 const HEALTH_CHECK_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(15);
+const RELAY_ATTEMPTS_HEADER: &str = "x-blockfrost-relay-attempts";
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct AccessToken(pub String);
@@ -23,7 +26,7 @@ pub struct AccessToken(pub String);
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct RequestId(Uuid);
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct JsonRequest {
     pub id: RequestId,
     method: JsonRequestMethod,
@@ -41,14 +44,14 @@ pub struct JsonResponse {
     pub body_base64: String,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct JsonHeader {
     name: String,
     value: String,
 }
 
 #[allow(clippy::upper_case_acronyms)]
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub enum JsonRequestMethod {
     GET,
     POST,
@@ -79,6 +82,17 @@ pub enum RelayMessage {
 pub struct LoadBalancerState {
     pub active_relays: Arc<Mutex<BTreeMap<Uuid, RelayState>>>,
     pub any_relay_cursor: Arc<atomic::AtomicU64>,
+    pub selection_strategy: SelectionStrategy,
+    /// How many times an idempotent `/any/*` request can be retried on another
+    /// relay, after the one it was sent to disconnected or timed out.
+    pub retry_budget: u32,
+    /// Time limit for a single attempt of a retryable `/any/*` request. It has
+    /// to be shorter than `retry_deadline` for a hanging relay to leave any
+    /// time for a retry.
+    pub attempt_timeout: std::time::Duration,
+    /// Overall time limit for all attempts of a single retryable `/any/*`
+    /// request. Other requests get the whole `REQUEST_TIMEOUT`.
+    pub retry_deadline: std::time::Duration,
     pub hydras: Option<hydra_server_platform::HydrasManager>,
     /// 32-byte key for stateless keyed-hash tokens.
     peer_secret: [u8; 32],
@@ -101,6 +115,9 @@ pub struct RelayState {
     pub do_finish: mpsc::Sender<String>,
     pub requests_in_progress: Arc<Mutex<HashMap<RequestId, RequestState>>>,
     pub network_rtt: Arc<Mutex<Option<std::time::Duration>>>,
+    /// Moving average of how long the relay takes to answer proxied requests,
+    /// `None` until it answers the first one.
+    pub latency_ewma: Arc<Mutex<Option<std::time::Duration>>>,
     pub connected_since: std::time::Instant,
     pub requests_sent: Arc<atomic::AtomicU64>,
     pub responses_received: Arc<atomic::AtomicU64>,
@@ -114,6 +131,9 @@ pub struct PlatformHealth {
     pub healthy: bool,
     pub version: Option<String>,
     pub has_data_node: Option<bool>,
+    /// Route groups advertised in `GET /`, `None` for Platforms too old to
+    /// advertise them.
+    pub capabilities: Option<Vec<RouteGroup>>,
 }
 
 impl PlatformHealth {
@@ -122,14 +142,61 @@ impl PlatformHealth {
             healthy: false,
             version: None,
             has_data_node: None,
+            capabilities: None,
+        }
+    }
+
+    /// Whether the Platform can serve requests of this [`RouteGroup`]. Older
+    /// Platforms don’t advertise their capabilities, so for them we fall back
+    /// on whether they have a data node.
+    fn can_serve(&self, group: RouteGroup) -> bool {
+        match &self.capabilities {
+            Some(capabilities) => capabilities.contains(&group),
+            None => group.backend() != Backend::DataNode || self.has_data_node == Some(true),
+        }
+    }
+}
+
+impl RelayState {
+    /// Paths outside of all route groups (e.g. `/metrics`) are served by
+    /// every Platform, and so are all paths until the first health check
+    /// completes, because we can’t know any better yet.
+    async fn can_serve(&self, path: &str) -> bool {
+        let Some(group) = RouteGroup::of_path(path) else {
+            return true;
+        };
+
+        match &*self.platform_health.lock().await {
+            Some(health) => health.can_serve(group),
+            None => true,
+        }
+    }
+
+    /// Only when the latest health check failed, i.e. not before the first one.
+    async fn is_unhealthy(&self) -> bool {
+        self.platform_health
+            .lock()
+            .await
+            .as_ref()
+            .is_some_and(|health| !health.healthy)
+    }
+
+    async fn load(&self) -> RelayLoad {
+        RelayLoad {
+            requests_in_progress: self.requests_in_progress.lock().await.len() as u64,
+            latency_ewma: *self.latency_ewma.lock().await,
         }
     }
 }
 
 #[derive(Debug)]
 pub struct RequestState {
-    respond_to: oneshot::Sender<JsonResponse>,
+    /// An `Err(_)` means that the Gateway itself failed the request, e.g.
+    /// because the relay disconnected, as opposed to a regular response of the
+    /// Platform, even if unsuccessful.
+    respond_to: oneshot::Sender<Result<JsonResponse, (hyper::StatusCode, String)>>,
     expires: std::time::Instant,
+    created_at: std::time::Instant,
     underlying: JsonRequest,
     is_health_check: bool,
 }
@@ -145,11 +212,50 @@ impl LoadBalancerState {
         LoadBalancerState {
             active_relays,
             any_relay_cursor,
+            selection_strategy: SelectionStrategy::default(),
+            retry_budget: 2,
+            attempt_timeout: REQUEST_TIMEOUT,
+            retry_deadline: REQUEST_TIMEOUT,
             hydras,
             peer_secret,
         }
     }
 
+    pub fn with_selection_strategy(mut self, selection_strategy: SelectionStrategy) -> Self {
+        self.selection_strategy = selection_strategy;
+        self
+    }
+
+    pub fn with_retries(
+        mut self,
+        retry_budget: u32,
+        attempt_timeout: std::time::Duration,
+        retry_deadline: std::time::Duration,
+    ) -> Self {
+        self.retry_budget = retry_budget;
+        self.attempt_timeout = attempt_timeout;
+        self.retry_deadline = retry_deadline;
+        self
+    }
+
+    /// Feeds a failed `/any/*` request into the relay’s latency average, as if
+    /// it took `penalty`, so that [`SelectionStrategy::EwmaLatency`] stops
+    /// preferring a relay that times out or drops requests.
+    pub async fn penalize(&self, relay_name: &AssetName, penalty: std::time::Duration) {
+        let relay_state = self
+            .active_relays
+            .lock()
+            .await
+            .values()
+            .find(|relay_state| &relay_state.name == relay_name)
+            .cloned();
+
+        if let Some(relay_state) = relay_state {
+            let mut latency_ewma = relay_state.latency_ewma.lock().await;
+            *latency_ewma = Some(update_ewma(*latency_ewma, penalty));
+        }
+    }
+
     async fn relay_for_prefix(
         &self,
         api_prefix: Uuid,
@@ -168,36 +274,55 @@ impl LoadBalancerState {
             .map(|rs| (rs.new_request_channel.clone(), rs.name.clone()))
     }
 
+    /// Relays named in `excluded` are never picked, e.g. those that have just
+    /// failed the same request.
     async fn relay_for_any(
         &self,
         rest: &str,
+        excluded: &[AssetName],
     ) -> Result<(mpsc::Sender<RequestState>, AssetName), (hyper::StatusCode, String)> {
         let active_relays = self.active_relays.lock().await;
-        let n = active_relays.len();
 
-        if n == 0 {
+        if active_relays.is_empty() {
             return Err((
                 hyper::StatusCode::NOT_FOUND,
                 format!("no relays connected for request: {rest}"),
             ));
         }
 
+        // `BTreeMap` values are already sorted by UUID, which gives the
+        // strategies a deterministic order of candidates:
+        let mut candidates: Vec<(&RelayState, RelayLoad)> = Vec::new();
+        for relay_state in active_relays.values() {
+            if !excluded.contains(&relay_state.name)
+                && !relay_state.is_unhealthy().await
+                && relay_state.can_serve(rest).await
+            {
+                candidates.push((relay_state, relay_state.load().await));
+            }
+        }
+
+        let loads: Vec<RelayLoad> = candidates.iter().map(|(_, load)| *load).collect();
         let request_count = self
             .any_relay_cursor
             .fetch_add(1, atomic::Ordering::Relaxed);
-        let idx = (request_count % n as u64) as usize;
 
-        // `BTreeMap` keys are already sorted, so `nth(idx)` gives us a
-        // deterministic round-robin order:
-        let api_prefix = *active_relays
-            .keys()
-            .nth(idx)
-            .expect("non-empty relay map must yield a key");
-
-        Ok(active_relays
-            .get(&api_prefix)
-            .map(|rs| (rs.new_request_channel.clone(), rs.name.clone()))
-            .expect("selected relay must exist in active_relays"))
+        match self
+            .selection_strategy
+            .pick(&loads, request_count, Uuid::new_v4().as_u128())
+        {
+            Some(idx) => Ok((
+                candidates[idx].0.new_request_channel.clone(),
+                candidates[idx].0.name.clone(),
+            )),
+            None => Err((
+                hyper::StatusCode::SERVICE_UNAVAILABLE,
+                format!(
+                    "none of the {} connected relays is healthy and can serve request: {rest}",
+                    active_relays.len()
+                ),
+            )),
+        }
     }
 
     /// Create a stateless token: `base64url(payload) + "." + hex(blake3_keyed_hash)`.
@@ -350,6 +475,7 @@ pub mod api {
     struct RelayStats {
         api_prefix: Uuid,
         network_rtt_seconds: Option<f64>,
+        latency_ewma_seconds: Option<f64>,
         connected_since: chrono::DateTime<chrono::Utc>,
         requests_sent: u64,
         responses_received: u64,
@@ -358,6 +484,10 @@ pub mod api {
         healthy: Option<bool>,
         version: Option<String>,
         has_data_node: Option<bool>,
+        capabilities: Option<Vec<RouteGroup>>,
+        /// How much the configured [`SelectionStrategy`] prefers the relay for
+        /// the next `/any/*` request, see [`SelectionStrategy::preferences`].
+        selection_preference: f64,
     }
 
     /// This route shows some stats about all relays connected with a WebSocket,
@@ -377,7 +507,29 @@ pub mod api {
             .map(|(api_prefix, relay_state)| (*api_prefix, relay_state.clone()))
             .collect();
 
-        for (api_prefix, relay_state) in &snapshot {
+        // Unhealthy relays are skipped, so their preference is 0. We don’t know the
+        // path here, so capabilities are not taken into account:
+        let mut loads: Vec<Option<RelayLoad>> = Vec::with_capacity(snapshot.len());
+        for (_, relay_state) in &snapshot {
+            let load = relay_state.load().await;
+            loads.push((!relay_state.is_unhealthy().await).then_some(load));
+        }
+        let healthy_loads: Vec<RelayLoad> = loads.iter().flatten().copied().collect();
+        let mut healthy_preferences = load_balancer
+            .selection_strategy
+            .preferences(&healthy_loads)
+            .into_iter();
+        let selection_preferences: Vec<f64> = loads
+            .iter()
+            .map(|load| match load {
+                Some(_) => healthy_preferences.next().unwrap_or(0.0),
+                None => 0.0,
+            })
+            .collect();
+
+        for ((api_prefix, relay_state), selection_preference) in
+            snapshot.iter().zip(selection_preferences)
+        {
             let platform_health = relay_state.platform_health.lock().await.clone();
             rv.insert(
                 relay_state.name.clone(),
@@ -388,6 +540,11 @@ pub mod api {
                         .lock()
                         .await
                         .map(|a| a.as_secs_f64()),
+                    latency_ewma_seconds: relay_state
+                        .latency_ewma
+                        .lock()
+                        .await
+                        .map(|a| a.as_secs_f64()),
                     connected_since: now_chrono - (now_instant - relay_state.connected_since),
                     requests_sent: relay_state.requests_sent.load(atomic::Ordering::SeqCst),
                     responses_received: relay_state
@@ -397,7 +554,9 @@ pub mod api {
                         as u64,
                     healthy: platform_health.as_ref().map(|h| h.healthy),
                     version: platform_health.as_ref().and_then(|h| h.version.clone()),
-                    has_data_node: platform_health.and_then(|h| h.has_data_node),
+                    has_data_node: platform_health.as_ref().and_then(|h| h.has_data_node),
+                    capabilities: platform_health.and_then(|h| h.capabilities),
+                    selection_preference,
                 },
             );
         }
@@ -441,27 +600,114 @@ pub mod api {
     }
 
     /// This route handles requests directed at any active relay. The relay is
-    /// picked by sorting all connected UUIDs and selecting the next one via a
-    /// shared round-robin counter.
+    /// picked among those whose Platform is healthy and can serve the path,
+    /// by the configured [`SelectionStrategy`]. If there are none, it’s a 503.
+    ///
+    /// When the relay disconnects or doesn’t answer within the attempt timeout,
+    /// idempotent (i.e. `GET`) requests are retried on another one, within the
+    /// configured budget and deadline. The number of attempts is returned in the
+    /// `x-blockfrost-relay-attempts` header.
     async fn handle_any_route(
         load_balancer: LoadBalancerState,
         rest: String,
         req: Request,
     ) -> Result<impl IntoResponse, APIError> {
-        let rv: Result<hyper::Response<axum::body::Body>, (StatusCode, String)> = async move {
-            let (new_request_channel, relay_name) = load_balancer.relay_for_any(&rest).await?;
+        let mut attempts: u32 = 0;
 
-            forward_request(new_request_channel, relay_name, rest, req).await
+        let rv: Result<hyper::Response<axum::body::Body>, (StatusCode, String)> = async {
+            let deadline = std::time::Instant::now() + load_balancer.retry_deadline;
+
+            let (mut new_request_channel, mut relay_name) =
+                load_balancer.relay_for_any(&rest, &[]).await?;
+
+            let query = req.uri().query().map(ToString::to_string);
+            let json_req = request_to_json(req, rest.clone(), query, &relay_name).await?;
+            let retryable = is_retryable(&json_req);
+            let mut failed_relays: Vec<AssetName> = Vec::new();
+
+            loop {
+                attempts += 1;
+
+                let mut attempt = json_req.clone();
+                attempt.id = RequestId(Uuid::new_v4());
+                let timeout = if retryable {
+                    deadline
+                        .saturating_duration_since(std::time::Instant::now())
+                        .min(load_balancer.attempt_timeout)
+                } else {
+                    REQUEST_TIMEOUT
+                };
+
+                let result = forward_json_request(
+                    new_request_channel,
+                    relay_name.clone(),
+                    &rest,
+                    attempt,
+                    timeout,
+                )
+                .await;
+
+                if let Err((code, _)) = &result
+                    && is_relay_failure(*code)
+                {
+                    load_balancer.penalize(&relay_name, timeout).await;
+                }
+
+                let (code, reason) = match result {
+                    Err((code, reason))
+                        if retryable
+                            && is_relay_failure(code)
+                            && attempts <= load_balancer.retry_budget
+                            && std::time::Instant::now() < deadline =>
+                    {
+                        (code, reason)
+                    },
+                    other => return other,
+                };
+
+                warn!(
+                    "{}: retrying on another relay after attempt {attempts}, because: {code}: {reason}",
+                    relay_name.as_str(),
+                );
+                failed_relays.push(relay_name);
+
+                // With no other relay to try, the client is better off with
+                // the actual failure than with a 503:
+                (new_request_channel, relay_name) = load_balancer
+                    .relay_for_any(&rest, &failed_relays)
+                    .await
+                    .map_err(|_| (code, reason))?;
+            }
         }
         .await;
 
-        match rv {
-            Ok(resp) => Ok(resp),
+        let mut response = match rv {
+            Ok(resp) => resp,
             Err((code, reason)) => {
                 error!("returning {}, because: {}", code, reason);
-                Ok((code, reason).into_response())
+                (code, reason).into_response()
             },
+        };
+
+        if attempts > 0 {
+            response
+                .headers_mut()
+                .insert(RELAY_ATTEMPTS_HEADER, attempts.into());
         }
+
+        Ok(response)
+    }
+
+    /// Only `GET` requests are retried, so e.g. `POST /tx/submit` never is, as
+    /// it could end up submitted twice.
+    fn is_retryable(json_req: &JsonRequest) -> bool {
+        json_req.method == JsonRequestMethod::GET
+    }
+
+    /// Whether the failure came from the relay connection rather than from the
+    /// Platform, see [`RequestState::respond_to`].
+    fn is_relay_failure(code: StatusCode) -> bool {
+        code == StatusCode::BAD_GATEWAY || code == StatusCode::GATEWAY_TIMEOUT
     }
 
     async fn forward_request(
@@ -473,10 +719,28 @@ pub mod api {
         let query = req.uri().query().map(ToString::to_string);
         let json_req = request_to_json(req, rest.clone(), query, &relay_name).await?;
 
-        let (response_tx, response_rx) = oneshot::channel::<JsonResponse>();
+        forward_json_request(
+            new_request_channel,
+            relay_name,
+            &rest,
+            json_req,
+            REQUEST_TIMEOUT,
+        )
+        .await
+    }
+
+    async fn forward_json_request(
+        new_request_channel: mpsc::Sender<RequestState>,
+        relay_name: AssetName,
+        rest: &str,
+        json_req: JsonRequest,
+        timeout: std::time::Duration,
+    ) -> Result<hyper::Response<axum::body::Body>, (StatusCode, String)> {
+        let (response_tx, response_rx) = oneshot::channel();
 
         let new_request = RequestState {
-            expires: std::time::Instant::now() + REQUEST_TIMEOUT,
+            expires: std::time::Instant::now() + timeout,
+            created_at: std::time::Instant::now(),
             respond_to: response_tx,
             underlying: json_req,
             is_health_check: false,
@@ -493,8 +757,9 @@ pub mod api {
             )
         })?;
 
-        match tokio::time::timeout(REQUEST_TIMEOUT, response_rx).await {
-            Ok(Ok(response)) => json_to_response(response, &relay_name).await,
+        match tokio::time::timeout(timeout, response_rx).await {
+            Ok(Ok(Ok(response))) => json_to_response(response, &relay_name).await,
+            Ok(Ok(Err(failure))) => Err(failure),
             Ok(Err(_)) => {
                 // sender dropped
                 Err((
@@ -556,6 +821,7 @@ pub mod event_loop {
             do_finish: finish_tx,
             requests_in_progress: Arc::new(Mutex::new(HashMap::new())),
             network_rtt: Arc::new(Mutex::new(None)),
+            latency_ewma: Arc::new(Mutex::new(None)),
             connected_since: std::time::Instant::now(),
             requests_sent: Arc::new(atomic::AtomicU64::new(0)),
             responses_received: Arc::new(atomic::AtomicU64::new(0)),
@@ -1145,7 +1411,14 @@ pub mod event_loop {
                 relay_state
                     .responses_received
                     .fetch_add(1, atomic::Ordering::SeqCst);
-                match request_state.respond_to.send(response) {
+                if !is_health_check {
+                    let mut latency_ewma = relay_state.latency_ewma.lock().await;
+                    *latency_ewma = Some(update_ewma(
+                        *latency_ewma,
+                        request_state.created_at.elapsed(),
+                    ));
+                }
+                match request_state.respond_to.send(Ok(response)) {
                     Ok(_) => (),
                     Err(_) => warn!(
                         "{}: received response after its request timed out: {}",
@@ -1203,7 +1476,7 @@ pub mod event_loop {
                     .await
                     .remove(&request_id)
                 {
-                    fail_request(request, StatusCode::BAD_REQUEST, &err, asset_name).await;
+                    fail_request(request, StatusCode::BAD_GATEWAY, &err, asset_name).await;
                 }
 
                 // break 'event_loop
@@ -1227,15 +1500,9 @@ pub mod event_loop {
             why,
             request.underlying,
         );
-        use base64::{Engine as _, engine::general_purpose};
         let _ignored_failure: Result<_, _> = request
             .respond_to
-            .send(JsonResponse {
-                id: request_id.clone(),
-                code: code.as_u16(),
-                header: vec![],
-                body_base64: general_purpose::STANDARD.encode(why.as_bytes()),
-            })
+            .send(Err((code, why.to_string())))
             .inspect_err(|_| {
                 warn!(
                     "{}: tried to fail a request after said request timed out: {}",
@@ -1250,6 +1517,9 @@ pub mod event_loop {
 struct PlatformRootResponse {
     version: Option<String>,
     data_node: Option<serde::de::IgnoredAny>,
+    /// Kept as raw values, so that a single group unknown to this version of
+    /// the Gateway doesn’t make us lose all the others.
+    capabilities: Option<Vec<serde_json::Value>>,
 }
 
 /// A background task to periodically check the relay’s platform over the
@@ -1275,6 +1545,7 @@ async fn check_platform_health(new_request_channel: &mpsc::Sender<RequestState>)
         let request = RequestState {
             respond_to: response_tx,
             expires: std::time::Instant::now() + HEALTH_CHECK_TIMEOUT,
+            created_at: std::time::Instant::now(),
             underlying: JsonRequest {
                 id: RequestId(Uuid::new_v4()),
                 method: JsonRequestMethod::GET,
@@ -1291,8 +1562,8 @@ async fn check_platform_health(new_request_channel: &mpsc::Sender<RequestState>)
         }
 
         match response_rx.await {
-            Ok(response) => interpret_health_response(&response),
-            Err(_) => PlatformHealth::unreachable(),
+            Ok(Ok(response)) => interpret_health_response(&response),
+            Ok(Err(_)) | Err(_) => PlatformHealth::unreachable(),
         }
     })
     .await
@@ -1311,6 +1582,12 @@ fn interpret_health_response(response: &JsonResponse) -> PlatformHealth {
         healthy: response.code == 200,
         version: body.as_ref().and_then(|b| b.version.clone()),
         has_data_node: body.as_ref().map(|b| b.data_node.is_some()),
+        capabilities: body.and_then(|b| b.capabilities).map(|capabilities| {
+            capabilities
+                .into_iter()
+                .filter_map(|group| serde_json::from_value(group).ok())
+                .collect()
+        }),
     }
 }
 
@@ -1444,6 +1721,7 @@ mod tests {
             do_finish,
             requests_in_progress: Arc::new(Mutex::new(HashMap::new())),
             network_rtt: Arc::new(Mutex::new(None)),
+            latency_ewma: Arc::new(Mutex::new(None)),
             connected_since: std::time::Instant::now(),
             requests_sent: Arc::new(atomic::AtomicU64::new(0)),
             responses_received: Arc::new(atomic::AtomicU64::new(0)),
@@ -1451,6 +1729,27 @@ mod tests {
         }
     }
 
+    /// A relay answering every request with `code`.
+    fn test_responding_relay_state(name: &str, code: u16) -> RelayState {
+        let (new_request_channel, mut request_rx) = mpsc::channel::<RequestState>(1);
+
+        tokio::spawn(async move {
+            while let Some(request) = request_rx.recv().await {
+                let _ignored_failure: Result<_, _> = request.respond_to.send(Ok(JsonResponse {
+                    id: request.underlying.id.clone(),
+                    code,
+                    header: vec![],
+                    body_base64: String::new(),
+                }));
+            }
+        });
+
+        RelayState {
+            new_request_channel,
+            ..test_relay_state(name)
+        }
+    }
+
     fn test_key() -> [u8; 32] {
         *blake3::hash(b"test-peer-secret").as_bytes()
     }
@@ -1497,23 +1796,333 @@ mod tests {
             .insert(second, test_relay_state("second"));
 
         assert_eq!(
-            lb.relay_for_any("/metrics").await.unwrap().1.as_str(),
+            lb.relay_for_any("/metrics", &[]).await.unwrap().1.as_str(),
             "first"
         );
         assert_eq!(
-            lb.relay_for_any("/metrics").await.unwrap().1.as_str(),
+            lb.relay_for_any("/metrics", &[]).await.unwrap().1.as_str(),
             "second"
         );
         assert_eq!(
-            lb.relay_for_any("/metrics").await.unwrap().1.as_str(),
+            lb.relay_for_any("/metrics", &[]).await.unwrap().1.as_str(),
             "third"
         );
         assert_eq!(
-            lb.relay_for_any("/metrics").await.unwrap().1.as_str(),
+            lb.relay_for_any("/metrics", &[]).await.unwrap().1.as_str(),
             "first"
         );
     }
 
+    fn test_health(
+        has_data_node: Option<bool>,
+        capabilities: Option<Vec<RouteGroup>>,
+    ) -> Option<PlatformHealth> {
+        Some(PlatformHealth {
+            healthy: true,
+            version: Some("1.0.0".to_string()),
+            has_data_node,
+            capabilities,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_relay_for_any_skips_incapable_relays() {
+        let lb = LoadBalancerState::new(None, test_key());
+        let compact = test_relay_state("compact");
+        let full = test_relay_state("full");
+        let legacy = test_relay_state("legacy");
+
+        *compact.platform_health.lock().await = test_health(
+            Some(false),
+            Some(vec![RouteGroup::TxSubmit, RouteGroup::LedgerState]),
+        );
+        *full.platform_health.lock().await =
+            test_health(Some(true), Some(RouteGroup::ALL.to_vec()));
+        // Predates capabilities, and has no data node:
+        *legacy.platform_health.lock().await = test_health(Some(false), None);
+
+        for (n, relay_state) in [compact, full, legacy].into_iter().enumerate() {
+            lb.active_relays
+                .lock()
+                .await
+                .insert(Uuid::from_u128(n as u128 + 1), relay_state);
+        }
+
+        for _ in 0..3 {
+            assert_eq!(
+                lb.relay_for_any("/addresses/addr1/transactions", &[])
+                    .await
+                    .unwrap()
+                    .1
+                    .as_str(),
+                "full"
+            );
+        }
+
+        let mut names: Vec<String> = Vec::new();
+        for _ in 0..3 {
+            names.push(
+                lb.relay_for_any("/tx/submit", &[])
+                    .await
+                    .unwrap()
+                    .1
+                    .as_str()
+                    .to_string(),
+            );
+        }
+        names.sort();
+        assert_eq!(names, vec!["compact", "full", "legacy"]);
+    }
+
+    #[tokio::test]
+    async fn test_relay_for_any_unavailable_without_capable_relays() {
+        let lb = LoadBalancerState::new(None, test_key());
+        let compact = test_relay_state("compact");
+        let unchecked = test_relay_state("unchecked");
+
+        *compact.platform_health.lock().await =
+            test_health(Some(false), Some(vec![RouteGroup::TxSubmit]));
+
+        lb.active_relays
+            .lock()
+            .await
+            .insert(Uuid::from_u128(1), compact);
+
+        let (code, _) = lb.relay_for_any("/blocks/latest", &[]).await.unwrap_err();
+        assert_eq!(code, hyper::StatusCode::SERVICE_UNAVAILABLE);
+
+        // Paths outside of all groups are still served:
+        assert!(lb.relay_for_any("/metrics", &[]).await.is_ok());
+
+        // And relays without a completed health check are given the benefit of the doubt:
+        lb.active_relays
+            .lock()
+            .await
+            .insert(Uuid::from_u128(2), unchecked);
+        assert_eq!(
+            lb.relay_for_any("/blocks/latest", &[])
+                .await
+                .unwrap()
+                .1
+                .as_str(),
+            "unchecked"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_relay_for_any_uses_selection_strategy_and_skips_unhealthy() {
+        let lb = LoadBalancerState::new(None, test_key())
+            .with_selection_strategy(SelectionStrategy::EwmaLatency);
+        let slow = test_relay_state("slow");
+        let fast = test_relay_state("fast");
+        let fastest = test_relay_state("fastest");
+
+        *slow.latency_ewma.lock().await = Some(std::time::Duration::from_millis(900));
+        *fast.latency_ewma.lock().await = Some(std::time::Duration::from_millis(30));
+        *fastest.latency_ewma.lock().await = Some(std::time::Duration::from_millis(5));
+        *fastest.platform_health.lock().await = Some(PlatformHealth {
+            healthy: false,
+            ..PlatformHealth::unreachable()
+        });
+
+        for (n, relay_state) in [slow, fast, fastest].into_iter().enumerate() {
+            lb.active_relays
+                .lock()
+                .await
+                .insert(Uuid::from_u128(n as u128 + 1), relay_state);
+        }
+
+        for _ in 0..3 {
+            assert_eq!(
+                lb.relay_for_any("/metrics", &[]).await.unwrap().1.as_str(),
+                "fast"
+            );
+        }
+    }
+
+    /// A relay accepting every request, but never answering any.
+    fn test_hanging_relay_state(name: &str) -> RelayState {
+        let (new_request_channel, mut request_rx) = mpsc::channel::<RequestState>(1);
+
+        tokio::spawn(async move {
+            let mut hanging = Vec::new();
+            while let Some(request) = request_rx.recv().await {
+                hanging.push(request);
+            }
+        });
+
+        RelayState {
+            new_request_channel,
+            ..test_relay_state(name)
+        }
+    }
+
+    /// Sends a request to `/any/{rest}`, first through a broken relay (its
+    /// receiver is dropped), and then a working one.
+    async fn any_route_with_broken_relay(
+        method: &str,
+        rest: &str,
+        retry_budget: u32,
+    ) -> (hyper::StatusCode, Option<String>) {
+        any_route_with_failing_relay(test_relay_state("broken"), method, rest, retry_budget).await
+    }
+
+    /// Like [`any_route_with_broken_relay`], but the first relay is `failing`.
+    async fn any_route_with_failing_relay(
+        failing: RelayState,
+        method: &str,
+        rest: &str,
+        retry_budget: u32,
+    ) -> (hyper::StatusCode, Option<String>) {
+        use axum::response::IntoResponse;
+
+        let lb = LoadBalancerState::new(None, test_key()).with_retries(
+            retry_budget,
+            std::time::Duration::from_millis(200),
+            std::time::Duration::from_secs(5),
+        );
+        lb.active_relays
+            .lock()
+            .await
+            .insert(Uuid::from_u128(1), failing);
+        lb.active_relays.lock().await.insert(
+            Uuid::from_u128(2),
+            test_responding_relay_state("working", 200),
+        );
+
+        let request = hyper::Request::builder()
+            .method(method)
+            .uri(format!("/any/{rest}"))
+            .body(axum::body::Body::empty())
+            .unwrap();
+
+        let response = api::any_route(
+            axum::extract::Path(rest.to_string()),
+            axum::Extension(lb),
+            request,
+        )
+        .await
+        .unwrap()
+        .into_response();
+
+        let attempts = response
+            .headers()
+            .get(RELAY_ATTEMPTS_HEADER)
+            .map(|value| value.to_str().unwrap().to_string());
+
+        (response.status(), attempts)
+    }
+
+    #[tokio::test]
+    async fn test_any_route_retries_get_on_another_relay() {
+        assert_eq!(
+            any_route_with_broken_relay("GET", "blocks/latest", 2).await,
+            (hyper::StatusCode::OK, Some("2".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_any_route_retries_get_after_hanging_relay() {
+        let started = std::time::Instant::now();
+
+        assert_eq!(
+            any_route_with_failing_relay(
+                test_hanging_relay_state("hanging"),
+                "GET",
+                "blocks/latest",
+                2
+            )
+            .await,
+            (hyper::StatusCode::OK, Some("2".to_string()))
+        );
+
+        // Only the attempt timeout was spent on the hanging relay, not the deadline:
+        assert!(started.elapsed() < std::time::Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn test_penalize_raises_latency_ewma() {
+        let lb = LoadBalancerState::new(None, test_key());
+        let relay_state = test_relay_state("timing-out");
+        *relay_state.latency_ewma.lock().await = Some(std::time::Duration::from_millis(10));
+        lb.active_relays
+            .lock()
+            .await
+            .insert(Uuid::from_u128(1), relay_state.clone());
+
+        lb.penalize(
+            &AssetName("timing-out".to_string()),
+            std::time::Duration::from_secs(20),
+        )
+        .await;
+
+        assert!(relay_state.latency_ewma.lock().await.unwrap() > std::time::Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn test_any_route_without_retry_budget() {
+        assert_eq!(
+            any_route_with_broken_relay("GET", "blocks/latest", 0).await,
+            (hyper::StatusCode::BAD_GATEWAY, Some("1".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_any_route_never_retries_post() {
+        assert_eq!(
+            any_route_with_broken_relay("POST", "tx/submit", 2).await,
+            (hyper::StatusCode::BAD_GATEWAY, Some("1".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_any_route_post_outlives_retry_deadline() {
+        use axum::response::IntoResponse;
+
+        let (new_request_channel, mut request_rx) = mpsc::channel::<RequestState>(1);
+        tokio::spawn(async move {
+            while let Some(request) = request_rx.recv().await {
+                tokio::time::sleep(std::time::Duration::from_millis(300)).await;
+                let _ignored_failure: Result<_, _> = request.respond_to.send(Ok(JsonResponse {
+                    id: request.underlying.id.clone(),
+                    code: 200,
+                    header: vec![],
+                    body_base64: String::new(),
+                }));
+            }
+        });
+
+        let lb = LoadBalancerState::new(None, test_key()).with_retries(
+            2,
+            std::time::Duration::from_millis(50),
+            std::time::Duration::from_millis(100),
+        );
+        lb.active_relays.lock().await.insert(
+            Uuid::from_u128(1),
+            RelayState {
+                new_request_channel,
+                ..test_relay_state("slow")
+            },
+        );
+
+        let request = hyper::Request::builder()
+            .method("POST")
+            .uri("/any/tx/submit")
+            .body(axum::body::Body::empty())
+            .unwrap();
+
+        let response = api::any_route(
+            axum::extract::Path("tx/submit".to_string()),
+            axum::Extension(lb),
+            request,
+        )
+        .await
+        .unwrap()
+        .into_response();
+
+        assert_eq!(response.status(), hyper::StatusCode::OK);
+    }
+
     #[test]
     fn test_token_roundtrip() {
         let lb = LoadBalancerState::new(None, test_key());
@@ -1589,6 +2198,31 @@ mod tests {
         assert!(health.healthy);
         assert_eq!(health.version.as_deref(), Some("1.0.0"));
         assert_eq!(health.has_data_node, Some(true));
+        assert_eq!(health.capabilities, None);
+    }
+
+    #[test]
+    fn test_health_response_with_capabilities() {
+        let health = interpret_health_response(&health_response(
+            200,
+            encode_health_body(serde_json::json!({
+                "name": "blockfrost-platform",
+                "version": "1.1.0",
+                "revision": "aaaaaaaa",
+                "healthy": true,
+                "node_info": null,
+                "errors": [],
+                "capabilities": ["tx_submit", "some_future_group", "ledger_state"],
+            })),
+        ));
+
+        assert_eq!(health.has_data_node, Some(false));
+        assert_eq!(
+            health.capabilities,
+            Some(vec![RouteGroup::TxSubmit, RouteGroup::LedgerState])
+        );
+        assert!(health.can_serve(RouteGroup::LedgerState));
+        assert!(!health.can_serve(RouteGroup::Addresses));
     }
 
     #[test]
@@ -1644,7 +2278,7 @@ mod tests {
             };
             request
                 .respond_to
-                .send(response)
+                .send(Ok(response))
                 .expect("health check awaits the response");
         });
 
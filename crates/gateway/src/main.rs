@@ -77,7 +77,13 @@ async fn main() -> Result<()> {
         None
     };
     let load_balancer =
-        load_balancer::LoadBalancerState::new(hydras_manager, config.server.peer_secret);
+        load_balancer::LoadBalancerState::new(hydras_manager, config.server.peer_secret)
+            .with_selection_strategy(config.load_balancer.strategy)
+            .with_retries(
+                config.load_balancer.retry_budget,
+                std::time::Duration::from_secs(config.load_balancer.attempt_timeout_seconds),
+                std::time::Duration::from_secs(config.load_balancer.retry_deadline_seconds),
+            );
     let register_rate_limiter = rate_limit::new_register_rate_limiter();
 
     let base_router = Router::new()
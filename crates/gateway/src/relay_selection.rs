@@ -0,0 +1,226 @@
+//! Strategies for picking one of the relays capable of serving an `/any/*`
+//! request, configured with `[load_balancer] strategy` in the Gateway TOML.
+
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// How much a single new latency sample moves the average.
+const EWMA_ALPHA: f64 = 0.3;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SelectionStrategy {
+    /// Every relay in turn, in the order of their UUIDs.
+    #[default]
+    RoundRobin,
+    /// The relay with the fewest requests in progress.
+    LeastOutstanding,
+    /// The relay with the lowest average response latency, multiplied by its
+    /// requests in progress, so that a single fast relay doesn’t get flooded.
+    EwmaLatency,
+    /// The less busy of two relays chosen at random, which spreads the load
+    /// almost as well as [`Self::LeastOutstanding`], without herding all
+    /// concurrent requests onto the same relay.
+    PowerOfTwoChoices,
+}
+
+/// What a [`SelectionStrategy`] knows about a single relay.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RelayLoad {
+    pub requests_in_progress: u64,
+    /// `None` until the relay answers its first request.
+    pub latency_ewma: Option<Duration>,
+}
+
+impl SelectionStrategy {
+    /// Lower is better. Relays without any latency sample cost as little as
+    /// possible for [`Self::EwmaLatency`], so that they get one soon.
+    pub fn cost(&self, load: &RelayLoad) -> f64 {
+        let in_progress = load.requests_in_progress as f64 + 1.0;
+
+        match self {
+            SelectionStrategy::RoundRobin => 1.0,
+            SelectionStrategy::LeastOutstanding | SelectionStrategy::PowerOfTwoChoices => {
+                in_progress
+            },
+            SelectionStrategy::EwmaLatency => {
+                let millis = load
+                    .latency_ewma
+                    .map_or(0.0, |latency| latency.as_secs_f64() * 1000.0);
+                (millis + 1.0) * in_progress
+            },
+        }
+    }
+
+    /// Index of the relay to use, or `None` if there are none. The `cursor` is
+    /// a counter shared by all selections, used to break ties in a round-robin
+    /// way, and `random` is only used by [`Self::PowerOfTwoChoices`].
+    pub fn pick(&self, loads: &[RelayLoad], cursor: u64, random: u128) -> Option<usize> {
+        let n = loads.len();
+
+        if n == 0 {
+            return None;
+        }
+
+        if *self == SelectionStrategy::PowerOfTwoChoices && n > 1 {
+            let first = (random % n as u128) as usize;
+            // A different relay than `first`, using the other half of the bits:
+            let second = (first + 1 + ((random >> 64) % (n as u128 - 1)) as usize) % n;
+
+            return Some(if self.cost(&loads[second]) < self.cost(&loads[first]) {
+                second
+            } else {
+                first
+            });
+        }
+
+        // `min_by` returns the first of equal elements, and we start from the cursor:
+        let start = (cursor % n as u64) as usize;
+        (0..n)
+            .map(|i| (start + i) % n)
+            .min_by(|&a, &b| self.cost(&loads[a]).total_cmp(&self.cost(&loads[b])))
+    }
+
+    /// How much the strategy prefers each relay, i.e. the inverse of its
+    /// [`Self::cost`], normalized to sum up to 1. Shown in `/stats`.
+    ///
+    /// **Note**: it’s not a probability. All strategies but
+    /// [`Self::PowerOfTwoChoices`] are deterministic, and always pick the
+    /// relay with the highest preference (or the next one in turn, for
+    /// [`Self::RoundRobin`]).
+    pub fn preferences(&self, loads: &[RelayLoad]) -> Vec<f64> {
+        let inverse_costs: Vec<f64> = loads.iter().map(|load| 1.0 / self.cost(load)).collect();
+        let total: f64 = inverse_costs.iter().sum();
+
+        inverse_costs
+            .iter()
+            .map(|inverse| inverse / total)
+            .collect()
+    }
+}
+
+/// Exponentially weighted moving average of response latencies. Failed
+/// requests are fed in as a penalty sample, see
+/// [`crate::load_balancer::LoadBalancerState::penalize`].
+pub fn update_ewma(previous: Option<Duration>, sample: Duration) -> Duration {
+    match previous {
+        None => sample,
+        Some(previous) => previous.mul_f64(1.0 - EWMA_ALPHA) + sample.mul_f64(EWMA_ALPHA),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn load(requests_in_progress: u64, latency_millis: Option<u64>) -> RelayLoad {
+        RelayLoad {
+            requests_in_progress,
+            latency_ewma: latency_millis.map(Duration::from_millis),
+        }
+    }
+
+    #[test]
+    fn test_round_robin_follows_the_cursor() {
+        let loads = [load(5, Some(100)), load(0, Some(1)), load(9, None)];
+
+        for cursor in 0..6 {
+            assert_eq!(
+                SelectionStrategy::RoundRobin.pick(&loads, cursor, 0),
+                Some(cursor as usize % 3)
+            );
+        }
+    }
+
+    #[rstest]
+    #[case(SelectionStrategy::LeastOutstanding, vec![load(3, Some(1)), load(1, Some(500)), load(2, None)], 1)]
+    #[case(SelectionStrategy::EwmaLatency, vec![load(3, Some(1)), load(1, Some(500)), load(2, Some(40))], 0)]
+    #[case(SelectionStrategy::EwmaLatency, vec![load(0, Some(20)), load(0, None)], 1)]
+    fn test_pick_lowest_cost(
+        #[case] strategy: SelectionStrategy,
+        #[case] loads: Vec<RelayLoad>,
+        #[case] expected: usize,
+    ) {
+        for cursor in 0..loads.len() as u64 {
+            assert_eq!(strategy.pick(&loads, cursor, 0), Some(expected));
+        }
+    }
+
+    #[test]
+    fn test_ties_are_broken_by_the_cursor() {
+        let loads = [load(1, None), load(0, None), load(0, None)];
+
+        assert_eq!(
+            SelectionStrategy::LeastOutstanding.pick(&loads, 0, 0),
+            Some(1)
+        );
+        assert_eq!(
+            SelectionStrategy::LeastOutstanding.pick(&loads, 2, 0),
+            Some(2)
+        );
+    }
+
+    #[test]
+    fn test_power_of_two_choices_picks_less_busy_of_two() {
+        let loads = [load(4, None), load(1, None), load(7, None)];
+        let strategy = SelectionStrategy::PowerOfTwoChoices;
+
+        // Relays 0 and 1:
+        assert_eq!(strategy.pick(&loads, 0, 0), Some(1));
+        // Relays 2 and 1:
+        assert_eq!(strategy.pick(&loads, 0, (1 << 64) | 1), Some(1));
+        // Relays 0 and 2, so the least busy one isn’t an option:
+        assert_eq!(strategy.pick(&loads, 0, (1 << 64) | 2), Some(0));
+
+        for random in 0..16 {
+            assert_eq!(strategy.pick(&loads[..1], 0, random), Some(0));
+            assert_eq!(strategy.pick(&loads[..2], 0, random << 64), Some(1));
+        }
+        assert_eq!(strategy.pick(&[], 0, 0), None);
+    }
+
+    #[test]
+    fn test_preferences() {
+        let loads = [load(0, None), load(1, None), load(3, None)];
+
+        assert_eq!(
+            SelectionStrategy::RoundRobin.preferences(&loads),
+            vec![1.0 / 3.0; 3]
+        );
+
+        let preferences = SelectionStrategy::LeastOutstanding.preferences(&loads);
+        assert!((preferences.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+        assert!(preferences[0] > preferences[1] && preferences[1] > preferences[2]);
+    }
+
+    #[test]
+    fn test_update_ewma_with_penalty() {
+        let latency = update_ewma(None, Duration::from_millis(10));
+        let penalized = update_ewma(Some(latency), Duration::from_secs(20));
+
+        assert_eq!(
+            SelectionStrategy::EwmaLatency.pick(
+                &[
+                    RelayLoad {
+                        requests_in_progress: 0,
+                        latency_ewma: Some(penalized),
+                    },
+                    load(0, Some(500)),
+                ],
+                0,
+                0
+            ),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn test_update_ewma() {
+        let first = update_ewma(None, Duration::from_millis(100));
+        assert_eq!(first, Duration::from_millis(100));
+
+        let second = update_ewma(Some(first), Duration::from_millis(200));
+        assert!((second.as_secs_f64() - 0.130).abs() < 1e-6);
+    }
+}
@@ -27,7 +27,7 @@ pub fn test_config(icebreakers_config: Option<IcebreakersConfig>) -> Arc<Config>
         max_response_body_bytes: bf_common::DEFAULT_MAX_BODY_BYTES,
         log_level: LogLevel::Info.into(),
         mode: Mode::Compact,
-        node_socket_path: node_socket_path_env,
+        node_socket_paths: vec![node_socket_path_env],
         icebreakers_config,
         max_pool_connections: 10,
         network: Network::Preview,
@@ -36,6 +36,7 @@ pub fn test_config(icebreakers_config: Option<IcebreakersConfig>) -> Arc<Config>
         genesis: genesis(),
         data_node: None,
         hydra: None,
+        resubmit_on_rollback: false,
     };
 
     Arc::new(config)
@@ -95,7 +96,7 @@ pub fn test_config_with_data_node(
         max_response_body_bytes: bf_common::DEFAULT_MAX_BODY_BYTES,
         log_level: LogLevel::Info.into(),
         mode: Mode::Compact,
-        node_socket_path: node_socket_path_env,
+        node_socket_paths: vec![node_socket_path_env],
         icebreakers_config,
         max_pool_connections: 10,
         network: Network::Preview,
@@ -107,6 +108,7 @@ pub fn test_config_with_data_node(
             request_timeout: Duration::from_secs(30),
         }),
         hydra: None,
+        resubmit_on_rollback: false,
     };
 
     Arc::new(config)
@@ -2,6 +2,7 @@ use axum::{
     body::{Body, to_bytes},
     http::Request,
 };
+use bf_common::route_groups::RouteGroup;
 use blockfrost_platform::api::root::RootResponse;
 use integration_tests::{initialize_logging, platform::build_app};
 use pretty_assertions::assert_eq;
@@ -35,4 +36,7 @@ async fn test_route_root() {
     assert_eq!(root_response.node_info.unwrap().sync_progress, 100.0);
     // data_node is not configured in build_app()
     assert!(root_response.data_node.is_none());
+    // build_app() runs in the compact mode
+    assert!(root_response.capabilities.contains(&RouteGroup::TxSubmit));
+    assert!(!root_response.capabilities.contains(&RouteGroup::Blocks));
 }
@@ -0,0 +1,87 @@
+use axum::{
+    body::{Body, to_bytes},
+    http::Request,
+};
+use integration_tests::{
+    get_blockfrost_client, initialize_logging,
+    platform::{build_app, tx_builder::build_tx},
+};
+use pretty_assertions::assert_eq;
+use reqwest::{Method, StatusCode};
+use serde_json::Value;
+use tower::ServiceExt;
+
+// Test: `/tx/{hash}/status` doesn’t know transactions submitted elsewhere
+#[tokio::test]
+#[ntest::timeout(120_000)]
+async fn test_route_tx_status_unknown() {
+    initialize_logging();
+    let (app, _, _, _, _) = build_app().await.expect("Failed to build the application");
+
+    let request = Request::builder()
+        .uri("/tx/6d2174d3956d8eb2b3e1e198e817ccf1332a599d5d7320400bfd820490d706be/status")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = app
+        .oneshot(request)
+        .await
+        .expect("Request to /tx/{hash}/status failed");
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+// Test: `/tx/{hash}/status` tracks a transaction accepted by `/tx/submit`
+#[tokio::test]
+#[ntest::timeout(120_000)]
+async fn test_route_tx_status_after_submit() {
+    initialize_logging();
+    let (app, _, _, _, _) = build_app().await.expect("Failed to build the application");
+    let blockfrost_client = get_blockfrost_client();
+    let tx = build_tx(&blockfrost_client).await.unwrap();
+
+    let request = Request::builder()
+        .method(Method::POST)
+        .uri("/tx/submit")
+        .header("Content-Type", "application/cbor")
+        .body(Body::from(tx.to_hex()))
+        .unwrap();
+
+    let response = app
+        .clone()
+        .oneshot(request)
+        .await
+        .expect("Request to /tx/submit failed");
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body_bytes = to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("Failed to read response body");
+    let tx_hash: String =
+        serde_json::from_slice(&body_bytes).expect("Response body is not a JSON string");
+
+    let request = Request::builder()
+        .uri(format!("/tx/{tx_hash}/status"))
+        .body(Body::empty())
+        .unwrap();
+
+    let response = app
+        .oneshot(request)
+        .await
+        .expect("Request to /tx/{hash}/status failed");
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body_bytes = to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("Failed to read response body");
+    let status: Value = serde_json::from_slice(&body_bytes).expect("Response body is not JSON");
+
+    assert_eq!(status["tx_hash"], tx_hash.as_str());
+    assert!(
+        ["pending", "in_mempool", "confirmed"].contains(&status["status"].as_str().unwrap()),
+        "Unexpected status: {status}"
+    );
+    assert_eq!(status["resubmissions"], 0);
+}
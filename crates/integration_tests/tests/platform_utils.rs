@@ -0,0 +1,68 @@
+use axum::{
+    body::{Body, Bytes, to_bytes},
+    http::Request,
+};
+use bf_node::era_history::SlotTime;
+use integration_tests::{initialize_logging, platform::build_app};
+use pretty_assertions::assert_eq;
+use reqwest::StatusCode;
+use tower::ServiceExt;
+
+async fn get(app: &axum::Router, uri: &str) -> (StatusCode, Bytes) {
+    let response = app
+        .clone()
+        .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap_or_else(|_| panic!("Request to {uri} failed"));
+
+    let status = response.status();
+    let body_bytes = to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("Failed to read response body");
+
+    (status, body_bytes)
+}
+
+// Test: `/utils/slot/{slot}/time` and `/utils/time/{unix}/slot` agree on Preview,
+// which has 1 s slots and 86400-slot epochs since its start at 1666656000
+#[tokio::test]
+#[ntest::timeout(120_000)]
+async fn test_route_utils_slot_time_round_trip() {
+    initialize_logging();
+    let (app, _, _, _, _) = build_app().await.expect("Failed to build the application");
+
+    let expected = SlotTime {
+        slot: 86_400,
+        epoch: 1,
+        time: 1_666_742_400,
+    };
+
+    let (status, body) = get(&app, "/utils/slot/86400/time").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        serde_json::from_slice::<SlotTime>(&body).expect("Response body is not a SlotTime"),
+        expected
+    );
+
+    let (status, body) = get(&app, "/utils/time/1666742400/slot").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        serde_json::from_slice::<SlotTime>(&body).expect("Response body is not a SlotTime"),
+        expected
+    );
+}
+
+// Test: `/utils/slot/{slot}/time` and `/utils/time/{unix}/slot` reject values
+// outside of the era history
+#[tokio::test]
+#[ntest::timeout(120_000)]
+async fn test_route_utils_slot_time_out_of_range() {
+    initialize_logging();
+    let (app, _, _, _, _) = build_app().await.expect("Failed to build the application");
+
+    let (status, _) = get(&app, "/utils/slot/1000000000000000/time").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = get(&app, "/utils/time/1000/slot").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
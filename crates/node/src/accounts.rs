@@ -0,0 +1,249 @@
+use super::connection::NodeClient;
+use crate::ledger_query::{RawArg, decode_error, encode_credential, encode_set, query_if_current};
+use bech32::{Bech32, Hrp};
+use bf_api_provider::types::AccountsResponse;
+use bf_common::errors::BlockfrostError;
+use pallas_codec::minicbor::{self, Decoder};
+use pallas_network::miniprotocols::localstate::{self, queries_v16};
+use serde_json::json;
+
+const GET_FILTERED_DELEGATIONS_AND_REWARD_ACCOUNTS: u16 = 10;
+const GET_FILTERED_VOTE_DELEGATEES: u16 = 28;
+
+/// Vote delegations only exist since Conway.
+const CONWAY_ERA: u16 = 6;
+
+impl NodeClient {
+    /// Fetches a stake account with the `GetFilteredDelegationsAndRewardAccounts`
+    /// and `GetFilteredVoteDelegatees` local-state queries. `stake_address` must
+    /// be in bech32.
+    ///
+    /// **Note**: the node only knows the current delegations and reward balance,
+    /// so `controlled_amount` is only the reward balance, without UTxOs, the
+    /// historical sums are zero, and `active_epoch` is `null`.
+    pub async fn account(
+        &mut self,
+        stake_address: &str,
+    ) -> Result<AccountsResponse, BlockfrostError> {
+        let (_, bytes) = bech32::decode(stake_address)?;
+
+        let (is_script, hash) = match bytes.as_slice() {
+            [header, hash @ ..] if hash.len() == 28 => (header & 0x10 != 0, hash),
+            _ => return Err(BlockfrostError::invalid_stake_address()),
+        };
+
+        let credentials = encode_set(&[encode_credential(is_script, hash)?])?;
+
+        let key = format!("account:{}", hex::encode(&credentials));
+
+        let (delegations, vote_delegatees) = self
+            .with_cached_statequery(key, |generic_client: &mut localstate::GenericClient| {
+                Box::pin(async move {
+                    let era = queries_v16::get_current_era(generic_client).await?;
+
+                    let delegations = query_if_current(
+                        generic_client,
+                        era,
+                        GET_FILTERED_DELEGATIONS_AND_REWARD_ACCOUNTS,
+                        vec![RawArg(credentials.clone())],
+                    )
+                    .await?;
+
+                    let vote_delegatees = if era >= CONWAY_ERA {
+                        Some(
+                            query_if_current(
+                                generic_client,
+                                era,
+                                GET_FILTERED_VOTE_DELEGATEES,
+                                vec![RawArg(credentials)],
+                            )
+                            .await?,
+                        )
+                    } else {
+                        None
+                    };
+
+                    Ok((delegations, vote_delegatees))
+                })
+            })
+            .await?;
+
+        let (pool_hash, rewards) =
+            decode_delegation_and_rewards(&delegations).map_err(decode_error)?;
+
+        // Unregistered accounts have no reward account:
+        let Some(rewards) = rewards else {
+            return Err(BlockfrostError::not_found());
+        };
+
+        let pool_id = pool_hash
+            .map(|pool_hash| {
+                let hrp = Hrp::parse("pool")?;
+
+                Ok::<_, BlockfrostError>(bech32::encode::<Bech32>(hrp, &pool_hash)?)
+            })
+            .transpose()?;
+
+        let drep_id = match vote_delegatees {
+            Some(cbor) => decode_vote_delegatee(&cbor)
+                .map_err(decode_error)?
+                .map(drep_to_bech32)
+                .transpose()?,
+            None => None,
+        };
+
+        serde_json::from_value(json!({
+            "stake_address": stake_address,
+            "active": true,
+            "active_epoch": null,
+            "controlled_amount": rewards.to_string(),
+            "rewards_sum": "0",
+            "withdrawals_sum": "0",
+            "reserves_sum": "0",
+            "treasury_sum": "0",
+            "withdrawable_amount": rewards.to_string(),
+            "pool_id": pool_id,
+            "drep_id": drep_id,
+        }))
+        .map_err(|e| {
+            BlockfrostError::internal_server_error(format!("Failed to convert account: {e}"))
+        })
+    }
+}
+
+/// Decodes `(Map Credential (KeyHash StakePool), Map Credential Coin)` for a
+/// single credential into `(pool_hash, rewards)`.
+fn decode_delegation_and_rewards(
+    cbor: &[u8],
+) -> Result<(Option<Vec<u8>>, Option<u64>), minicbor::decode::Error> {
+    let mut d = Decoder::new(cbor);
+
+    d.array()?;
+
+    let pool_hash = if d.map()? == Some(0) {
+        None
+    } else {
+        d.skip()?; // credential
+        Some(d.bytes()?.to_vec())
+    };
+
+    let rewards = if d.map()? == Some(0) {
+        None
+    } else {
+        d.skip()?; // credential
+        Some(d.u64()?)
+    };
+
+    Ok((pool_hash, rewards))
+}
+
+/// A `DRep` delegation, as `(tag, hash)`, with `hash` only for registered DReps.
+type Drep = (u8, Option<Vec<u8>>);
+
+/// Decodes `Map Credential DRep` for a single credential.
+fn decode_vote_delegatee(cbor: &[u8]) -> Result<Option<Drep>, minicbor::decode::Error> {
+    let mut d = Decoder::new(cbor);
+
+    if d.map()? == Some(0) {
+        return Ok(None);
+    }
+
+    d.skip()?; // credential
+    d.array()?;
+
+    let tag = d.u8()?;
+    let hash = match tag {
+        0 | 1 => Some(d.bytes()?.to_vec()),
+        _ => None,
+    };
+
+    Ok(Some((tag, hash)))
+}
+
+/// Formats a `DRep` like `/governance/dreps`, i.e. in CIP-129 for registered
+/// DReps.
+fn drep_to_bech32((tag, hash): Drep) -> Result<String, BlockfrostError> {
+    match (tag, hash) {
+        (0 | 1, Some(hash)) => {
+            let header = if tag == 0 { 0x22 } else { 0x23 };
+            let hrp = Hrp::parse("drep")?;
+
+            Ok(bech32::encode::<Bech32>(
+                hrp,
+                &[[header].as_slice(), hash.as_slice()].concat(),
+            )?)
+        },
+        (2, _) => Ok("drep_always_abstain".to_string()),
+        (3, _) => Ok("drep_always_no_confidence".to_string()),
+        _ => Err(BlockfrostError::internal_server_error(format!(
+            "Unknown DRep tag {tag}"
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_delegation_and_rewards() {
+        // [{[0, h'00…']: h'ab…'}, {[0, h'00…']: 1000000}]
+        let cbor = hex::decode(
+            [
+                "82",
+                "a18200581c",
+                &"00".repeat(28),
+                "581c",
+                &"ab".repeat(28),
+                "a18200581c",
+                &"00".repeat(28),
+                "1a000f4240",
+            ]
+            .concat(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            decode_delegation_and_rewards(&cbor).unwrap(),
+            (Some(vec![0xab; 28]), Some(1_000_000))
+        );
+        assert_eq!(
+            decode_delegation_and_rewards(&[0x82, 0xa0, 0xa0]).unwrap(),
+            (None, None)
+        );
+    }
+
+    #[test]
+    fn test_decode_vote_delegatee() {
+        let credential = ["8200581c", &"00".repeat(28)].concat();
+
+        let cases: &[(String, Option<Drep>, Option<&str>)] = &[
+            ("a0".to_string(), None, None),
+            (
+                ["a1", &credential, "8200581c", &"ab".repeat(28)].concat(),
+                Some((0, Some(vec![0xab; 28]))),
+                Some("drep1y246h2at4w46h2at4w46h2at4w46h2at4w46h2at4w46h2caa85du"),
+            ),
+            (
+                ["a1", &credential, "8102"].concat(),
+                Some((2, None)),
+                Some("drep_always_abstain"),
+            ),
+            (
+                ["a1", &credential, "8103"].concat(),
+                Some((3, None)),
+                Some("drep_always_no_confidence"),
+            ),
+        ];
+
+        for (cbor, expected, drep_id) in cases {
+            let decoded = decode_vote_delegatee(&hex::decode(cbor).unwrap()).unwrap();
+            assert_eq!(&decoded, expected, "{cbor}");
+            assert_eq!(
+                decoded.map(|drep| drep_to_bech32(drep).unwrap()).as_deref(),
+                *drep_id,
+                "{cbor}"
+            );
+        }
+    }
+}
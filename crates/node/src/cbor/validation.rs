@@ -1,6 +1,11 @@
+use crate::transactions::TxSubmitError;
 use bf_common::errors::BlockfrostError;
 use pallas_hardano::display::haskell_error::as_cbor_decode_failure;
-use pallas_primitives::{alonzo::Value, babbage::GenTransactionOutput, conway::Tx};
+use pallas_network::miniprotocols::localstate::queries_v16;
+use pallas_primitives::conway::Tx;
+use pallas_traverse::{MultiEraOutput, MultiEraTx};
+use serde_json::{Value, json};
+use std::sync::{Arc, RwLock};
 use tracing::warn;
 
 /// Checks if the given transaction is a valid CBOR-encoded transaction, by trying to decode it.
@@ -8,9 +13,13 @@ use tracing::warn;
 /// If the transaction is invalid, returns the decoding error.
 /// If the transaction is valid, returns Ok(()).
 pub(crate) fn validate_tx_cbor(tx: &[u8]) -> Result<(), BlockfrostError> {
-    match pallas_codec::minicbor::decode::<Tx>(tx) {
+    let decoded = pallas_codec::minicbor::decode::<Tx>(tx)
+        .map_err(|e| (e.to_string(), e.position().unwrap_or(0)))
+        .and_then(|_| MultiEraTx::decode(tx).map_err(|e| (e.to_string(), 0)));
+
+    match decoded {
         Ok(decoded) => {
-            if _check_multiasset_zero(decoded) {
+            if check_multiasset_zero(&decoded) {
                 Err(BlockfrostError::custom_400(
                     as_cbor_decode_failure("MultiAsset cannot contain zeros".to_string(), 0)
                         .unwrap_or_else(|e| format!("Failed to format decode error: {e}")),
@@ -19,10 +28,10 @@ pub(crate) fn validate_tx_cbor(tx: &[u8]) -> Result<(), BlockfrostError> {
                 Ok(())
             }
         },
-        Err(e) => {
+        Err((e, position)) => {
             warn!("Invalid TX CBOR: {:?}, CBOR: {}", e, hex::encode(tx));
             Err(BlockfrostError::custom_400(
-                as_cbor_decode_failure(e.to_string(), e.position().unwrap_or(0) as u64)
+                as_cbor_decode_failure(e, position as u64)
                     .unwrap_or_else(|e| format!("Failed to format decode error: {e}")),
             ))
         },
@@ -30,25 +39,318 @@ pub(crate) fn validate_tx_cbor(tx: &[u8]) -> Result<(), BlockfrostError> {
 }
 
 /// A workaround to match the the ledger behaviour.
-/// Checks if the transaction contains any multiasset outputs with zero amounts.
-/// pallas decoding will not fail, but we need to fail for this case.
+/// Checks if the transaction contains any multiasset outputs (of any format,
+/// including the collateral return) with non-positive amounts, or mints zero
+/// of an asset. pallas decoding will not fail for all of these, but we need to
+/// fail for them.
 /// Initially we implemented this workaround in the pallas codabase.
 /// See https://github.com/IntersectMBO/cardano-ledger/blob/49623962445143680dd725ebbf812c37e099b65c/eras/mary/impl/src/Cardano/Ledger/Mary/Value.hs#L328
-fn _check_multiasset_zero(tx: Tx) -> bool {
-    for output in tx.transaction_body.outputs.iter() {
-        if let GenTransactionOutput::Legacy(o) = output
-            && let Value::Multiasset(_, kv) = &o.amount
-        {
-            for assets in kv.values() {
-                for amount in assets.values() {
-                    if *amount == 0 {
-                        return true;
-                    }
-                }
+fn check_multiasset_zero(tx: &MultiEraTx) -> bool {
+    let in_outputs = tx
+        .outputs()
+        .iter()
+        .chain(tx.collateral_return().iter())
+        .any(|output| {
+            output.value().assets().iter().any(|policy_assets| {
+                policy_assets
+                    .assets()
+                    .iter()
+                    .any(|asset| asset.any_coin() <= 0)
+            })
+        });
+
+    let in_mint = tx.mints().iter().any(|policy_assets| {
+        policy_assets
+            .assets()
+            .iter()
+            .any(|asset| asset.any_coin() == 0)
+    });
+
+    in_outputs || in_mint
+}
+
+/// Hard-fork era indices of the eras whose rules [`check_phase_one`] knows.
+const BABBAGE_ERA: u16 = 5;
+const CONWAY_ERA: u16 = 6;
+
+/// Protocol parameters needed by [`check_phase_one`]. They can only change at
+/// epoch boundaries, so they are cached per epoch in a
+/// [`PhaseOneParamsCache`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PhaseOneParams {
+    pub epoch: u32,
+    /// Hard-fork era index of the node’s ledger, which can only change at
+    /// epoch boundaries, too.
+    pub era: u16,
+    pub max_tx_size: u64,
+    pub min_fee_a: u64,
+    pub min_fee_b: u64,
+    pub coins_per_utxo_byte: u64,
+    /// `(numerator, denominator)` of the price of a unit of memory.
+    pub price_mem: (u64, u64),
+    /// `(numerator, denominator)` of the price of a CPU step.
+    pub price_steps: (u64, u64),
+    pub collateral_percentage: u64,
+}
+
+/// Shared by all connections to the same node.
+pub(crate) type PhaseOneParamsCache = Arc<RwLock<Option<PhaseOneParams>>>;
+
+impl PhaseOneParams {
+    /// Missing parameters become 0, which disables the corresponding checks.
+    pub fn new(epoch: u32, era: u16, p: &queries_v16::ProtocolParam) -> Self {
+        let price = |r: Option<&queries_v16::RationalNumber>| {
+            r.map_or((0, 1), |r| (r.numerator, r.denominator))
+        };
+
+        Self {
+            epoch,
+            era,
+            max_tx_size: p.max_transaction_size.unwrap_or_default(),
+            min_fee_a: p.minfee_a.unwrap_or_default(),
+            min_fee_b: p.minfee_b.unwrap_or_default(),
+            coins_per_utxo_byte: p.ada_per_utxo_byte.map(u64::from).unwrap_or_default(),
+            price_mem: price(p.execution_costs.as_ref().map(|c| &c.mem_price)),
+            price_steps: price(p.execution_costs.as_ref().map(|c| &c.step_price)),
+            collateral_percentage: p.collateral_percentage.unwrap_or_default(),
+        }
+    }
+}
+
+/// Ledger state that [`check_phase_one`] needs besides the transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PhaseOneContext {
+    /// The slot the node’s mempool validates against, i.e. the one after the
+    /// tip.
+    pub slot: u64,
+    /// Lovelace in all collateral inputs, if every one of them could be
+    /// resolved.
+    pub collateral_inputs_coin: Option<u64>,
+}
+
+/// Runs a subset of the ledger’s phase-1 rules locally, so that clients get
+/// an actionable error without a node round-trip. All failed rules are
+/// reported at once, as the ledger does, in the cardano-submit-api shape.
+///
+/// **Note**: every rule here is a lower bound of the ledger’s, i.e. we never
+/// reject a transaction the node would accept. E.g. the fee for reference
+/// scripts is not included in the minimum fee, because that would require
+/// resolving the reference inputs. For the same reason, in eras other than
+/// Babbage and Conway, all checks are left to the node.
+pub(crate) fn check_phase_one(
+    tx: &[u8],
+    params: &PhaseOneParams,
+    context: &PhaseOneContext,
+) -> Result<(), TxSubmitError> {
+    let Some(era_name) = shelley_based_era(params.era) else {
+        return Ok(());
+    };
+
+    let decoded = MultiEraTx::decode(tx)
+        .map_err(|e| BlockfrostError::custom_400(format!("Failed to decode transaction: {e}")))?;
+
+    let mut failures = Vec::new();
+
+    let size = tx.len() as u64;
+    if params.max_tx_size > 0 && size > params.max_tx_size {
+        failures.push(UtxoFailure::alonzo(format!(
+            "MaxTxSizeUTxO {size} {}",
+            params.max_tx_size
+        )));
+    }
+
+    let fee = decoded.fee().unwrap_or_default();
+    let min_fee = min_fee(params, size, &decoded);
+    if fee < min_fee {
+        failures.push(UtxoFailure::alonzo(format!(
+            "FeeTooSmallUTxO (Coin {min_fee}) (Coin {fee})"
+        )));
+    }
+
+    let too_small: Vec<String> = decoded
+        .outputs()
+        .iter()
+        .chain(decoded.collateral_return().iter())
+        .enumerate()
+        .filter_map(|(index, output)| {
+            let min_utxo = min_utxo(params, output);
+            (output.value().coin() < min_utxo).then(|| format!("(TxOut #{index}, Coin {min_utxo})"))
+        })
+        .collect();
+    if !too_small.is_empty() {
+        failures.push(UtxoFailure::babbage(format!(
+            "BabbageOutputTooSmallUTxO [{}]",
+            too_small.join(",")
+        )));
+    }
+
+    let invalid_before = decoded.validity_start();
+    let invalid_hereafter = decoded.ttl();
+    if invalid_before.is_some_and(|slot| context.slot < slot)
+        || invalid_hereafter.is_some_and(|slot| context.slot >= slot)
+    {
+        let show = |slot: Option<u64>| match slot {
+            Some(slot) => format!("SJust (SlotNo {slot})"),
+            None => "SNothing".to_string(),
+        };
+        failures.push(UtxoFailure::alonzo(format!(
+            "OutsideValidityIntervalUTxO (ValidityInterval {{invalidBefore = {}, invalidHereafter = {}}}) (SlotNo {})",
+            show(invalid_before),
+            show(invalid_hereafter),
+            context.slot
+        )));
+    }
+
+    if !decoded.redeemers().is_empty() {
+        if decoded.collateral().is_empty() {
+            failures.push(UtxoFailure::alonzo("NoCollateralInputs".to_string()));
+        } else {
+            let balance = match context.collateral_inputs_coin {
+                Some(coin) => Some(
+                    i128::from(coin)
+                        - i128::from(
+                            decoded
+                                .collateral_return()
+                                .map(|output| output.value().coin())
+                                .unwrap_or_default(),
+                        ),
+                ),
+                None => decoded.total_collateral().map(i128::from),
+            };
+            let required = i128::from(fee) * i128::from(params.collateral_percentage);
+
+            if let Some(balance) = balance
+                && balance * 100 < required
+            {
+                failures.push(UtxoFailure::alonzo(format!(
+                    "InsufficientCollateral (DeltaCoin {balance}) (Coin {})",
+                    (required + 99) / 100
+                )));
             }
         }
     }
-    false
+
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        let errors: Vec<String> = failures
+            .iter()
+            .map(|failure| failure.show(params.era))
+            .collect();
+        warn!(
+            "Phase-1 checks failed: {:?}, CBOR: {}",
+            errors,
+            hex::encode(tx)
+        );
+        Err(TxSubmitError::PhaseOne(phase_one_failure(era_name, errors)))
+    }
+}
+
+/// `minFeeA * size + minFeeB`, plus the price of all declared execution units.
+fn min_fee(params: &PhaseOneParams, size: u64, tx: &MultiEraTx) -> u64 {
+    let (mem, steps) = tx
+        .redeemers()
+        .iter()
+        .fold((0u128, 0u128), |(mem, steps), r| {
+            let ex_units = r.ex_units();
+            (
+                mem + u128::from(ex_units.mem),
+                steps + u128::from(ex_units.steps),
+            )
+        });
+
+    let (mem_num, mem_den) = (
+        u128::from(params.price_mem.0),
+        u128::from(params.price_mem.1),
+    );
+    let (steps_num, steps_den) = (
+        u128::from(params.price_steps.0),
+        u128::from(params.price_steps.1),
+    );
+
+    let scripts_fee = if mem_den == 0 || steps_den == 0 {
+        0
+    } else {
+        (mem * mem_num * steps_den + steps * steps_num * mem_den).div_ceil(mem_den * steps_den)
+    };
+
+    let fee = u128::from(params.min_fee_a) * u128::from(size)
+        + u128::from(params.min_fee_b)
+        + scripts_fee;
+
+    u64::try_from(fee).unwrap_or(u64::MAX)
+}
+
+/// `(160 + serialized size) * coinsPerUTxOByte`, for both legacy and
+/// post-Alonzo outputs, as since Babbage.
+fn min_utxo(params: &PhaseOneParams, output: &MultiEraOutput) -> u64 {
+    (160 + output.encode().len() as u64).saturating_mul(params.coins_per_utxo_byte)
+}
+
+/// Name of a hard-fork era in the cardano-submit-api JSON, if it’s one whose
+/// rules [`check_phase_one`] knows.
+fn shelley_based_era(era: u16) -> Option<&'static str> {
+    match era {
+        BABBAGE_ERA => Some("ShelleyBasedEraBabbage"),
+        CONWAY_ERA => Some("ShelleyBasedEraConway"),
+        _ => None,
+    }
+}
+
+/// A failure of the ledger’s `UTXO` rule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct UtxoFailure {
+    /// Whether Babbage reports it as one of the Alonzo failures it inherited.
+    /// Conway flattened them all.
+    from_alonzo: bool,
+    display: String,
+}
+
+impl UtxoFailure {
+    fn alonzo(display: String) -> Self {
+        Self {
+            from_alonzo: true,
+            display,
+        }
+    }
+
+    fn babbage(display: String) -> Self {
+        Self {
+            from_alonzo: false,
+            display,
+        }
+    }
+
+    /// How the ledger of `era` shows it inside the `ApplyTxErr`.
+    fn show(&self, era: u16) -> String {
+        let display = &self.display;
+
+        match era {
+            BABBAGE_ERA if self.from_alonzo => {
+                format!("UtxowFailure (UtxoFailure (AlonzoInBabbageUtxoPredFailure ({display})))")
+            },
+            BABBAGE_ERA => format!("UtxowFailure (UtxoFailure ({display}))"),
+            _ => format!("ConwayUtxowFailure (UtxoFailure ({display}))"),
+        }
+    }
+}
+
+/// The same JSON as cardano-submit-api returns for a ledger rejection.
+fn phase_one_failure(era_name: &str, errors: Vec<String>) -> Value {
+    json!({
+        "tag": "TxSubmitFail",
+        "contents": {
+            "tag": "TxCmdTxSubmitValidationError",
+            "contents": {
+                "tag": "TxValidationErrorInCardanoMode",
+                "contents": {
+                    "kind": "ShelleyTxValidationError",
+                    "era": era_name,
+                    "error": errors,
+                },
+            },
+        },
+    })
 }
 
 #[cfg(test)]
@@ -83,4 +385,263 @@ mod tests {
         let valid_tx = hex::decode("84a300d90102818258205176274bef11d575edd6aa72392aaf993a07f736e70239c1fb22d4b1426b22bc01018282583900ddf1eb9ce2a1561e8f156991486b97873fb6969190cbc99ddcb3816621dcb03574152623414ed354d2d8f50e310f3f2e7d167cb20e5754271a003d09008258390099a5cb0fa8f19aba38cacf8a243d632149129f882df3a8e67f6bd512bcb0cde66a545e9fbc7ca4492f39bca1f4f265cc1503b4f7d6ff205c1b000000024f127a7c021a0002a2ada100d90102818258208b83e59abc9d7a66a77be5e0825525546a595174f8b929f164fcf5052d7aab7b5840709c64556c946abf267edd90b8027343d065193ef816529d8fa7aa2243f1fd2ec27036a677974199e2264cb582d01925134b9a20997d5a734da298df957eb002f5f6").unwrap();
         assert!(validate_tx_cbor(&valid_tx).is_ok());
     }
+
+    /// The same basic transaction as above: 294 bytes, a fee of 172717.
+    const BASIC_TX: &str = "84a300d90102818258205176274bef11d575edd6aa72392aaf993a07f736e70239c1fb22d4b1426b22bc01018282583900ddf1eb9ce2a1561e8f156991486b97873fb6969190cbc99ddcb3816621dcb03574152623414ed354d2d8f50e310f3f2e7d167cb20e5754271a003d09008258390099a5cb0fa8f19aba38cacf8a243d632149129f882df3a8e67f6bd512bcb0cde66a545e9fbc7ca4492f39bca1f4f265cc1503b4f7d6ff205c1b000000024f127a7c021a0002a2ada100d90102818258208b83e59abc9d7a66a77be5e0825525546a595174f8b929f164fcf5052d7aab7b5840709c64556c946abf267edd90b8027343d065193ef816529d8fa7aa2243f1fd2ec27036a677974199e2264cb582d01925134b9a20997d5a734da298df957eb002f5f6";
+
+    fn params() -> PhaseOneParams {
+        PhaseOneParams {
+            epoch: 500,
+            era: CONWAY_ERA,
+            max_tx_size: 16384,
+            min_fee_a: 44,
+            min_fee_b: 155381,
+            coins_per_utxo_byte: 4310,
+            price_mem: (577, 10_000),
+            price_steps: (721, 10_000_000),
+            collateral_percentage: 150,
+        }
+    }
+
+    fn context() -> PhaseOneContext {
+        PhaseOneContext {
+            slot: 100_000_000,
+            collateral_inputs_coin: None,
+        }
+    }
+
+    fn phase_one_errors(params: &PhaseOneParams) -> Vec<String> {
+        let tx = hex::decode(BASIC_TX).unwrap();
+
+        check_phase_one_errors(&tx, params, &context())
+    }
+
+    fn check_phase_one_errors(
+        tx: &[u8],
+        params: &PhaseOneParams,
+        context: &PhaseOneContext,
+    ) -> Vec<String> {
+        match check_phase_one(tx, params, context) {
+            Ok(()) => vec![],
+            Err(TxSubmitError::PhaseOne(json)) => {
+                serde_json::from_value(json["contents"]["contents"]["contents"]["error"].clone())
+                    .unwrap()
+            },
+            Err(err) => panic!("unexpected error: {err:?}"),
+        }
+    }
+
+    #[test]
+    fn test_check_phase_one_ok() {
+        assert!(phase_one_errors(&params()).is_empty());
+    }
+
+    #[test]
+    fn test_check_phase_one_failures() {
+        let params = PhaseOneParams {
+            max_tx_size: 200,
+            min_fee_b: 200_000,
+            coins_per_utxo_byte: 20_000,
+            ..params()
+        };
+
+        assert_eq!(
+            phase_one_errors(&params),
+            vec![
+                "ConwayUtxowFailure (UtxoFailure (MaxTxSizeUTxO 294 200))".to_string(),
+                "ConwayUtxowFailure (UtxoFailure (FeeTooSmallUTxO (Coin 212936) (Coin 172717)))"
+                    .to_string(),
+                "ConwayUtxowFailure (UtxoFailure (BabbageOutputTooSmallUTxO [(TxOut #0, Coin 4500000)]))"
+                    .to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_check_phase_one_babbage_failures() {
+        let params = PhaseOneParams {
+            era: BABBAGE_ERA,
+            min_fee_b: 200_000,
+            coins_per_utxo_byte: 20_000,
+            ..params()
+        };
+
+        assert_eq!(
+            phase_one_errors(&params),
+            vec![
+                "UtxowFailure (UtxoFailure (AlonzoInBabbageUtxoPredFailure (FeeTooSmallUTxO (Coin 212936) (Coin 172717))))".to_string(),
+                "UtxowFailure (UtxoFailure (BabbageOutputTooSmallUTxO [(TxOut #0, Coin 4500000)]))"
+                    .to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_check_phase_one_other_eras() {
+        let params = PhaseOneParams {
+            max_tx_size: 200,
+            ..params()
+        };
+
+        // Alonzo had other rules, and we don’t know future ones yet:
+        for era in [4, 7] {
+            assert!(
+                phase_one_errors(&PhaseOneParams {
+                    era,
+                    ..params.clone()
+                })
+                .is_empty()
+            );
+        }
+    }
+
+    /// A Conway transaction spending one input into one 2 ADA output, with an
+    /// optional validity interval, and optional script and collateral inputs.
+    fn custom_tx(
+        invalid_before: Option<u64>,
+        invalid_hereafter: Option<u64>,
+        with_redeemer: bool,
+        with_collateral: bool,
+    ) -> Vec<u8> {
+        use pallas_codec::minicbor::Encoder;
+
+        let input = |e: &mut Encoder<&mut Vec<u8>>| {
+            e.array(1).unwrap().array(2).unwrap();
+            e.bytes(&[0x11; 32]).unwrap().u8(0).unwrap();
+        };
+
+        let mut cbor = Vec::new();
+        let e = &mut Encoder::new(&mut cbor);
+
+        let body_fields = 3
+            + u64::from(invalid_before.is_some())
+            + u64::from(invalid_hereafter.is_some())
+            + u64::from(with_collateral);
+
+        e.array(4).unwrap().map(body_fields).unwrap();
+        e.u8(0).unwrap();
+        input(e);
+        e.u8(1).unwrap().array(1).unwrap().array(2).unwrap();
+        e.bytes(
+            &hex::decode("61e15900a9a62a8fb01f936a25bf54af209c7ed1248c4e5abd05ec4e76").unwrap(),
+        )
+        .unwrap()
+        .u64(2_000_000)
+        .unwrap();
+        e.u8(2).unwrap().u64(1_000_000).unwrap();
+        if let Some(slot) = invalid_hereafter {
+            e.u8(3).unwrap().u64(slot).unwrap();
+        }
+        if let Some(slot) = invalid_before {
+            e.u8(8).unwrap().u64(slot).unwrap();
+        }
+        if with_collateral {
+            e.u8(13).unwrap();
+            input(e);
+        }
+
+        if with_redeemer {
+            // A spending redeemer with `()` and 1000 memory units and CPU steps:
+            e.map(1).unwrap().u8(5).unwrap().array(1).unwrap();
+            e.array(4).unwrap().u8(0).unwrap().u8(0).unwrap();
+            e.tag(pallas_codec::minicbor::data::Tag::new(121))
+                .unwrap()
+                .array(0)
+                .unwrap();
+            e.array(2).unwrap().u16(1000).unwrap().u16(1000).unwrap();
+        } else {
+            e.map(0).unwrap();
+        }
+
+        e.bool(true).unwrap().null().unwrap();
+
+        cbor
+    }
+
+    fn custom_tx_errors(tx: &[u8], context: &PhaseOneContext) -> Vec<String> {
+        check_phase_one_errors(tx, &params(), context)
+    }
+
+    #[test]
+    fn test_check_phase_one_validity_interval() {
+        let outside = |interval: &str| {
+            vec![format!(
+                "ConwayUtxowFailure (UtxoFailure (OutsideValidityIntervalUTxO (ValidityInterval {interval}) (SlotNo 100000000)))"
+            )]
+        };
+
+        let cases = [
+            (None, None, vec![]),
+            (Some(100_000_000), Some(100_000_001), vec![]),
+            (
+                Some(100_000_001),
+                None,
+                outside("{invalidBefore = SJust (SlotNo 100000001), invalidHereafter = SNothing}"),
+            ),
+            (
+                None,
+                Some(100_000_000),
+                outside("{invalidBefore = SNothing, invalidHereafter = SJust (SlotNo 100000000)}"),
+            ),
+        ];
+
+        for (invalid_before, invalid_hereafter, expected) in cases {
+            let tx = custom_tx(invalid_before, invalid_hereafter, false, false);
+
+            assert_eq!(
+                custom_tx_errors(&tx, &context()),
+                expected,
+                "{invalid_before:?}, {invalid_hereafter:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_check_phase_one_collateral() {
+        let cases = [
+            // Without scripts, collateral doesn’t matter:
+            (false, false, Some(0), vec![]),
+            (true, true, Some(1_500_000), vec![]),
+            // Unresolved collateral inputs are left to the node:
+            (true, true, None, vec![]),
+            (
+                true,
+                false,
+                None,
+                vec!["ConwayUtxowFailure (UtxoFailure (NoCollateralInputs))".to_string()],
+            ),
+            (
+                true,
+                true,
+                Some(1_499_999),
+                vec![
+                    "ConwayUtxowFailure (UtxoFailure (InsufficientCollateral (DeltaCoin 1499999) (Coin 1500000)))"
+                        .to_string(),
+                ],
+            ),
+        ];
+
+        for (with_redeemer, with_collateral, collateral_inputs_coin, expected) in cases {
+            let tx = custom_tx(None, None, with_redeemer, with_collateral);
+            let context = PhaseOneContext {
+                collateral_inputs_coin,
+                ..context()
+            };
+
+            assert_eq!(
+                custom_tx_errors(&tx, &context),
+                expected,
+                "{with_redeemer}, {with_collateral}, {collateral_inputs_coin:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_min_fee() {
+        let tx = hex::decode(BASIC_TX).unwrap();
+        let tx = MultiEraTx::decode(&tx).unwrap();
+
+        // No redeemers, so only the size counts:
+        assert_eq!(min_fee(&params(), 294, &tx), 44 * 294 + 155381);
+    }
 }
@@ -0,0 +1,666 @@
+use super::{connection::NodeClient, era_history::EraHistory, pool::NodePool};
+use bf_common::errors::BlockfrostError;
+use pallas_network::miniprotocols::{
+    Point,
+    chainsync::{BlockContent, NextResponse, Tip},
+};
+use pallas_traverse::MultiEraBlock;
+use serde::Serialize;
+use std::{collections::VecDeque, sync::Arc};
+use tokio::{
+    sync::{RwLock, broadcast},
+    time::{Duration, sleep},
+};
+use tracing::{error, info};
+
+/// Rollbacks are never deeper than the security parameter `k` (2160 on all
+/// well-known networks), so that’s how many recent blocks we remember.
+pub(crate) const MAX_ROLLBACK_DEPTH: usize = 2160;
+
+/// Subscribers that fall behind by more than this many events get lagged.
+const EVENTS_CAPACITY: usize = 1024;
+
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct BlockHeader {
+    pub hash: String,
+    pub height: u64,
+    pub slot: u64,
+    pub era: u16,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct ChainPoint {
+    pub hash: String,
+    pub slot: u64,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ChainEvent {
+    RollForward {
+        block: BlockHeader,
+        tx_hashes: Vec<String>,
+    },
+    /// `point` is `None` for a rollback to the genesis.
+    RollBackward { point: Option<ChainPoint> },
+}
+
+/// The node tip as reported by chain-sync. `point` is `None` at the genesis.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct ChainTip {
+    pub point: Option<ChainPoint>,
+    pub height: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Intersection {
+    /// `None` is the genesis.
+    Found(Option<ChainPoint>),
+    NotFound,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChainSyncTx {
+    pub hash: String,
+    pub cbor: Vec<u8>,
+}
+
+/// What a single block adds to the totals of its epoch.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BlockTotals {
+    pub tx_count: u64,
+    /// Sum of all transaction outputs, in lovelace.
+    pub output: u128,
+    /// Sum of all transaction fees, in lovelace.
+    pub fees: u128,
+}
+
+/// Totals of the blocks of an epoch seen so far by the [`ChainFollower`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EpochTotals {
+    pub epoch: u64,
+    pub first_block_slot: u64,
+    pub last_block_slot: u64,
+    pub block_count: u64,
+    pub tx_count: u64,
+    pub output: u128,
+    pub fees: u128,
+    /// Whether the first block of the epoch was seen, i.e. the follower was
+    /// already following the chain when the epoch started.
+    pub complete: bool,
+}
+
+/// [`EpochTotals`] after each of the recent blocks, so that a rollback only has
+/// to drop the newest ones.
+#[derive(Debug, Default)]
+struct EpochTally {
+    after_block: VecDeque<(u64, EpochTotals)>,
+}
+
+impl EpochTally {
+    fn roll_forward(&mut self, slot: u64, epoch: u64, block: BlockTotals) {
+        let totals = match self.after_block.back() {
+            Some((_, previous)) if previous.epoch == epoch => EpochTotals {
+                last_block_slot: slot,
+                block_count: previous.block_count + 1,
+                tx_count: previous.tx_count + block.tx_count,
+                output: previous.output + block.output,
+                fees: previous.fees + block.fees,
+                ..previous.clone()
+            },
+            // We only know this is the first block of the epoch if we saw the
+            // last one of the previous epoch:
+            previous => EpochTotals {
+                epoch,
+                first_block_slot: slot,
+                last_block_slot: slot,
+                block_count: 1,
+                tx_count: block.tx_count,
+                output: block.output,
+                fees: block.fees,
+                complete: previous.is_some(),
+            },
+        };
+
+        self.after_block.push_back((slot, totals));
+        if self.after_block.len() > MAX_ROLLBACK_DEPTH {
+            self.after_block.pop_front();
+        }
+    }
+
+    /// Drops all blocks after `point`.
+    fn roll_back(&mut self, point: Option<&ChainPoint>) {
+        match point {
+            Some(point) => {
+                while self
+                    .after_block
+                    .back()
+                    .is_some_and(|(slot, _)| *slot > point.slot)
+                {
+                    self.after_block.pop_back();
+                }
+            },
+            None => self.after_block.clear(),
+        }
+    }
+
+    fn current(&self) -> Option<&EpochTotals> {
+        self.after_block.back().map(|(_, totals)| totals)
+    }
+}
+
+/// A full block, for clients that follow the chain themselves.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChainSyncBlock {
+    pub header: BlockHeader,
+    /// `None` for the first block after the genesis.
+    pub ancestor: Option<String>,
+    pub txs: Vec<ChainSyncTx>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChainSyncStep {
+    RollForward {
+        block: ChainSyncBlock,
+        tip: ChainTip,
+    },
+    RollBackward {
+        point: Option<ChainPoint>,
+        tip: ChainTip,
+    },
+}
+
+/// Follows the chain with the N2C chain-sync mini-protocol, starting at the
+/// node tip, and publishes every [`ChainEvent`] to its subscribers.
+///
+/// It can be safely cloned. **Note**: it keeps its own
+/// [`crate::pool::DedicatedConnection`], outside of the [`NodePool`].
+#[derive(Clone)]
+pub struct ChainFollower {
+    tip: Arc<RwLock<Option<BlockHeader>>>,
+    epoch_tally: Arc<RwLock<EpochTally>>,
+    events: broadcast::Sender<ChainEvent>,
+}
+
+impl ChainFollower {
+    /// Starts following the chain in the background.
+    pub fn spawn(node: NodePool) -> Self {
+        let (events, _) = broadcast::channel(EVENTS_CAPACITY);
+
+        let self_ = Self {
+            tip: Arc::new(RwLock::new(None)),
+            epoch_tally: Arc::new(RwLock::new(EpochTally::default())),
+            events,
+        };
+
+        let follower = self_.clone();
+        tokio::spawn(async move { follower.run(node).await });
+
+        self_
+    }
+
+    /// Subscribes to all events from now on.
+    pub fn subscribe(&self) -> broadcast::Receiver<ChainEvent> {
+        self.events.subscribe()
+    }
+
+    /// The most recent block, already taking rollbacks into account. It’s
+    /// `None` until the first block arrives after a (re)connection.
+    pub async fn tip(&self) -> Option<BlockHeader> {
+        self.tip.read().await.clone()
+    }
+
+    /// Totals of the epoch of the most recent block, already taking rollbacks
+    /// into account. It’s `None` until the follower sees a whole epoch, i.e.
+    /// one that started after the follower did.
+    pub async fn epoch_totals(&self) -> Option<EpochTotals> {
+        self.epoch_tally
+            .read()
+            .await
+            .current()
+            .filter(|totals| totals.complete)
+            .cloned()
+    }
+
+    async fn run(&self, node: NodePool) {
+        let mut recent = VecDeque::new();
+
+        loop {
+            if let Err(err) = self.follow(&node, &mut recent).await {
+                error!(
+                    "ChainFollower: {}, reconnecting in {RECONNECT_DELAY:?}",
+                    err.message
+                );
+            }
+
+            sleep(RECONNECT_DELAY).await;
+        }
+    }
+
+    async fn follow(
+        &self,
+        node: &NodePool,
+        recent: &mut VecDeque<BlockHeader>,
+    ) -> Result<(), BlockfrostError> {
+        let mut node = node.dedicated().await?;
+
+        // Resume where we left off, or start at the tip if it’s no longer on the chain:
+        let intersection = node
+            .chainsync_intersect(intersection_candidates(recent))
+            .await?;
+
+        if intersection.is_none() {
+            recent.clear();
+            self.epoch_tally.write().await.roll_back(None);
+            node.chainsync_intersect_tip().await?;
+        }
+
+        let mut era_history = node.era_history().await?;
+
+        info!("ChainFollower: following the chain");
+
+        loop {
+            match node.chainsync_next().await? {
+                NextResponse::RollForward(block, _) => {
+                    let (header, tx_hashes, block_totals) = decode_block(&block)?;
+
+                    let mut epoch = slot_to_epoch(&era_history, header.slot)?;
+                    let mut epoch_tally = self.epoch_tally.write().await;
+                    if epoch_tally
+                        .current()
+                        .is_some_and(|totals| totals.epoch != epoch)
+                    {
+                        // A new epoch can also be a new era, with other epoch lengths:
+                        era_history = node.era_history().await?;
+                        epoch = slot_to_epoch(&era_history, header.slot)?;
+                    }
+                    epoch_tally.roll_forward(header.slot, epoch, block_totals);
+                    drop(epoch_tally);
+
+                    recent.push_back(header.clone());
+                    if recent.len() > MAX_ROLLBACK_DEPTH {
+                        recent.pop_front();
+                    }
+
+                    self.publish(
+                        recent,
+                        ChainEvent::RollForward {
+                            block: header,
+                            tx_hashes,
+                        },
+                    )
+                    .await;
+                },
+                NextResponse::RollBackward(point, _) => {
+                    let point = chain_point(point);
+
+                    roll_back(recent, point.as_ref());
+                    self.epoch_tally.write().await.roll_back(point.as_ref());
+
+                    self.publish(recent, ChainEvent::RollBackward { point })
+                        .await;
+                },
+                NextResponse::Await => {},
+            }
+        }
+    }
+
+    async fn publish(&self, recent: &VecDeque<BlockHeader>, event: ChainEvent) {
+        *self.tip.write().await = recent.back().cloned();
+
+        // No subscribers is not an error:
+        let _ = self.events.send(event);
+    }
+}
+
+impl NodeClient {
+    /// Finds the most recent of `points` (`None` being the genesis) on the
+    /// node’s chain, for clients that follow the chain themselves with
+    /// [`Self::chainsync_next_block`].
+    ///
+    /// **Note**: the chain-sync state lives in the connection, so the caller
+    /// has to keep this very [`NodeClient`] for the whole session.
+    pub async fn chainsync_find_intersection(
+        &mut self,
+        points: &[Option<ChainPoint>],
+    ) -> Result<(Intersection, ChainTip), BlockfrostError> {
+        let points = points
+            .iter()
+            .map(|point| match point {
+                None => Ok(Point::Origin),
+                Some(point) => hex::decode(&point.hash)
+                    .map(|hash| Point::Specific(point.slot, hash))
+                    .map_err(|_| {
+                        BlockfrostError::custom_400(format!("Invalid block hash: {}", point.hash))
+                    }),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let client = self.client.as_mut().unwrap().chainsync();
+
+        let result = client.find_intersect(points).await;
+
+        let (point, tip) = result.map_err(|err| self.chainsync_error(err))?;
+
+        let intersection = match point {
+            Some(point) => Intersection::Found(chain_point(point)),
+            None => Intersection::NotFound,
+        };
+
+        Ok((intersection, chain_tip(tip)))
+    }
+
+    /// The next chain-sync step after [`Self::chainsync_find_intersection`],
+    /// waiting for a new block at the tip.
+    pub async fn chainsync_next_block(&mut self) -> Result<ChainSyncStep, BlockfrostError> {
+        loop {
+            match self.chainsync_next().await? {
+                NextResponse::RollForward(block, tip) => {
+                    return Ok(ChainSyncStep::RollForward {
+                        block: decode_full_block(&block)?,
+                        tip: chain_tip(tip),
+                    });
+                },
+                NextResponse::RollBackward(point, tip) => {
+                    return Ok(ChainSyncStep::RollBackward {
+                        point: chain_point(point),
+                        tip: chain_tip(tip),
+                    });
+                },
+                NextResponse::Await => {},
+            }
+        }
+    }
+
+    /// Finds the most recent of `points` on the node’s chain. Returns `None` if
+    /// there’s none.
+    async fn chainsync_intersect(
+        &mut self,
+        points: Vec<Point>,
+    ) -> Result<Option<Point>, BlockfrostError> {
+        if points.is_empty() {
+            return Ok(None);
+        }
+
+        let client = self.client.as_mut().unwrap().chainsync();
+
+        let result = client.find_intersect(points).await;
+
+        result
+            .map(|(point, _)| point)
+            .map_err(|err| self.chainsync_error(err))
+    }
+
+    async fn chainsync_intersect_tip(&mut self) -> Result<Point, BlockfrostError> {
+        let client = self.client.as_mut().unwrap().chainsync();
+
+        let result = client.intersect_tip().await;
+
+        result.map_err(|err| self.chainsync_error(err))
+    }
+
+    async fn chainsync_next(&mut self) -> Result<NextResponse<BlockContent>, BlockfrostError> {
+        let client = self.client.as_mut().unwrap().chainsync();
+
+        let result = client.request_or_await_next().await;
+
+        result.map_err(|err| self.chainsync_error(err))
+    }
+
+    /// A chain-sync client in an unknown state is useless, so the connection
+    /// can’t go back to the pool.
+    fn chainsync_error(&mut self, err: impl std::fmt::Display) -> BlockfrostError {
+        let message = format!("Chain-sync error: {err}");
+        self.invalidate_connection(&message);
+
+        BlockfrostError::internal_server_error(message)
+    }
+}
+
+fn slot_to_epoch(era_history: &EraHistory, slot: u64) -> Result<u64, BlockfrostError> {
+    era_history.slot_to_epoch(slot).ok_or_else(|| {
+        BlockfrostError::internal_server_error(format!("Slot {slot} is out of the era history"))
+    })
+}
+
+fn decode_block(
+    block: &BlockContent,
+) -> Result<(BlockHeader, Vec<String>, BlockTotals), BlockfrostError> {
+    let block = MultiEraBlock::decode(&block.0).map_err(|e| {
+        BlockfrostError::internal_server_error(format!("Failed to decode block: {e}"))
+    })?;
+
+    let header = BlockHeader {
+        hash: block.hash().to_string(),
+        height: block.number(),
+        slot: block.slot(),
+        era: u16::from(block.era()),
+    };
+
+    let txs = block.txs();
+
+    let tx_hashes = txs.iter().map(|tx| tx.hash().to_string()).collect();
+
+    let totals = BlockTotals {
+        tx_count: txs.len() as u64,
+        output: txs
+            .iter()
+            .flat_map(|tx| tx.outputs())
+            .map(|output| u128::from(output.value().coin()))
+            .sum(),
+        fees: txs
+            .iter()
+            .map(|tx| u128::from(tx.fee().unwrap_or_default()))
+            .sum(),
+    };
+
+    Ok((header, tx_hashes, totals))
+}
+
+fn decode_full_block(block: &BlockContent) -> Result<ChainSyncBlock, BlockfrostError> {
+    let block = MultiEraBlock::decode(&block.0).map_err(|e| {
+        BlockfrostError::internal_server_error(format!("Failed to decode block: {e}"))
+    })?;
+
+    let header = BlockHeader {
+        hash: block.hash().to_string(),
+        height: block.number(),
+        slot: block.slot(),
+        era: u16::from(block.era()),
+    };
+
+    let txs = block
+        .txs()
+        .iter()
+        .map(|tx| ChainSyncTx {
+            hash: tx.hash().to_string(),
+            cbor: tx.encode(),
+        })
+        .collect();
+
+    Ok(ChainSyncBlock {
+        header,
+        ancestor: block.header().previous_hash().map(|hash| hash.to_string()),
+        txs,
+    })
+}
+
+fn chain_point(point: Point) -> Option<ChainPoint> {
+    match point {
+        Point::Origin => None,
+        Point::Specific(slot, hash) => Some(ChainPoint {
+            hash: hex::encode(hash),
+            slot,
+        }),
+    }
+}
+
+fn chain_tip(tip: Tip) -> ChainTip {
+    ChainTip {
+        point: chain_point(tip.0),
+        height: tip.1,
+    }
+}
+
+/// Drops all blocks after `point`.
+fn roll_back(recent: &mut VecDeque<BlockHeader>, point: Option<&ChainPoint>) {
+    match point {
+        Some(point) => {
+            while recent.back().is_some_and(|block| {
+                block.slot > point.slot || (block.slot == point.slot && block.hash != point.hash)
+            }) {
+                recent.pop_back();
+            }
+        },
+        None => recent.clear(),
+    }
+}
+
+/// The newest blocks first, then exponentially sparser, like `cardano-node`
+/// clients usually do.
+fn intersection_candidates(recent: &VecDeque<BlockHeader>) -> Vec<Point> {
+    let mut candidates = Vec::new();
+    let mut offset = 0;
+
+    while offset < recent.len() {
+        let block = &recent[recent.len() - 1 - offset];
+
+        if let Ok(hash) = hex::decode(&block.hash) {
+            candidates.push(Point::Specific(block.slot, hash));
+        }
+
+        offset = if offset == 0 { 1 } else { offset * 2 };
+    }
+
+    candidates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block(height: u64) -> BlockHeader {
+        BlockHeader {
+            hash: format!("{height:064x}"),
+            height,
+            slot: height * 20,
+            era: 6,
+        }
+    }
+
+    #[test]
+    fn test_roll_back() {
+        let mut recent: VecDeque<_> = (1..=5).map(block).collect();
+
+        roll_back(
+            &mut recent,
+            Some(&ChainPoint {
+                hash: block(3).hash,
+                slot: 60,
+            }),
+        );
+        assert_eq!(recent.back(), Some(&block(3)));
+
+        roll_back(&mut recent, None);
+        assert!(recent.is_empty());
+    }
+
+    fn block_totals(tx_count: u64) -> BlockTotals {
+        BlockTotals {
+            tx_count,
+            output: u128::from(tx_count) * 1_000_000,
+            fees: u128::from(tx_count) * 200_000,
+        }
+    }
+
+    #[test]
+    fn test_epoch_tally() {
+        let mut tally = EpochTally::default();
+
+        // Joined in the middle of epoch 4:
+        tally.roll_forward(100, 4, block_totals(1));
+        assert_eq!(tally.current().map(|totals| totals.complete), Some(false));
+
+        tally.roll_forward(200, 5, block_totals(2));
+        tally.roll_forward(220, 5, block_totals(3));
+        tally.roll_forward(240, 5, block_totals(0));
+
+        assert_eq!(
+            tally.current(),
+            Some(&EpochTotals {
+                epoch: 5,
+                first_block_slot: 200,
+                last_block_slot: 240,
+                block_count: 3,
+                tx_count: 5,
+                output: 5_000_000,
+                fees: 1_000_000,
+                complete: true,
+            })
+        );
+
+        tally.roll_back(Some(&ChainPoint {
+            hash: String::new(),
+            slot: 200,
+        }));
+        assert_eq!(
+            tally
+                .current()
+                .map(|totals| (totals.block_count, totals.tx_count)),
+            Some((1, 2))
+        );
+
+        // Back into the previous epoch, then forward again:
+        tally.roll_back(Some(&ChainPoint {
+            hash: String::new(),
+            slot: 150,
+        }));
+        assert_eq!(tally.current().map(|totals| totals.epoch), Some(4));
+        tally.roll_forward(210, 5, block_totals(4));
+        assert_eq!(
+            tally
+                .current()
+                .map(|totals| (totals.block_count, totals.tx_count, totals.complete)),
+            Some((1, 4, true))
+        );
+
+        tally.roll_back(None);
+        assert_eq!(tally.current(), None);
+    }
+
+    #[test]
+    fn test_intersection_candidates() {
+        let recent: VecDeque<_> = (1..=10).map(block).collect();
+
+        let slots: Vec<_> = intersection_candidates(&recent)
+            .iter()
+            .map(|point| point.slot_or_default())
+            .collect();
+
+        assert_eq!(slots, vec![200, 180, 160, 120, 40]);
+    }
+
+    #[test]
+    fn test_chain_tip() {
+        assert_eq!(
+            chain_tip(Tip(Point::Specific(60, vec![0xab, 0xcd]), 3)),
+            ChainTip {
+                point: Some(ChainPoint {
+                    hash: "abcd".to_string(),
+                    slot: 60,
+                }),
+                height: 3,
+            }
+        );
+
+        assert_eq!(
+            chain_tip(Tip(Point::Origin, 0)),
+            ChainTip {
+                point: None,
+                height: 0,
+            }
+        );
+    }
+}
@@ -1,10 +1,13 @@
+use crate::{
+    cbor::validation::PhaseOneParamsCache, facade::NodeClientFacade, query_cache::QueryCache,
+};
 use bf_common::errors::BlockfrostError;
-use pallas_network::{facades::NodeClient as NodeClientFacade, miniprotocols::localstate};
+use pallas_network::miniprotocols::localstate;
 use std::{boxed::Box, pin::Pin};
 use tokio::time::{Duration, timeout};
 use tracing::error;
 
-/// Our wrapper around [`pallas_network::facades::NodeClient`]. If you only use
+/// Our wrapper around [`NodeClientFacade`]. If you only use
 /// this, you won’t get any deadlocks, inconsistencies, etc.
 pub struct NodeClient {
     /// Note: this is an [`Option`] *only* to satisfy the borrow checker. It’s
@@ -13,7 +16,12 @@ pub struct NodeClient {
     pub(crate) client: Option<NodeClientFacade>,
     pub(crate) connection_id: u64,
     pub(crate) unrecoverable_error_happened: bool,
-    pub(crate) network_magic: u64,
+    pub(crate) socket_path: String,
+    /// Protocol parameters for local phase-1 checks, shared by all connections
+    /// to the same node.
+    pub(crate) phase_one_params: PhaseOneParamsCache,
+    /// See [`Self::with_cached_statequery`].
+    pub(crate) query_cache: QueryCache,
 }
 
 impl NodeClient {
@@ -86,6 +94,11 @@ impl NodeClient {
             .await
     }
 
+    /// The node this connection belongs to.
+    pub fn socket_path(&self) -> &str {
+        &self.socket_path
+    }
+
     /// After you call this, the pool will never use this N2C connection again.
     /// The need to do this arises rarely in normal operation, but it happens.
     pub fn invalidate_connection(&mut self, why: &str) {
@@ -0,0 +1,561 @@
+//! Slot ↔ wall-clock conversions based on the era history of the node, i.e.
+//! the result of the hard-fork `GetInterpreter` query, like both `cardano-cli`
+//! (through `Ouroboros.Consensus.HardFork.History.Qry`) and Ogmios do. Unlike
+//! [`pallas_traverse::wellknown`], this works on custom networks, too.
+
+use super::{
+    connection::NodeClient,
+    ledger_query::{RawCbor, decode_error},
+};
+use bf_common::errors::BlockfrostError;
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use pallas_codec::minicbor::{self, Decode, Decoder, Encode, Encoder, data::Type, encode::Write};
+use pallas_network::miniprotocols::localstate::{self, queries_v16};
+use serde::{Deserialize, Serialize};
+
+/// Start (or end) of an era, relative to the system start.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EraBound {
+    pub time_ms: u64,
+    pub slot: u64,
+    pub epoch: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EraSummary {
+    pub start: EraBound,
+    /// `None` if the era is unbounded, which only happens on testnets that
+    /// never fork.
+    pub end: Option<EraBound>,
+    pub epoch_length: u64,
+    pub slot_length_ms: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EraHistory {
+    pub system_start: DateTime<Utc>,
+    pub eras: Vec<EraSummary>,
+}
+
+/// Response of the `/utils/slot/{slot}/time` and `/utils/time/{unix}/slot`
+/// endpoints.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SlotTime {
+    pub slot: u64,
+    pub epoch: u64,
+    /// UNIX time in seconds.
+    pub time: i64,
+}
+
+impl EraHistory {
+    /// The era `slot` belongs to.
+    ///
+    /// **Note**: the end of the last era is only the horizon up to which the
+    /// node can forecast safely, so we extrapolate past it, as if no hard fork
+    /// ever came after.
+    fn era_of_slot(&self, slot: u64) -> Option<&EraSummary> {
+        self.eras.iter().rev().find(|era| era.start.slot <= slot)
+    }
+
+    /// Wall-clock time at the beginning of `slot`. It’s `None` if that’s too
+    /// far in the future to be represented.
+    pub fn slot_to_time(&self, slot: u64) -> Option<DateTime<Utc>> {
+        let era = self.era_of_slot(slot)?;
+        let relative_ms = (slot - era.start.slot)
+            .checked_mul(era.slot_length_ms)?
+            .checked_add(era.start.time_ms)?;
+
+        self.system_start
+            .checked_add_signed(Duration::try_milliseconds(
+                i64::try_from(relative_ms).ok()?,
+            )?)
+    }
+
+    /// Epoch that `slot` belongs to.
+    pub fn slot_to_epoch(&self, slot: u64) -> Option<u64> {
+        let era = self.era_of_slot(slot)?;
+
+        ((slot - era.start.slot) / era.epoch_length.max(1)).checked_add(era.start.epoch)
+    }
+
+    /// The era `epoch` belongs to, extrapolated past the end of the last one,
+    /// like in [`Self::era_of_slot`].
+    fn era_of_epoch(&self, epoch: u64) -> Option<&EraSummary> {
+        self.eras.iter().rev().find(|era| era.start.epoch <= epoch)
+    }
+
+    /// Absolute slot of the `slot_in_epoch`-th slot of `epoch`. It’s `None` if
+    /// the epoch doesn’t have that many slots.
+    pub fn epoch_slot_to_slot(&self, epoch: u64, slot_in_epoch: u64) -> Option<u64> {
+        let era = self.era_of_epoch(epoch)?;
+
+        if slot_in_epoch >= era.epoch_length {
+            return None;
+        }
+
+        (epoch - era.start.epoch)
+            .checked_mul(era.epoch_length)?
+            .checked_add(era.start.slot)?
+            .checked_add(slot_in_epoch)
+    }
+
+    /// The slot in progress at `time`. It’s `None` if `time` is before the
+    /// system start.
+    pub fn time_to_slot(&self, time: DateTime<Utc>) -> Option<u64> {
+        let relative_ms = u64::try_from((time - self.system_start).num_milliseconds()).ok()?;
+        let era = self
+            .eras
+            .iter()
+            .rev()
+            .find(|era| era.start.time_ms <= relative_ms)?;
+
+        ((relative_ms - era.start.time_ms) / era.slot_length_ms.max(1)).checked_add(era.start.slot)
+    }
+
+    /// `(zero_time, zero_slot, slot_length)` of the current era, in
+    /// milliseconds, as expected by `uplc` for script evaluation.
+    pub fn slot_config(&self) -> Option<(u64, u64, u32)> {
+        let era = self.eras.last()?;
+        let system_start_ms = u64::try_from(self.system_start.timestamp_millis()).ok()?;
+
+        Some((
+            system_start_ms.checked_add(era.start.time_ms)?,
+            era.start.slot,
+            u32::try_from(era.slot_length_ms).ok()?,
+        ))
+    }
+}
+
+/// `GetInterpreter` of `QueryHardFork`, i.e. `[0, [2, [0]]]` on the wire.
+struct GetInterpreter;
+
+impl Encode<()> for GetInterpreter {
+    fn encode<W: Write>(
+        &self,
+        e: &mut Encoder<W>,
+        _ctx: &mut (),
+    ) -> Result<(), minicbor::encode::Error<W::Error>> {
+        e.array(2)?.u8(0)?; // BlockQuery
+        e.array(2)?.u8(2)?; // QueryHardFork
+        e.array(1)?.u8(0)?; // GetInterpreter
+
+        Ok(())
+    }
+}
+
+/// Queries the system start and the era history. Meant to be used inside
+/// [`NodeClient::with_statequery`], together with other queries.
+pub async fn query_era_history(
+    generic_client: &mut localstate::GenericClient,
+) -> Result<EraHistory, BlockfrostError> {
+    let system_start = queries_v16::get_system_start(generic_client).await?;
+    let RawCbor(interpreter) = generic_client.query(GetInterpreter).await?;
+
+    Ok(EraHistory {
+        system_start: system_start_to_utc(system_start)?,
+        eras: decode_era_summaries(&interpreter).map_err(decode_error)?,
+    })
+}
+
+fn system_start_to_utc(
+    system_start: queries_v16::SystemStart,
+) -> Result<DateTime<Utc>, BlockfrostError> {
+    fn big_int_to_i128(i: queries_v16::BigInt) -> Result<i128, String> {
+        match i {
+            queries_v16::BigInt::Int(ii) => Ok(i128::from(ii)),
+            _ => Err(format!("cannot convert {i:?} to i128")),
+        }
+    }
+
+    let year: i32 = big_int_to_i128(system_start.year)
+        .and_then(|i| i32::try_from(i).map_err(|err| err.to_string()))
+        .map_err(|e| {
+            BlockfrostError::internal_server_error(format!("Failed to convert year: {e}"))
+        })?;
+
+    let base_date = Utc
+        .with_ymd_and_hms(year, 1, 1, 0, 0, 0)
+        .single()
+        .ok_or_else(|| BlockfrostError::internal_server_error("Invalid base date".to_string()))?;
+
+    let days = Duration::days(system_start.day_of_year - 1);
+
+    let nanoseconds: i64 = big_int_to_i128(system_start.picoseconds_of_day)
+        .map(|i| i / 1_000)
+        .and_then(|i| i64::try_from(i).map_err(|err| err.to_string()))
+        .map_err(|e| {
+            BlockfrostError::internal_server_error(format!("Failed to convert picoseconds: {e}"))
+        })?;
+
+    Ok(base_date + days + Duration::nanoseconds(nanoseconds))
+}
+
+/// The `Summary` is a list (usually of indefinite length) of
+/// `[start, end or null, params]`.
+fn decode_era_summaries(cbor: &[u8]) -> Result<Vec<EraSummary>, minicbor::decode::Error> {
+    Decoder::new(cbor)
+        .array_iter::<EraSummary>()?
+        .collect::<Result<Vec<_>, _>>()
+}
+
+impl<'b> Decode<'b, ()> for EraSummary {
+    fn decode(d: &mut Decoder<'b>, _ctx: &mut ()) -> Result<Self, minicbor::decode::Error> {
+        d.array()?;
+
+        let start = decode_bound(d)?;
+
+        let end = if d.datatype()? == Type::Null {
+            d.null()?;
+            None
+        } else {
+            Some(decode_bound(d)?)
+        };
+
+        // `[epochSize, slotLength, safeZone, genesisWindow]`, but older nodes
+        // don’t send the `genesisWindow`:
+        let params_len = d.array()?;
+        let epoch_length = d.u64()?;
+        let slot_length_ms = d.u64()?;
+
+        match params_len {
+            Some(len) => {
+                for _ in 2..len {
+                    d.skip()?;
+                }
+            },
+            None => {
+                while d.datatype()? != Type::Break {
+                    d.skip()?;
+                }
+                d.set_position(d.position() + 1);
+            },
+        }
+
+        Ok(EraSummary {
+            start,
+            end,
+            epoch_length,
+            slot_length_ms,
+        })
+    }
+}
+
+/// `[relativeTime, slot, epoch]`, with the time in picoseconds.
+fn decode_bound(d: &mut Decoder<'_>) -> Result<EraBound, minicbor::decode::Error> {
+    d.array()?;
+
+    let time_ps = decode_picoseconds(d)?;
+    let slot = d.u64()?;
+    let epoch = d.u64()?;
+
+    Ok(EraBound {
+        time_ms: u64::try_from(time_ps / 1_000_000_000)
+            .map_err(|_| minicbor::decode::Error::message("relative time out of range"))?,
+        slot,
+        epoch,
+    })
+}
+
+/// Picoseconds easily exceed `u64` on long-running networks, so they can
+/// come as a positive bignum.
+fn decode_picoseconds(d: &mut Decoder<'_>) -> Result<u128, minicbor::decode::Error> {
+    if d.datatype()? != Type::Tag {
+        return Ok(u128::from(d.u64()?));
+    }
+
+    if d.tag()?.as_u64() != 2 {
+        return Err(minicbor::decode::Error::message(
+            "expected a positive bignum",
+        ));
+    }
+
+    let bytes = d.bytes()?;
+    if bytes.len() > 16 {
+        return Err(minicbor::decode::Error::message(
+            "relative time out of range",
+        ));
+    }
+
+    Ok(bytes
+        .iter()
+        .fold(0u128, |acc, &byte| (acc << 8) | u128::from(byte)))
+}
+
+impl NodeClient {
+    /// Fetches the era history of the node.
+    pub async fn era_history(&mut self) -> Result<EraHistory, BlockfrostError> {
+        self.with_cached_statequery(
+            "era_history".to_string(),
+            |generic_client: &mut localstate::GenericClient| {
+                Box::pin(async move { query_era_history(generic_client).await })
+            },
+        )
+        .await
+    }
+
+    /// Converts a slot within an epoch to an absolute slot.
+    pub async fn epoch_slot_to_slot(
+        &mut self,
+        epoch: u64,
+        slot_in_epoch: u64,
+    ) -> Result<u64, BlockfrostError> {
+        let era_history = self.era_history().await?;
+
+        era_history
+            .epoch_slot_to_slot(epoch, slot_in_epoch)
+            .ok_or_else(|| {
+                BlockfrostError::custom_400(
+                    "Missing, out of range or malformed slot number.".to_string(),
+                )
+            })
+    }
+
+    /// Converts a slot to its UNIX time and epoch.
+    pub async fn slot_to_time(&mut self, slot: u64) -> Result<SlotTime, BlockfrostError> {
+        let era_history = self.era_history().await?;
+
+        slot_time(&era_history, slot)
+    }
+
+    /// Finds the slot in progress at the given UNIX time (in seconds).
+    pub async fn time_to_slot(&mut self, unix_time: i64) -> Result<SlotTime, BlockfrostError> {
+        let era_history = self.era_history().await?;
+
+        let time = Utc.timestamp_opt(unix_time, 0).single().ok_or_else(|| {
+            BlockfrostError::custom_400(format!("Invalid UNIX time: {unix_time}"))
+        })?;
+
+        let slot = era_history.time_to_slot(time).ok_or_else(|| {
+            BlockfrostError::custom_400(format!(
+                "{unix_time} is before the network start ({})",
+                era_history.system_start.timestamp()
+            ))
+        })?;
+
+        slot_time(&era_history, slot)
+    }
+}
+
+fn slot_time(era_history: &EraHistory, slot: u64) -> Result<SlotTime, BlockfrostError> {
+    let out_of_range =
+        || BlockfrostError::custom_400(format!("Slot {slot} is out of the era history range"));
+
+    Ok(SlotTime {
+        slot,
+        epoch: era_history.slot_to_epoch(slot).ok_or_else(out_of_range)?,
+        time: era_history
+            .slot_to_time(slot)
+            .ok_or_else(out_of_range)?
+            .timestamp(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The beginning of the Preprod era history, as returned by the node:
+    /// Byron (20 s slots, 21600-slot epochs), then Shelley onwards (1 s slots,
+    /// 432000-slot epochs), the last era unbounded for simplicity.
+    fn preprod() -> EraHistory {
+        let mut cbor = Vec::new();
+        let mut e = minicbor::Encoder::new(&mut cbor);
+
+        e.begin_array().unwrap();
+        // Byron, ends at epoch 4:
+        e.array(3).unwrap();
+        e.array(3)
+            .unwrap()
+            .u8(0)
+            .unwrap()
+            .u8(0)
+            .unwrap()
+            .u8(0)
+            .unwrap();
+        e.array(3)
+            .unwrap()
+            .u64(1_728_000_000_000_000_000)
+            .unwrap()
+            .u64(86_400)
+            .unwrap()
+            .u64(4)
+            .unwrap();
+        e.array(4)
+            .unwrap()
+            .u64(21_600)
+            .unwrap()
+            .u64(20_000)
+            .unwrap()
+            .array(3)
+            .unwrap()
+            .u8(0)
+            .unwrap()
+            .u64(4320)
+            .unwrap()
+            .array(1)
+            .unwrap()
+            .u8(0)
+            .unwrap()
+            .u64(4320)
+            .unwrap();
+        // Shelley onwards, with the start time as a bignum:
+        e.array(3).unwrap();
+        e.array(3)
+            .unwrap()
+            .tag(minicbor::data::Tag::new(2))
+            .unwrap()
+            .bytes(&1_728_000_000_000_000_000u128.to_be_bytes()[8..])
+            .unwrap()
+            .u64(86_400)
+            .unwrap()
+            .u64(4)
+            .unwrap();
+        e.null().unwrap();
+        e.array(3)
+            .unwrap()
+            .u64(432_000)
+            .unwrap()
+            .u64(1_000)
+            .unwrap()
+            .array(1)
+            .unwrap()
+            .u8(0)
+            .unwrap();
+        e.end().unwrap();
+
+        EraHistory {
+            system_start: Utc.timestamp_opt(1_654_041_600, 0).unwrap(),
+            eras: decode_era_summaries(&cbor).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_decode_era_summaries() {
+        let history = preprod();
+
+        assert_eq!(history.eras.len(), 2);
+        assert_eq!(
+            history.eras[1],
+            EraSummary {
+                start: EraBound {
+                    time_ms: 1_728_000_000,
+                    slot: 86_400,
+                    epoch: 4,
+                },
+                end: None,
+                epoch_length: 432_000,
+                slot_length_ms: 1_000,
+            }
+        );
+    }
+
+    #[test]
+    fn test_slot_to_time() {
+        let history = preprod();
+
+        // Byron:
+        assert_eq!(history.slot_to_time(1).unwrap().timestamp(), 1_654_041_620);
+        assert_eq!(history.slot_to_epoch(21_601), Some(1));
+
+        // Shelley onwards:
+        let slot = 86_400 + 432_000 * 10 + 5;
+        assert_eq!(
+            history.slot_to_time(slot).unwrap().timestamp(),
+            1_654_041_600 + 1_728_000 + 432_000 * 10 + 5
+        );
+        assert_eq!(history.slot_to_epoch(slot), Some(14));
+    }
+
+    #[test]
+    fn test_slot_to_time_out_of_range() {
+        let history = preprod();
+
+        assert_eq!(history.slot_to_time(1_000_000_000_000_000), None);
+        assert_eq!(history.slot_to_time(u64::MAX), None);
+
+        let err = slot_time(&history, 1_000_000_000_000_000).unwrap_err();
+        assert_eq!(err.status_code, 400);
+        assert_eq!(
+            err.message,
+            "Slot 1000000000000000 is out of the era history range"
+        );
+    }
+
+    #[test]
+    fn test_time_to_slot() {
+        let history = preprod();
+
+        for slot in [0, 1, 21_600, 86_399, 86_400, 86_401, 100_000_000] {
+            let time = history.slot_to_time(slot).unwrap();
+            assert_eq!(history.time_to_slot(time), Some(slot));
+        }
+
+        // In the middle of a 20 s Byron slot:
+        assert_eq!(
+            history.time_to_slot(Utc.timestamp_opt(1_654_041_630, 0).unwrap()),
+            Some(1)
+        );
+
+        assert_eq!(
+            history.time_to_slot(Utc.timestamp_opt(1_654_041_599, 0).unwrap()),
+            None
+        );
+    }
+
+    /// Mainnet: 208 Byron epochs of 21600 20-second slots, then Shelley onwards.
+    fn mainnet() -> EraHistory {
+        let shelley_start = EraBound {
+            time_ms: 89_856_000_000,
+            slot: 4_492_800,
+            epoch: 208,
+        };
+
+        EraHistory {
+            system_start: Utc.timestamp_opt(1_506_203_091, 0).unwrap(),
+            eras: vec![
+                EraSummary {
+                    start: EraBound {
+                        time_ms: 0,
+                        slot: 0,
+                        epoch: 0,
+                    },
+                    end: Some(shelley_start),
+                    epoch_length: 21_600,
+                    slot_length_ms: 20_000,
+                },
+                EraSummary {
+                    start: shelley_start,
+                    end: None,
+                    epoch_length: 432_000,
+                    slot_length_ms: 1_000,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_epoch_slot_to_slot() {
+        let history = mainnet();
+
+        // Byron:
+        assert_eq!(history.epoch_slot_to_slot(0, 0), Some(0));
+        assert_eq!(history.epoch_slot_to_slot(1, 21_599), Some(43_199));
+        assert_eq!(history.epoch_slot_to_slot(1, 21_600), None);
+
+        // Shelley onwards:
+        assert_eq!(history.epoch_slot_to_slot(208, 0), Some(4_492_800));
+        assert_eq!(history.epoch_slot_to_slot(500, 8_087), Some(130_644_887));
+        assert_eq!(history.slot_to_epoch(130_644_887), Some(500));
+        assert_eq!(history.epoch_slot_to_slot(500, 432_000), None);
+
+        assert_eq!(history.epoch_slot_to_slot(u64::MAX, 0), None);
+    }
+
+    #[test]
+    fn test_slot_config() {
+        assert_eq!(
+            preprod().slot_config(),
+            Some((1_655_769_600_000, 86_400, 1_000))
+        );
+    }
+}
@@ -0,0 +1,555 @@
+use super::connection::NodeClient;
+use crate::{cbor::validation::validate_tx_cbor, era_history::query_era_history};
+use bf_common::errors::BlockfrostError;
+use pallas_addresses::Address;
+use pallas_codec::minicbor;
+use pallas_network::miniprotocols::localstate::{self, queries_v16};
+use pallas_primitives::conway::{Redeemer, RedeemerTag};
+use pallas_traverse::MultiEraTx;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    str::FromStr,
+};
+use tracing::warn;
+
+/// Execution units computed for a single redeemer.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct RedeemerEvaluation {
+    pub purpose: RedeemerPurpose,
+    pub index: u32,
+    pub memory: u64,
+    pub steps: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum RedeemerPurpose {
+    Spend,
+    Mint,
+    Certificate,
+    Withdrawal,
+    Vote,
+    Propose,
+}
+
+impl RedeemerPurpose {
+    /// Name used by Ogmios v5 (`EvaluationResult` keys, e.g. `spend:0`).
+    pub fn as_ogmios_v5(&self) -> &'static str {
+        match self {
+            Self::Spend => "spend",
+            Self::Mint => "mint",
+            Self::Certificate => "certificate",
+            Self::Withdrawal => "withdrawal",
+            Self::Vote => "vote",
+            Self::Propose => "propose",
+        }
+    }
+
+    /// Name used by Ogmios v6 (`validator.purpose`).
+    pub fn as_ogmios_v6(&self) -> &'static str {
+        match self {
+            Self::Spend => "spend",
+            Self::Mint => "mint",
+            Self::Certificate => "publish",
+            Self::Withdrawal => "withdraw",
+            Self::Vote => "vote",
+            Self::Propose => "propose",
+        }
+    }
+}
+
+impl From<&RedeemerTag> for RedeemerPurpose {
+    fn from(tag: &RedeemerTag) -> Self {
+        match tag {
+            RedeemerTag::Spend => Self::Spend,
+            RedeemerTag::Mint => Self::Mint,
+            RedeemerTag::Cert => Self::Certificate,
+            RedeemerTag::Reward => Self::Withdrawal,
+            RedeemerTag::Vote => Self::Vote,
+            RedeemerTag::Propose => Self::Propose,
+        }
+    }
+}
+
+/// Outcome of a script evaluation. Script failures are not an HTTP error, they
+/// are a regular result, just like in Ogmios.
+#[derive(Debug, Clone)]
+pub enum EvaluationResult {
+    Success(Vec<RedeemerEvaluation>),
+    Failure(EvaluationFailure),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EvaluationFailure {
+    /// The scripts could not be run at all, e.g. an input could not be resolved,
+    /// or the cost model of a Plutus version is missing.
+    CannotCreateEvaluationContext(String),
+    /// A script was run, and it failed.
+    ScriptFailure {
+        purpose: RedeemerPurpose,
+        index: u32,
+        error: String,
+        traces: Vec<String>,
+    },
+}
+
+impl From<uplc::tx::error::Error> for EvaluationFailure {
+    fn from(err: uplc::tx::error::Error) -> Self {
+        use uplc::tx::error::Error;
+
+        match err {
+            Error::RedeemerError { tag, index, err } => match (*err, purpose_of_uplc_tag(&tag)) {
+                (Error::Machine(error, _, traces), Some(purpose)) => Self::ScriptFailure {
+                    purpose,
+                    index,
+                    error: error.to_string(),
+                    traces: traces.iter().map(|trace| trace.to_string()).collect(),
+                },
+                (err, _) => Self::CannotCreateEvaluationContext(err.to_string()),
+            },
+            err => Self::CannotCreateEvaluationContext(err.to_string()),
+        }
+    }
+}
+
+/// Maps the redeemer tags of `uplc::tx::redeemer_tag_to_string`.
+fn purpose_of_uplc_tag(tag: &str) -> Option<RedeemerPurpose> {
+    match tag {
+        "Spend" => Some(RedeemerPurpose::Spend),
+        "Mint" => Some(RedeemerPurpose::Mint),
+        "Publish" => Some(RedeemerPurpose::Certificate),
+        "Withdraw" => Some(RedeemerPurpose::Withdrawal),
+        "Vote" => Some(RedeemerPurpose::Vote),
+        "Propose" => Some(RedeemerPurpose::Propose),
+        _ => None,
+    }
+}
+
+/// A transaction input supplied by the caller, in the Ogmios format.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct AdditionalTxIn {
+    #[serde(rename = "txId")]
+    pub tx_id: String,
+    pub index: u64,
+}
+
+/// A transaction output supplied by the caller, in the Ogmios format.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AdditionalTxOut {
+    pub address: String,
+    pub value: AdditionalValue,
+    #[serde(rename = "datumHash", default)]
+    pub datum_hash: Option<String>,
+    /// CBOR-encoded inline datum.
+    #[serde(default)]
+    pub datum: Option<String>,
+    /// Reference script, e.g. `{"plutus:v2": "<cbor hex>"}`.
+    #[serde(default)]
+    pub script: Option<BTreeMap<String, String>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AdditionalValue {
+    pub coins: u64,
+    /// Keys are `<policy_id>.<asset_name_hex>` or just `<policy_id>`.
+    #[serde(default)]
+    pub assets: BTreeMap<String, u64>,
+}
+
+pub type AdditionalUtxo = (AdditionalTxIn, AdditionalTxOut);
+
+impl NodeClient {
+    /// Evaluates all Plutus scripts of the transaction and returns execution
+    /// units for every redeemer. Inputs not present in `additional_utxos` are
+    /// resolved against the node’s ledger state.
+    pub async fn evaluate_transaction(
+        &mut self,
+        tx: Vec<u8>,
+        additional_utxos: Vec<AdditionalUtxo>,
+    ) -> Result<EvaluationResult, BlockfrostError> {
+        validate_tx_cbor(&tx)?;
+
+        let mut utxos = encode_additional_utxos(&additional_utxos)?;
+        let supplied: BTreeSet<(Vec<u8>, u64)> = additional_utxos
+            .iter()
+            .filter_map(|(txin, _)| hex::decode(&txin.tx_id).ok().map(|h| (h, txin.index)))
+            .collect();
+
+        let to_resolve: BTreeSet<queries_v16::TransactionInput> = collect_inputs(&tx)?
+            .into_iter()
+            .filter(|input| !supplied.contains(&(input.transaction_id.to_vec(), input.index)))
+            .collect();
+
+        let (cost_models, max_tx_ex_units, resolved, era_history) = self
+            .with_statequery(|generic_client: &mut localstate::GenericClient| {
+                Box::pin(async move {
+                    let era = queries_v16::get_current_era(generic_client).await?;
+                    let era_history = query_era_history(generic_client).await?;
+
+                    let pparams = queries_v16::get_current_pparams(generic_client, era).await?;
+
+                    let resolved = if to_resolve.is_empty() {
+                        Vec::new()
+                    } else {
+                        queries_v16::get_utxo_by_txin(generic_client, era, to_resolve)
+                            .await?
+                            .to_vec()
+                    };
+
+                    Ok((
+                        pparams.cost_models_for_script_languages,
+                        pparams.max_tx_ex_units,
+                        resolved,
+                        era_history,
+                    ))
+                })
+            })
+            .await?;
+
+        for (utxo, output) in resolved {
+            let input = queries_v16::TransactionInput {
+                transaction_id: utxo.transaction_id,
+                index: u64::from(utxo.index),
+            };
+
+            utxos.push((encode(&input)?, encode(&output)?));
+        }
+
+        let cost_models = cost_models.map(encode_cost_models).transpose()?;
+
+        let initial_budget = max_tx_ex_units
+            .map(|units| (units.steps, units.mem))
+            .ok_or_else(|| {
+                BlockfrostError::internal_server_error(
+                    "Protocol parameters are missing maxTxExecutionUnits".to_string(),
+                )
+            })?;
+
+        let slot_config = era_history.slot_config().ok_or_else(|| {
+            BlockfrostError::internal_server_error("Node returned an empty era history".to_string())
+        })?;
+
+        // Script evaluation is CPU-bound, so let’s not block the async runtime:
+        tokio::task::spawn_blocking(move || {
+            match uplc::tx::eval_phase_two_raw(
+                &tx,
+                &utxos,
+                cost_models.as_deref(),
+                initial_budget,
+                slot_config,
+                false,
+                |_| (),
+            ) {
+                Ok(redeemers) => redeemers
+                    .iter()
+                    .map(|(bytes, _)| decode_redeemer(bytes))
+                    .collect::<Result<Vec<_>, _>>()
+                    .map(EvaluationResult::Success),
+                Err(e) => {
+                    warn!("Script evaluation failed: {e}, CBOR: {}", hex::encode(&tx));
+                    Ok(EvaluationResult::Failure(e.into()))
+                },
+            }
+        })
+        .await
+        .map_err(|e| {
+            BlockfrostError::internal_server_error(format!("Script evaluation panicked: {e}"))
+        })?
+    }
+}
+
+/// Collects all inputs that have to be resolved for script evaluation: spent
+/// inputs, collateral inputs and reference inputs.
+fn collect_inputs(tx: &[u8]) -> Result<Vec<queries_v16::TransactionInput>, BlockfrostError> {
+    let tx = MultiEraTx::decode(tx)
+        .map_err(|e| BlockfrostError::custom_400(format!("Failed to decode transaction: {e}")))?;
+
+    Ok(tx
+        .inputs()
+        .iter()
+        .chain(tx.collateral().iter())
+        .chain(tx.reference_inputs().iter())
+        .map(|input| queries_v16::TransactionInput {
+            transaction_id: *input.hash(),
+            index: input.index(),
+        })
+        .collect())
+}
+
+fn decode_redeemer(bytes: &[u8]) -> Result<RedeemerEvaluation, BlockfrostError> {
+    let redeemer: Redeemer = minicbor::decode(bytes).map_err(|e| {
+        BlockfrostError::internal_server_error(format!("Failed to decode evaluated redeemer: {e}"))
+    })?;
+
+    Ok(RedeemerEvaluation {
+        purpose: RedeemerPurpose::from(&redeemer.tag),
+        index: redeemer.index,
+        memory: redeemer.ex_units.mem,
+        steps: redeemer.ex_units.steps,
+    })
+}
+
+fn encode<T: minicbor::Encode<()>>(value: &T) -> Result<Vec<u8>, BlockfrostError> {
+    minicbor::to_vec(value)
+        .map_err(|e| BlockfrostError::internal_server_error(format!("CBOR encoding failed: {e}")))
+}
+
+/// Encodes cost models as a CBOR map from the Plutus language to its cost model,
+/// i.e. the `CostMdls` format expected by the evaluator.
+fn encode_cost_models(models: queries_v16::CostModels) -> Result<Vec<u8>, BlockfrostError> {
+    let mut map: BTreeMap<u64, Vec<i64>> = BTreeMap::new();
+
+    if let Some(model) = models.plutus_v1 {
+        map.insert(0, model);
+    }
+    if let Some(model) = models.plutus_v2 {
+        map.insert(1, model);
+    }
+    if let Some(model) = models.plutus_v3 {
+        map.insert(2, model);
+    }
+
+    encode(&map)
+}
+
+/// CBOR-encoded `(TransactionInput, TransactionOutput)` pairs, as `uplc` wants them.
+type RawUtxos = Vec<(Vec<u8>, Vec<u8>)>;
+
+fn encode_additional_utxos(utxos: &[AdditionalUtxo]) -> Result<RawUtxos, BlockfrostError> {
+    utxos
+        .iter()
+        .map(|(txin, txout)| Ok((encode_txin(txin)?, encode_txout(txout)?)))
+        .collect()
+}
+
+fn decode_hex_field(value: &str, field: &str) -> Result<Vec<u8>, BlockfrostError> {
+    hex::decode(value)
+        .map_err(|_| BlockfrostError::custom_400(format!("Invalid hex in {field}: {value}")))
+}
+
+fn encode_txin(txin: &AdditionalTxIn) -> Result<Vec<u8>, BlockfrostError> {
+    let tx_id = decode_hex_field(&txin.tx_id, "txId")?;
+
+    if tx_id.len() != 32 {
+        return Err(BlockfrostError::custom_400(format!(
+            "Invalid txId: {}",
+            txin.tx_id
+        )));
+    }
+
+    let mut buffer = Vec::new();
+    let mut e = minicbor::Encoder::new(&mut buffer);
+
+    e.array(2)
+        .and_then(|e| e.bytes(&tx_id))
+        .and_then(|e| e.u64(txin.index))
+        .map_err(|e| {
+            BlockfrostError::internal_server_error(format!("CBOR encoding failed: {e}"))
+        })?;
+
+    Ok(buffer)
+}
+
+/// Encodes a caller-supplied output as a post-Alonzo (map-based) transaction output.
+fn encode_txout(txout: &AdditionalTxOut) -> Result<Vec<u8>, BlockfrostError> {
+    let address = Address::from_str(&txout.address)
+        .map_err(|_| BlockfrostError::invalid_address())?
+        .to_vec();
+
+    // policy id → asset name → quantity
+    let mut assets: BTreeMap<Vec<u8>, BTreeMap<Vec<u8>, u64>> = BTreeMap::new();
+
+    for (unit, quantity) in txout.value.assets.iter() {
+        let (policy_id, asset_name) = unit.split_once('.').unwrap_or((unit, ""));
+        let policy_id = decode_hex_field(policy_id, "value.assets")?;
+
+        if policy_id.len() != 28 {
+            return Err(BlockfrostError::custom_400(format!(
+                "Invalid policy id in value.assets: {unit}"
+            )));
+        }
+
+        assets
+            .entry(policy_id)
+            .or_default()
+            .insert(decode_hex_field(asset_name, "value.assets")?, *quantity);
+    }
+
+    let script = match &txout.script {
+        None => None,
+        Some(script) => {
+            let (language, cbor) = script
+                .iter()
+                .next()
+                .ok_or_else(|| BlockfrostError::custom_400("Empty reference script".to_string()))?;
+
+            let tag = match language.as_str() {
+                "plutus:v1" => 1,
+                "plutus:v2" => 2,
+                "plutus:v3" => 3,
+                other => {
+                    return Err(BlockfrostError::custom_400(format!(
+                        "Unsupported reference script language: {other}"
+                    )));
+                },
+            };
+
+            Some((tag, decode_hex_field(cbor, "script")?))
+        },
+    };
+
+    let datum_hash = txout
+        .datum_hash
+        .as_deref()
+        .map(|h| decode_hex_field(h, "datumHash"))
+        .transpose()?;
+    let datum = txout
+        .datum
+        .as_deref()
+        .map(|d| decode_hex_field(d, "datum"))
+        .transpose()?;
+
+    let fields =
+        2 + u64::from(datum_hash.is_some() || datum.is_some()) + u64::from(script.is_some());
+
+    let mut buffer = Vec::new();
+    let mut e = minicbor::Encoder::new(&mut buffer);
+
+    let result: Result<(), minicbor::encode::Error<std::convert::Infallible>> = (|| {
+        e.map(fields)?;
+
+        e.u8(0)?.bytes(&address)?;
+
+        e.u8(1)?;
+        if assets.is_empty() {
+            e.u64(txout.value.coins)?;
+        } else {
+            e.array(2)?
+                .u64(txout.value.coins)?
+                .map(assets.len() as u64)?;
+
+            for (policy_id, names) in assets.iter() {
+                e.bytes(policy_id)?.map(names.len() as u64)?;
+
+                for (name, quantity) in names.iter() {
+                    e.bytes(name)?.u64(*quantity)?;
+                }
+            }
+        }
+
+        if let Some(datum) = &datum {
+            e.u8(2)?
+                .array(2)?
+                .u8(1)?
+                .tag(minicbor::data::Tag::new(24))?
+                .bytes(datum)?;
+        } else if let Some(hash) = &datum_hash {
+            e.u8(2)?.array(2)?.u8(0)?.bytes(hash)?;
+        }
+
+        if let Some((tag, script)) = &script {
+            let mut script_ref = Vec::new();
+            minicbor::Encoder::new(&mut script_ref)
+                .array(2)?
+                .u8(*tag)?
+                .bytes(script)?;
+
+            e.u8(3)?
+                .tag(minicbor::data::Tag::new(24))?
+                .bytes(&script_ref)?;
+        }
+
+        Ok(())
+    })();
+
+    result.map_err(|e| {
+        BlockfrostError::internal_server_error(format!("CBOR encoding failed: {e}"))
+    })?;
+
+    Ok(buffer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uplc::{
+        machine::{self, Trace, cost_model::ExBudget},
+        tx::error::Error,
+    };
+
+    #[test]
+    fn test_evaluation_failure_from_uplc() {
+        let script_failure = Error::RedeemerError {
+            tag: "Withdraw".to_string(),
+            index: 1,
+            err: Box::new(Error::Machine(
+                machine::Error::EvaluationFailure,
+                ExBudget::default(),
+                vec![Trace::Log("nope".to_string())],
+            )),
+        };
+
+        assert_eq!(
+            EvaluationFailure::from(script_failure),
+            EvaluationFailure::ScriptFailure {
+                purpose: RedeemerPurpose::Withdrawal,
+                index: 1,
+                error: machine::Error::EvaluationFailure.to_string(),
+                traces: vec!["nope".to_string()],
+            }
+        );
+
+        let missing_cost_model = Error::RedeemerError {
+            tag: "Spend".to_string(),
+            index: 0,
+            err: Box::new(Error::CostModelNotFound(uplc::Language::PlutusV3)),
+        };
+
+        assert!(matches!(
+            EvaluationFailure::from(missing_cost_model),
+            EvaluationFailure::CannotCreateEvaluationContext(_)
+        ));
+
+        assert!(matches!(
+            EvaluationFailure::from(Error::NoRedeemers),
+            EvaluationFailure::CannotCreateEvaluationContext(_)
+        ));
+    }
+
+    #[test]
+    fn test_encode_txout_roundtrip() {
+        let txout = AdditionalTxOut {
+            address: "addr_test1vzpwq95z3xyum8vqndgdd9mdnmafh3djcxnc6jemlgdmswcve6tkw".to_string(),
+            value: AdditionalValue {
+                coins: 2_000_000,
+                assets: BTreeMap::from([(
+                    "00000000000000000000000000000000000000000000000000000000.74657374".to_string(),
+                    1,
+                )]),
+            },
+            datum_hash: None,
+            datum: Some("d87980".to_string()),
+            script: None,
+        };
+
+        let bytes = encode_txout(&txout).unwrap();
+        let decoded: pallas_primitives::conway::TransactionOutput =
+            minicbor::decode(&bytes).unwrap();
+
+        assert!(matches!(
+            decoded,
+            pallas_primitives::conway::TransactionOutput::PostAlonzo(_)
+        ));
+    }
+
+    #[test]
+    fn test_encode_txin_rejects_bad_hash() {
+        let txin = AdditionalTxIn {
+            tx_id: "abcd".to_string(),
+            index: 0,
+        };
+
+        assert!(encode_txin(&txin).is_err());
+    }
+}
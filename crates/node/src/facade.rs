@@ -0,0 +1,146 @@
+//! Our own version of [`pallas_network::facades::NodeClient`]. The only
+//! difference is the LocalTxSubmission client, which keeps rejections as the
+//! raw CBOR the node sent, instead of Pallas’s [`TxValidationError`], which
+//! can’t be encoded back faithfully for all eras.
+//!
+//! [`TxValidationError`]: pallas_network::miniprotocols::localtxsubmission::TxValidationError
+
+use pallas_codec::minicbor::{self, Decode, Decoder, Encode, Encoder, encode::Write};
+use pallas_network::{
+    facades::Error,
+    miniprotocols::{
+        PROTOCOL_N2C_CHAIN_SYNC, PROTOCOL_N2C_HANDSHAKE, PROTOCOL_N2C_STATE_QUERY,
+        PROTOCOL_N2C_TX_MONITOR, PROTOCOL_N2C_TX_SUBMISSION, chainsync, handshake, localstate,
+        localtxsubmission::{self, EraTx},
+        txmonitor,
+    },
+    multiplexer::{self, Bearer, RunningPlexer},
+};
+use tracing::error;
+
+/// The `ApplyTxErr` of a LocalTxSubmission rejection, exactly as the node sent
+/// it, e.g. for `bf_error_decoder`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawRejection(pub Vec<u8>);
+
+impl<'b, C> Decode<'b, C> for RawRejection {
+    fn decode(d: &mut Decoder<'b>, _ctx: &mut C) -> Result<Self, minicbor::decode::Error> {
+        let start = d.position();
+        d.skip()?;
+        let end = d.position();
+
+        Ok(RawRejection(d.input()[start..end].to_vec()))
+    }
+}
+
+impl<C> Encode<C> for RawRejection {
+    fn encode<W: Write>(
+        &self,
+        e: &mut Encoder<W>,
+        _ctx: &mut C,
+    ) -> Result<(), minicbor::encode::Error<W::Error>> {
+        e.writer_mut()
+            .write_all(&self.0)
+            .map_err(minicbor::encode::Error::write)?;
+
+        Ok(())
+    }
+}
+
+/// Pallas falls back to this if the reply is not CBOR at all.
+impl From<String> for RawRejection {
+    fn from(text: String) -> Self {
+        RawRejection(text.into_bytes())
+    }
+}
+
+pub type SubmissionClient = localtxsubmission::GenericClient<EraTx, RawRejection>;
+
+pub struct NodeClientFacade {
+    plexer: RunningPlexer,
+    handshake: handshake::N2CClient,
+    chainsync: chainsync::N2CClient,
+    statequery: localstate::Client,
+    submission: SubmissionClient,
+    monitor: txmonitor::Client,
+}
+
+impl NodeClientFacade {
+    fn new(bearer: Bearer) -> Self {
+        let mut plexer = multiplexer::Plexer::new(bearer);
+
+        let hs_channel = plexer.subscribe_client(PROTOCOL_N2C_HANDSHAKE);
+        let cs_channel = plexer.subscribe_client(PROTOCOL_N2C_CHAIN_SYNC);
+        let sq_channel = plexer.subscribe_client(PROTOCOL_N2C_STATE_QUERY);
+        let tx_channel = plexer.subscribe_client(PROTOCOL_N2C_TX_SUBMISSION);
+        let mo_channel = plexer.subscribe_client(PROTOCOL_N2C_TX_MONITOR);
+
+        let plexer = plexer.spawn();
+
+        Self {
+            plexer,
+            handshake: handshake::Client::new(hs_channel),
+            chainsync: chainsync::Client::new(cs_channel),
+            statequery: localstate::Client::new(sq_channel),
+            submission: SubmissionClient::new(tx_channel),
+            monitor: txmonitor::Client::new(mo_channel),
+        }
+    }
+
+    /// Connects to the node socket (a named pipe on Windows) and performs the
+    /// N2C handshake.
+    pub async fn connect(socket_path: &str, magic: u64) -> Result<Self, Error> {
+        #[cfg(unix)]
+        let bearer = Bearer::connect_unix(socket_path)
+            .await
+            .map_err(Error::ConnectFailure)?;
+
+        #[cfg(windows)]
+        let bearer = {
+            let pipe_name = std::ffi::OsString::from(socket_path);
+            tokio::task::spawn_blocking(move || Bearer::connect_named_pipe(pipe_name))
+                .await
+                .expect("connect_named_pipe panic!")
+                .map_err(Error::ConnectFailure)?
+        };
+
+        let mut client = Self::new(bearer);
+
+        let versions = handshake::n2c::VersionTable::v10_and_above(magic);
+
+        let confirmation = client
+            .handshake
+            .handshake(versions)
+            .await
+            .map_err(Error::HandshakeProtocol)?;
+
+        if let handshake::Confirmation::Rejected(reason) = confirmation {
+            error!(?reason, "handshake refused");
+            client.abort().await;
+            return Err(Error::IncompatibleVersion);
+        }
+
+        Ok(client)
+    }
+
+    pub fn chainsync(&mut self) -> &mut chainsync::N2CClient {
+        &mut self.chainsync
+    }
+
+    pub fn statequery(&mut self) -> &mut localstate::Client {
+        &mut self.statequery
+    }
+
+    pub fn submission(&mut self) -> &mut SubmissionClient {
+        &mut self.submission
+    }
+
+    pub fn monitor(&mut self) -> &mut txmonitor::Client {
+        &mut self.monitor
+    }
+
+    /// Joins the multiplexer threads. Not calling it is a resource leak.
+    pub async fn abort(self) {
+        self.plexer.abort().await
+    }
+}
@@ -0,0 +1,375 @@
+use super::connection::NodeClient;
+use crate::ledger_query::{
+    RawArg, decode_error, encode_credential, encode_error, encode_set, query_if_current,
+};
+use bech32::{Bech32, Hrp};
+use bf_api_provider::types::{DrepsProposalsResponse, DrepsSingleResponse};
+use bf_common::{
+    errors::BlockfrostError,
+    pagination::{ApplyPagination, Pagination},
+};
+use pallas_codec::minicbor::{self, Decoder};
+use pallas_network::miniprotocols::localstate::{self, queries_v16};
+use serde_json::{Value, json};
+
+const GET_DREP_STATE: u16 = 25;
+const GET_DREP_STAKE_DISTR: u16 = 26;
+const GET_PROPOSALS: u16 = 31;
+
+/// DReps that every stake credential can delegate to, without registration,
+/// with their `DRep` constructor tags.
+const SPECIAL_DREPS: &[(&str, u8)] =
+    &[("drep_always_abstain", 2), ("drep_always_no_confidence", 3)];
+
+impl NodeClient {
+    /// Fetches a registered DRep with the `GetDRepState` and `GetDRepStakeDistr`
+    /// local-state queries. `drep_id` must be in the CIP-129 format, or one of
+    /// `drep_always_abstain` and `drep_always_no_confidence`.
+    ///
+    /// **Note**: the node doesn’t keep the activity history, so `active_epoch`
+    /// and `last_active_epoch` are `null`.
+    pub async fn drep(&mut self, drep_id: &str) -> Result<DrepsSingleResponse, BlockfrostError> {
+        if let Some(&(_, tag)) = SPECIAL_DREPS.iter().find(|(id, _)| *id == drep_id) {
+            return self.special_drep(drep_id, tag).await;
+        }
+
+        let (_, bytes) = bech32::decode(drep_id)?;
+
+        let (has_script, hash) = match bytes.as_slice() {
+            [0x22, hash @ ..] if hash.len() == 28 => (false, hash.to_vec()),
+            [0x23, hash @ ..] if hash.len() == 28 => (true, hash.to_vec()),
+            _ => return Err(BlockfrostError::not_found()),
+        };
+
+        let credential = encode_credential(has_script, &hash)?;
+
+        let key = format!("drep:{}", hex::encode(&credential));
+
+        let (epoch, state, stake) = self
+            .with_cached_statequery(key, |generic_client: &mut localstate::GenericClient| {
+                Box::pin(async move {
+                    let era = queries_v16::get_current_era(generic_client).await?;
+                    let epoch = queries_v16::get_block_epoch_number(generic_client, era).await?;
+
+                    let state = query_if_current(
+                        generic_client,
+                        era,
+                        GET_DREP_STATE,
+                        vec![RawArg(encode_set(std::slice::from_ref(&credential))?)],
+                    )
+                    .await?;
+
+                    // A `DRep` is encoded just like a credential here, `[0|1, hash]`:
+                    let stake = query_if_current(
+                        generic_client,
+                        era,
+                        GET_DREP_STAKE_DISTR,
+                        vec![RawArg(encode_set(&[credential])?)],
+                    )
+                    .await?;
+
+                    Ok((epoch, state, stake))
+                })
+            })
+            .await?;
+
+        let Some((expiry, _deposit)) = decode_drep_state(&state).map_err(decode_error)? else {
+            return Err(BlockfrostError::not_found());
+        };
+
+        let amount = decode_drep_stake(&stake).map_err(decode_error)?;
+        let expired = u64::from(epoch) > expiry;
+
+        serde_json::from_value(json!({
+            "drep_id": drep_id,
+            "hex": hex::encode(&hash),
+            "amount": amount.to_string(),
+            "active": !expired,
+            "active_epoch": null,
+            "has_script": has_script,
+            "retired": false,
+            "expired": expired,
+            "last_active_epoch": null,
+        }))
+        .map_err(|e| BlockfrostError::internal_server_error(format!("Failed to convert DRep: {e}")))
+    }
+
+    /// The special DReps are never registered, nor expire, so only their
+    /// stake is queried.
+    async fn special_drep(
+        &mut self,
+        drep_id: &str,
+        tag: u8,
+    ) -> Result<DrepsSingleResponse, BlockfrostError> {
+        let mut drep = Vec::new();
+        minicbor::Encoder::new(&mut drep)
+            .array(1)
+            .and_then(|e| e.u8(tag))
+            .map_err(encode_error)?;
+
+        let stake = self
+            .with_cached_statequery(
+                format!("drep:{drep_id}"),
+                |generic_client: &mut localstate::GenericClient| {
+                    Box::pin(async move {
+                        let era = queries_v16::get_current_era(generic_client).await?;
+
+                        query_if_current(
+                            generic_client,
+                            era,
+                            GET_DREP_STAKE_DISTR,
+                            vec![RawArg(encode_set(&[drep])?)],
+                        )
+                        .await
+                    })
+                },
+            )
+            .await?;
+
+        let amount = decode_drep_stake(&stake).map_err(decode_error)?;
+
+        serde_json::from_value(json!({
+            "drep_id": drep_id,
+            "hex": "",
+            "amount": amount.to_string(),
+            "active": true,
+            "active_epoch": null,
+            "has_script": false,
+            "retired": false,
+            "expired": false,
+            "last_active_epoch": null,
+        }))
+        .map_err(|e| BlockfrostError::internal_server_error(format!("Failed to convert DRep: {e}")))
+    }
+
+    /// Lists active governance proposals with the `GetProposals` local-state query.
+    ///
+    /// **Note**: the node doesn’t know when a proposal was submitted, so results
+    /// are ordered by `(tx_hash, cert_index)`. Proposals that were already
+    /// enacted, dropped or expired are not available without a data node.
+    pub async fn proposals(
+        &mut self,
+        pagination: &Pagination,
+    ) -> Result<DrepsProposalsResponse, BlockfrostError> {
+        let result = self
+            .with_cached_statequery(
+                "proposals".to_string(),
+                |generic_client: &mut localstate::GenericClient| {
+                    Box::pin(async move {
+                        let era = queries_v16::get_current_era(generic_client).await?;
+
+                        // An empty set means all proposals:
+                        query_if_current(
+                            generic_client,
+                            era,
+                            GET_PROPOSALS,
+                            vec![RawArg(encode_set(&[])?)],
+                        )
+                        .await
+                    })
+                },
+            )
+            .await?;
+
+        let mut proposals = decode_proposals(&result).map_err(decode_error)?;
+
+        proposals.sort();
+        proposals.apply_pagination(pagination);
+
+        let response = proposals
+            .into_iter()
+            .map(|(tx_hash, cert_index, governance_type)| {
+                Ok(json!({
+                    "id": gov_action_id(&tx_hash, cert_index)?,
+                    "tx_hash": hex::encode(&tx_hash),
+                    "cert_index": cert_index,
+                    "governance_type": governance_type,
+                }))
+            })
+            .collect::<Result<Vec<Value>, BlockfrostError>>()?;
+
+        serde_json::from_value(Value::Array(response)).map_err(|e| {
+            BlockfrostError::internal_server_error(format!("Failed to convert proposals: {e}"))
+        })
+    }
+}
+
+/// CIP-129 governance action id: the transaction id followed by the index.
+fn gov_action_id(tx_hash: &[u8], cert_index: u32) -> Result<String, BlockfrostError> {
+    let mut bytes = tx_hash.to_vec();
+
+    match u8::try_from(cert_index) {
+        Ok(index) => bytes.push(index),
+        Err(_) => {
+            let index = u16::try_from(cert_index).map_err(|_| {
+                BlockfrostError::custom_400(format!(
+                    "Governance action index {cert_index} doesn’t fit into a CIP-129 id"
+                ))
+            })?;
+            bytes.extend_from_slice(&index.to_be_bytes());
+        },
+    }
+
+    let hrp = Hrp::parse("gov_action")?;
+
+    Ok(bech32::encode::<Bech32>(hrp, &bytes)?)
+}
+
+/// Decodes `Map (Credential DRepRole) DRepState` with at most one entry into
+/// `(expiry, deposit)`.
+fn decode_drep_state(cbor: &[u8]) -> Result<Option<(u64, u64)>, minicbor::decode::Error> {
+    let mut d = Decoder::new(cbor);
+
+    if d.map()? == Some(0) {
+        return Ok(None);
+    }
+
+    d.skip()?; // credential
+    d.array()?;
+    let expiry = d.u64()?;
+    d.skip()?; // anchor
+    let deposit = d.u64()?;
+
+    Ok(Some((expiry, deposit)))
+}
+
+/// Decodes `Map DRep Coin` with at most one entry.
+fn decode_drep_stake(cbor: &[u8]) -> Result<u64, minicbor::decode::Error> {
+    let mut d = Decoder::new(cbor);
+
+    if d.map()? == Some(0) {
+        return Ok(0);
+    }
+
+    d.skip()?; // DRep
+    d.u64()
+}
+
+/// `(tx_hash, cert_index, governance_type)` of a single proposal.
+type ProposalId = (Vec<u8>, u32, &'static str);
+
+/// Decodes `Seq GovActionState` into [`ProposalId`]s.
+fn decode_proposals(cbor: &[u8]) -> Result<Vec<ProposalId>, minicbor::decode::Error> {
+    let mut d = Decoder::new(cbor);
+    let len = d
+        .array()?
+        .ok_or_else(|| minicbor::decode::Error::message("indefinite proposals"))?;
+
+    let mut proposals = Vec::new();
+
+    for _ in 0..len {
+        d.array()?;
+
+        // gasId
+        d.array()?;
+        let tx_hash = d.bytes()?.to_vec();
+        let cert_index = d.u32()?;
+
+        // committee, DRep and SPO votes
+        d.skip()?;
+        d.skip()?;
+        d.skip()?;
+
+        // proposal procedure: [deposit, return address, gov action, anchor]
+        d.array()?;
+        d.skip()?;
+        d.skip()?;
+        let action_len = d
+            .array()?
+            .ok_or_else(|| minicbor::decode::Error::message("indefinite gov action"))?;
+        let governance_type = match d.u8()? {
+            0 => "parameter_change",
+            1 => "hard_fork_initiation",
+            2 => "treasury_withdrawals",
+            3 => "no_confidence",
+            4 => "new_committee",
+            5 => "new_constitution",
+            6 => "info_action",
+            _ => return Err(minicbor::decode::Error::message("unknown gov action")),
+        };
+        for _ in 1..action_len {
+            d.skip()?;
+        }
+        d.skip()?; // anchor
+
+        // proposed in, expires after
+        d.skip()?;
+        d.skip()?;
+
+        proposals.push((tx_hash, cert_index, governance_type));
+    }
+
+    Ok(proposals)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_drep_state() {
+        // {[0, h'00…']: [120, [], 500000000, []]}
+        let cbor = hex::decode(
+            [
+                "a1",
+                "8200581c",
+                &"00".repeat(28),
+                "84187880",
+                "1a1dcd6500",
+                "80",
+            ]
+            .concat(),
+        )
+        .unwrap();
+
+        assert_eq!(decode_drep_state(&cbor).unwrap(), Some((120, 500_000_000)));
+        assert_eq!(decode_drep_state(&[0xa0]).unwrap(), None);
+    }
+
+    #[test]
+    fn test_gov_action_id() {
+        let tx_hash = [0xab; 32];
+
+        assert!(gov_action_id(&tx_hash, 0).is_ok());
+        assert!(gov_action_id(&tx_hash, 300).is_ok());
+        assert_eq!(
+            gov_action_id(&tx_hash, 70_000).unwrap_err().status_code,
+            400
+        );
+    }
+
+    #[test]
+    fn test_decode_drep_stake() {
+        // {[2]: 1000000}
+        assert_eq!(
+            decode_drep_stake(&hex::decode("a181021a000f4240").unwrap()).unwrap(),
+            1_000_000
+        );
+        assert_eq!(decode_drep_stake(&[0xa0]).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_decode_proposals() {
+        // [[[h'ab…', 0], {}, {}, {}, [100, h'e0', [6], ["url", h'00…']], 500, 506]]
+        let cbor = hex::decode(
+            [
+                "8187",
+                "825820",
+                &"ab".repeat(32),
+                "00",
+                "a0a0a0",
+                "84186441e08106",
+                "826375726c5820",
+                &"00".repeat(32),
+                "1901f41901fa",
+            ]
+            .concat(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            decode_proposals(&cbor).unwrap(),
+            vec![(vec![0xab; 32], 0, "info_action")]
+        );
+    }
+}
@@ -0,0 +1,169 @@
+//! Some local-state queries are not (yet) covered by
+//! [`pallas_network::miniprotocols::localstate::queries_v16`], mostly the
+//! Conway-era ones. We encode those by hand, following
+//! `Ouroboros.Consensus.Shelley.Ledger.Query` of `ouroboros-consensus`.
+
+use bf_common::errors::BlockfrostError;
+use pallas_codec::minicbor::{self, Decode, Decoder, Encode, Encoder, encode::Write};
+use pallas_network::miniprotocols::localstate;
+
+/// A Shelley-based `BlockQuery`, wrapped in `QueryIfCurrent` for the given era,
+/// i.e. `[0, [0, [era, [tag, ...args]]]]` on the wire.
+pub(crate) struct BlockQuery {
+    pub era: u16,
+    pub tag: u16,
+    pub args: Vec<RawArg>,
+}
+
+impl Encode<()> for BlockQuery {
+    fn encode<W: Write>(
+        &self,
+        e: &mut Encoder<W>,
+        ctx: &mut (),
+    ) -> Result<(), minicbor::encode::Error<W::Error>> {
+        e.array(2)?.u8(0)?; // BlockQuery
+        e.array(2)?.u8(0)?; // QueryIfCurrent
+        e.array(2)?.u16(self.era)?;
+        e.array(1 + self.args.len() as u64)?.u16(self.tag)?;
+
+        for arg in self.args.iter() {
+            arg.encode(e, ctx)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Raw CBOR of a query result, to be decoded by the caller.
+pub(crate) struct RawCbor(pub Vec<u8>);
+
+impl<'b> Decode<'b, ()> for RawCbor {
+    fn decode(d: &mut Decoder<'b>, _ctx: &mut ()) -> Result<Self, minicbor::decode::Error> {
+        let start = d.position();
+        d.skip()?;
+        let end = d.position();
+
+        Ok(RawCbor(d.input()[start..end].to_vec()))
+    }
+}
+
+/// An already encoded query argument, e.g. a set of credentials.
+pub(crate) struct RawArg(pub Vec<u8>);
+
+impl Encode<()> for RawArg {
+    fn encode<W: Write>(
+        &self,
+        e: &mut Encoder<W>,
+        _ctx: &mut (),
+    ) -> Result<(), minicbor::encode::Error<W::Error>> {
+        e.writer_mut()
+            .write_all(&self.0)
+            .map_err(minicbor::encode::Error::write)?;
+
+        Ok(())
+    }
+}
+
+/// Runs a `QueryIfCurrent` block query and returns the raw CBOR of its result,
+/// unwrapping the era-mismatch `Either`.
+pub(crate) async fn query_if_current(
+    generic_client: &mut localstate::GenericClient,
+    era: u16,
+    tag: u16,
+    args: Vec<RawArg>,
+) -> Result<Vec<u8>, BlockfrostError> {
+    let RawCbor(result) = generic_client.query(BlockQuery { era, tag, args }).await?;
+
+    let mut d = Decoder::new(&result);
+
+    match d.array().map_err(decode_error)? {
+        Some(1) => {
+            let start = d.position();
+            Ok(result[start..].to_vec())
+        },
+        _ => Err(BlockfrostError::internal_server_error(format!(
+            "Era mismatch in local-state query {tag} (era {era})"
+        ))),
+    }
+}
+
+pub(crate) fn decode_error(e: minicbor::decode::Error) -> BlockfrostError {
+    BlockfrostError::internal_server_error(format!(
+        "Failed to decode local-state query result: {e}"
+    ))
+}
+
+pub(crate) fn encode_error<E: std::fmt::Display>(e: E) -> BlockfrostError {
+    BlockfrostError::internal_server_error(format!("CBOR encoding failed: {e}"))
+}
+
+/// Encodes a `Credential` as `[0|1, hash]`, with `1` for scripts.
+pub(crate) fn encode_credential(is_script: bool, hash: &[u8]) -> Result<Vec<u8>, BlockfrostError> {
+    let mut buffer = Vec::new();
+
+    minicbor::Encoder::new(&mut buffer)
+        .array(2)
+        .and_then(|e| e.u8(u8::from(is_script)))
+        .and_then(|e| e.bytes(hash))
+        .map_err(encode_error)?;
+
+    Ok(buffer)
+}
+
+/// Encodes a `Set` of already encoded items.
+pub(crate) fn encode_set(items: &[Vec<u8>]) -> Result<Vec<u8>, BlockfrostError> {
+    let mut buffer = Vec::new();
+
+    minicbor::Encoder::new(&mut buffer)
+        .array(items.len() as u64)
+        .map_err(encode_error)?;
+
+    for item in items {
+        buffer.extend_from_slice(item);
+    }
+
+    Ok(buffer)
+}
+
+/// Skips an optional set tag (258), which the ledger uses since Conway.
+pub(crate) fn skip_set_tag(d: &mut Decoder<'_>) -> Result<(), minicbor::decode::Error> {
+    if d.datatype()? == minicbor::data::Type::Tag {
+        d.tag()?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_block_query_encoding() {
+        // GetCurrentPParams in Conway, as sent by `cardano-cli`:
+        let query = BlockQuery {
+            era: 6,
+            tag: 3,
+            args: vec![],
+        };
+
+        assert_eq!(
+            hex::encode(minicbor::to_vec(&query).unwrap()),
+            "8200820082068103"
+        );
+    }
+
+    #[test]
+    fn test_block_query_encoding_with_args() {
+        let query = BlockQuery {
+            era: 6,
+            tag: 25,
+            args: vec![RawArg(vec![0x80])],
+        };
+
+        assert_eq!(
+            hex::encode(minicbor::to_vec(&query).unwrap()),
+            "82008200820682181980"
+        );
+    }
+}
@@ -1,7 +1,23 @@
+// `json!` with all the epoch parameters fields needs more than the default 128:
+#![recursion_limit = "256"]
+
+pub mod accounts;
 pub mod cbor;
+pub mod chain_sync;
 pub mod connection;
+pub mod era_history;
+pub mod evaluation;
+pub mod facade;
+pub mod governance;
+pub(crate) mod ledger_query;
+pub mod mempool;
 pub mod monitoring;
 pub mod pool;
 pub mod pool_manager;
+pub mod pools;
+pub mod protocol_params;
+pub(crate) mod query_cache;
 pub mod sync_progress;
 pub mod transactions;
+pub mod tx_tracker;
+pub mod utxos;
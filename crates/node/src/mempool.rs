@@ -0,0 +1,414 @@
+use super::connection::NodeClient;
+use crate::utxos::{output_amount, output_datum, output_reference_script_hash};
+use bf_common::{
+    errors::BlockfrostError,
+    pagination::{ApplyPagination, Pagination},
+};
+use pallas_addresses::Address;
+use pallas_network::miniprotocols::txmonitor::{self, MempoolSizeAndCapacity};
+use pallas_primitives::conway::{Certificate, RedeemerTag};
+use pallas_traverse::{MultiEraOutput, MultiEraTx};
+use serde::Serialize;
+use serde_json::{Value, json};
+use std::{collections::BTreeMap, str::FromStr};
+use tracing::warn;
+
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct MempoolTxHash {
+    pub tx_hash: String,
+}
+
+/// A transaction in a mempool snapshot.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MempoolTx {
+    pub hash: String,
+    pub cbor: Vec<u8>,
+}
+
+/// Size of the mempool, in the Blockfrost `/mempool/size` format.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct MempoolSize {
+    pub capacity_in_bytes: u32,
+    pub size_in_bytes: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MempoolSnapshot {
+    /// The slot at which the snapshot was taken.
+    pub slot: u64,
+    pub capacity_in_bytes: u32,
+    pub size_in_bytes: u32,
+    /// In the order they were added to the mempool.
+    pub txs: Vec<MempoolTx>,
+}
+
+impl NodeClient {
+    /// Lists hashes of all transactions in the mempool, in the order they were
+    /// added.
+    pub async fn mempool_txs(
+        &mut self,
+        pagination: &Pagination,
+    ) -> Result<Vec<MempoolTxHash>, BlockfrostError> {
+        let mut txs = self
+            .mempool_snapshot()
+            .await?
+            .txs
+            .into_iter()
+            .map(|tx| MempoolTxHash { tx_hash: tx.hash })
+            .collect::<Vec<_>>();
+
+        txs.apply_pagination(pagination);
+
+        Ok(txs)
+    }
+
+    /// Current size and capacity of the mempool, without reading its
+    /// transactions.
+    pub async fn mempool_size(&mut self) -> Result<MempoolSize, BlockfrostError> {
+        let client = self.client.as_mut().unwrap().monitor();
+
+        let result = async {
+            client.acquire().await?;
+            let sizes = client.query_size_and_capacity().await?;
+            client.release().await?;
+
+            Ok::<MempoolSizeAndCapacity, txmonitor::Error>(sizes)
+        }
+        .await;
+
+        let sizes = result.map_err(|err| self.txmonitor_error(err))?;
+
+        Ok(MempoolSize {
+            capacity_in_bytes: sizes.capacity_in_bytes,
+            size_in_bytes: sizes.size_in_bytes,
+        })
+    }
+
+    /// A single mempool transaction, in the Blockfrost format.
+    ///
+    /// **Note**: the node doesn’t know the outputs spent by the transaction, so
+    /// input addresses are `null` and `deposit` is `"0"`.
+    pub async fn mempool_tx(&mut self, tx_hash: &str) -> Result<Value, BlockfrostError> {
+        let txs = self.mempool_snapshot().await?.txs;
+
+        let Some(tx) = txs.into_iter().find(|tx| tx.hash == tx_hash) else {
+            return Err(BlockfrostError::not_found());
+        };
+
+        Ok(tx_content(&decode_tx(&tx.cbor)?))
+    }
+
+    /// Lists hashes of mempool transactions which pay to `address`.
+    ///
+    /// **Note**: only outputs are matched, since the node doesn’t know the
+    /// addresses of spent outputs.
+    pub async fn mempool_txs_by_address(
+        &mut self,
+        address: &str,
+        pagination: &Pagination,
+    ) -> Result<Vec<MempoolTxHash>, BlockfrostError> {
+        let address_bytes = Address::from_str(address)
+            .map_err(|_| BlockfrostError::invalid_address())?
+            .to_vec();
+
+        let mut txs = Vec::new();
+
+        for tx in self.mempool_snapshot().await?.txs {
+            if pays_to(&decode_tx(&tx.cbor)?, &address_bytes) {
+                txs.push(MempoolTxHash { tx_hash: tx.hash });
+            }
+        }
+
+        txs.apply_pagination(pagination);
+
+        Ok(txs)
+    }
+
+    /// Acquires a mempool snapshot with the N2C local-tx-monitor mini-protocol,
+    /// and reads its size and all of its transactions.
+    pub async fn mempool_snapshot(&mut self) -> Result<MempoolSnapshot, BlockfrostError> {
+        let client = self.client.as_mut().unwrap().monitor();
+
+        let result = async {
+            let slot = client.acquire().await?;
+            let sizes = client.query_size_and_capacity().await?;
+
+            let mut txs = Vec::new();
+            while let Some((_era, cbor)) = client.query_next_tx().await? {
+                txs.push(Vec::from(cbor.0));
+            }
+
+            client.release().await?;
+
+            Ok::<(u64, MempoolSizeAndCapacity, Vec<Vec<u8>>), txmonitor::Error>((slot, sizes, txs))
+        }
+        .await;
+
+        let (slot, sizes, txs) = result.map_err(|err| self.txmonitor_error(err))?;
+
+        Ok(MempoolSnapshot {
+            slot,
+            capacity_in_bytes: sizes.capacity_in_bytes,
+            size_in_bytes: sizes.size_in_bytes,
+            txs: decode_mempool_txs(txs),
+        })
+    }
+
+    /// Like with chain-sync, a local-tx-monitor client in an unknown state is
+    /// useless, so the connection can’t go back to the pool.
+    fn txmonitor_error(&mut self, err: impl std::fmt::Display) -> BlockfrostError {
+        let message = format!("Local-tx-monitor error: {err}");
+        self.invalidate_connection(&message);
+
+        BlockfrostError::internal_server_error(message)
+    }
+}
+
+/// A single transaction we can’t decode (e.g. from a newer era) shouldn’t make
+/// the whole mempool unavailable, so it’s skipped.
+fn decode_mempool_txs(txs: Vec<Vec<u8>>) -> Vec<MempoolTx> {
+    txs.into_iter()
+        .filter_map(|cbor| match decode_tx(&cbor) {
+            Ok(tx) => Some(MempoolTx {
+                hash: tx.hash().to_string(),
+                cbor,
+            }),
+            Err(err) => {
+                warn!(
+                    "Skipping a mempool transaction: {err}, CBOR: {}",
+                    hex::encode(&cbor)
+                );
+                None
+            },
+        })
+        .collect()
+}
+
+fn decode_tx(cbor: &[u8]) -> Result<MultiEraTx<'_>, BlockfrostError> {
+    MultiEraTx::decode(cbor).map_err(|e| {
+        BlockfrostError::internal_server_error(format!("Failed to decode mempool transaction: {e}"))
+    })
+}
+
+fn pays_to(tx: &MultiEraTx, address_bytes: &[u8]) -> bool {
+    tx.outputs()
+        .iter()
+        .chain(tx.collateral_return().iter())
+        .any(|output| {
+            output
+                .address()
+                .is_ok_and(|address| address.to_vec() == address_bytes)
+        })
+}
+
+/// Builds the Blockfrost `/mempool/{hash}` response.
+fn tx_content(tx: &MultiEraTx) -> Value {
+    let outputs = tx.outputs();
+
+    let mut output_amount_total = BTreeMap::<String, u128>::new();
+    let mut lovelace_total: u128 = 0;
+
+    for output in outputs.iter() {
+        let value = output.value();
+        lovelace_total += u128::from(value.coin());
+
+        for policy_assets in value.assets() {
+            for asset in policy_assets.assets() {
+                let unit = format!("{}{}", policy_assets.policy(), hex::encode(asset.name()));
+                *output_amount_total.entry(unit).or_default() += asset.any_coin().unsigned_abs();
+            }
+        }
+    }
+
+    let mut output_amount_json =
+        vec![json!({ "unit": "lovelace", "quantity": lovelace_total.to_string() })];
+    output_amount_json.extend(
+        output_amount_total
+            .into_iter()
+            .map(|(unit, quantity)| json!({ "unit": unit, "quantity": quantity.to_string() })),
+    );
+
+    let (mut delegation_count, mut stake_cert_count) = (0, 0);
+    let (mut pool_update_count, mut pool_retire_count) = (0, 0);
+
+    for cert in tx.certs() {
+        match cert.as_conway() {
+            Some(Certificate::StakeRegistration(..))
+            | Some(Certificate::StakeDeregistration(..))
+            | Some(Certificate::Reg(..))
+            | Some(Certificate::UnReg(..)) => stake_cert_count += 1,
+            Some(Certificate::StakeDelegation(..)) | Some(Certificate::StakeVoteDeleg(..)) => {
+                delegation_count += 1
+            },
+            Some(Certificate::StakeRegDeleg(..)) | Some(Certificate::StakeVoteRegDeleg(..)) => {
+                stake_cert_count += 1;
+                delegation_count += 1;
+            },
+            Some(Certificate::PoolRegistration { .. }) => pool_update_count += 1,
+            Some(Certificate::PoolRetirement(..)) => pool_retire_count += 1,
+            _ => {},
+        }
+    }
+
+    let inputs = tx
+        .inputs()
+        .iter()
+        .map(|input| (input, false, false))
+        .chain(tx.collateral().iter().map(|input| (input, true, false)))
+        .chain(
+            tx.reference_inputs()
+                .iter()
+                .map(|input| (input, false, true)),
+        )
+        .map(|(input, collateral, reference)| {
+            json!({
+                "address": null,
+                "tx_hash": input.hash().to_string(),
+                "output_index": input.index(),
+                "collateral": collateral,
+                "reference": reference,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let outputs_json = outputs
+        .iter()
+        .map(|output| (output, false))
+        .chain(tx.collateral_return().iter().map(|output| (output, true)))
+        .enumerate()
+        .map(|(output_index, (output, collateral))| {
+            output_to_json(output, output_index, collateral)
+        })
+        .collect::<Vec<_>>();
+
+    let redeemers = tx
+        .redeemers()
+        .iter()
+        .map(|redeemer| {
+            let purpose = match redeemer.tag() {
+                RedeemerTag::Spend => "spend",
+                RedeemerTag::Mint => "mint",
+                RedeemerTag::Cert => "cert",
+                RedeemerTag::Reward => "reward",
+                RedeemerTag::Vote => "vote",
+                RedeemerTag::Propose => "propose",
+            };
+
+            json!({
+                "tx_index": redeemer.index(),
+                "purpose": purpose,
+                "unit_mem": redeemer.ex_units().mem.to_string(),
+                "unit_steps": redeemer.ex_units().steps.to_string(),
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let asset_mint_or_burn_count: usize = tx
+        .mints()
+        .iter()
+        .map(|policy_assets| policy_assets.assets().len())
+        .sum();
+
+    json!({
+        "tx": {
+            "hash": tx.hash().to_string(),
+            "output_amount": output_amount_json,
+            "fees": tx.fee().unwrap_or_default().to_string(),
+            "deposit": "0",
+            "size": tx.size(),
+            "invalid_before": tx.validity_start().map(|slot| slot.to_string()),
+            "invalid_hereafter": tx.ttl().map(|slot| slot.to_string()),
+            "utxo_count": tx.inputs().len() + outputs.len(),
+            "withdrawal_count": tx.withdrawals_sorted_set().len(),
+            "mir_cert_count": 0,
+            "delegation_count": delegation_count,
+            "stake_cert_count": stake_cert_count,
+            "pool_update_count": pool_update_count,
+            "pool_retire_count": pool_retire_count,
+            "asset_mint_or_burn_count": asset_mint_or_burn_count,
+            "redeemer_count": redeemers.len(),
+            "valid_contract": tx.is_valid(),
+        },
+        "inputs": inputs,
+        "outputs": outputs_json,
+        "redeemers": redeemers,
+    })
+}
+
+fn output_to_json(output: &MultiEraOutput, output_index: usize, collateral: bool) -> Value {
+    let (data_hash, inline_datum) = output_datum(output);
+
+    json!({
+        "address": output.address().map(|address| address.to_string()).unwrap_or_default(),
+        "amount": output_amount(output),
+        "output_index": output_index,
+        "data_hash": data_hash,
+        "inline_datum": inline_datum,
+        "collateral": collateral,
+        "reference_script_hash": output_reference_script_hash(output),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `[{0: [[h'11…', 0]], 1: [[h'61…', 1000000]], 2: 170000}, {}, true, null]`
+    fn tx_cbor() -> Vec<u8> {
+        hex::decode(
+            [
+                "84a3",
+                "0081825820",
+                &"11".repeat(32),
+                "00",
+                "018182581d61",
+                &"22".repeat(28),
+                "1a000f4240",
+                "021a00029810",
+                "a0f5f6",
+            ]
+            .concat(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_pays_to() {
+        let cbor = tx_cbor();
+        let tx = decode_tx(&cbor).unwrap();
+
+        let mut address = vec![0x61];
+        address.extend([0x22; 28]);
+        assert!(pays_to(&tx, &address));
+
+        address[1] = 0x33;
+        assert!(!pays_to(&tx, &address));
+    }
+
+    #[test]
+    fn test_decode_mempool_txs_skips_undecodable() {
+        let txs = decode_mempool_txs(vec![vec![0xff, 0x00], tx_cbor()]);
+
+        assert_eq!(txs.len(), 1);
+        assert_eq!(txs[0].cbor, tx_cbor());
+        assert_eq!(
+            txs[0].hash,
+            decode_tx(&tx_cbor()).unwrap().hash().to_string()
+        );
+    }
+
+    #[test]
+    fn test_tx_content() {
+        let cbor = tx_cbor();
+        let content = tx_content(&decode_tx(&cbor).unwrap());
+
+        assert_eq!(content["tx"]["fees"], "170000");
+        assert_eq!(content["tx"]["utxo_count"], 2);
+        assert_eq!(
+            content["tx"]["output_amount"],
+            json!([{ "unit": "lovelace", "quantity": "1000000" }])
+        );
+        assert_eq!(content["inputs"][0]["tx_hash"], json!("11".repeat(32)));
+        assert_eq!(content["outputs"][0]["collateral"], false);
+    }
+}
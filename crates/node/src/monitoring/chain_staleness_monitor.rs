@@ -8,6 +8,8 @@ const CHAIN_STALE_IF_OLDER_THAN: std::time::Duration = std::time::Duration::from
 pub struct ChainStalenessMonitor {
     last_chain_advancement: std::time::Instant,
     last_chain_block: String,
+    /// UNIX time of the last block’s slot, from the era history.
+    last_chain_block_time: Option<i64>,
     errors: Arc<Mutex<Vec<BlockfrostError>>>,
 }
 
@@ -23,25 +25,48 @@ impl ChainStalenessMonitor {
             last_chain_advancement: std::time::Instant::now(),
             last_chain_block: "0000000000000000000000000000000000000000000000000000000000000000"
                 .to_string(),
+            last_chain_block_time: None,
             errors: Arc::new(Mutex::new(vec![])),
         }
     }
 
+    /// The chain is stale if the tip has stopped advancing and its slot time
+    /// is too far in the past. Measuring the age of the tip itself, rather than
+    /// since when we’ve been seeing it, reports a stuck node right after our
+    /// own restart, too. Without the slot time, we fall back to the latter.
     pub async fn update(&mut self, node_info: &Option<NodeInfo>) {
-        if let Some(node_info) = node_info
-            && self.last_chain_block != node_info.block
-        {
-            self.last_chain_block = node_info.block.clone();
-            self.last_chain_advancement = std::time::Instant::now();
+        let mut advanced = false;
+
+        if let Some(node_info) = node_info {
+            if self.last_chain_block != node_info.block {
+                self.last_chain_block = node_info.block.clone();
+                self.last_chain_advancement = std::time::Instant::now();
+                advanced = true;
+            }
+            self.last_chain_block_time = Some(node_info.slot_time).filter(|&time| time > 0);
         }
 
         let elapsed = self.last_chain_advancement.elapsed();
+        let tip_age = self.last_chain_block_time.map(|time| {
+            std::time::Duration::from_secs((chrono::Utc::now().timestamp() - time).max(0) as u64)
+        });
+
+        let is_stale = match tip_age {
+            Some(tip_age) => !advanced && tip_age > CHAIN_STALE_IF_OLDER_THAN,
+            None => elapsed > CHAIN_STALE_IF_OLDER_THAN,
+        };
 
-        *(self.errors.lock().await) = if elapsed > CHAIN_STALE_IF_OLDER_THAN {
-            let err = format!(
-                "Chain stuck at {}, has not seen updates in {:?}.",
-                self.last_chain_block, elapsed
-            );
+        *(self.errors.lock().await) = if is_stale {
+            let err = match tip_age {
+                Some(tip_age) => format!(
+                    "Chain stuck at {}, has not seen updates in {:?}, the tip is {:?} old.",
+                    self.last_chain_block, elapsed, tip_age
+                ),
+                None => format!(
+                    "Chain stuck at {}, has not seen updates in {:?}.",
+                    self.last_chain_block, elapsed
+                ),
+            };
             tracing::error!("{}", err);
             vec![BlockfrostError::internal_server_error(err)]
         } else {
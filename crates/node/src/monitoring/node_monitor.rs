@@ -1,6 +1,6 @@
 use crate::{pool::NodePool, sync_progress::NodeInfo};
 use bf_common::errors::BlockfrostError;
-use std::sync::Arc;
+use std::{cmp::Ordering, sync::Arc};
 use tokio::sync::Mutex;
 
 pub struct NodeMonitor {
@@ -22,18 +22,41 @@ impl NodeMonitor {
         }
     }
 
+    /// Checks all nodes of the pool, and ranks them, so that [`NodePool::get`]
+    /// prefers the healthiest, most synced one. It’s only an error if none of
+    /// them can be reached.
     pub async fn update(&self, node: &NodePool) {
-        let node_info: Result<NodeInfo, BlockfrostError> = async {
-            let mut node = node.get().await?;
-            node.sync_progress().await
+        let socket_paths = node.socket_paths();
+        let mut node_infos = Vec::with_capacity(socket_paths.len());
+
+        for (index, socket_path) in socket_paths.iter().enumerate() {
+            let node_info: Result<NodeInfo, BlockfrostError> = async {
+                let mut node = node.get_from(index).await?;
+                node.sync_progress().await
+            }
+            .await;
+
+            if socket_paths.len() > 1
+                && let Err(err) = &node_info
+            {
+                tracing::warn!("NodeMonitor: {socket_path} is unhealthy: {}", err.message);
+            }
+
+            node_infos.push(node_info);
         }
-        .await;
 
-        let (node_info, errors) = match node_info {
-            Ok(a) => (Some(a), vec![]),
-            Err(err) => (None, vec![err]),
+        let ranking = rank_nodes(&node_infos);
+
+        let node_info = node_infos[ranking[0]].as_ref().ok().cloned();
+
+        let errors = if node_info.is_some() {
+            vec![]
+        } else {
+            node_infos.into_iter().filter_map(Result::err).collect()
         };
 
+        node.rank(ranking);
+
         *(self.errors.lock().await) = errors;
         *(self.node_info.lock().await) = node_info;
     }
@@ -46,3 +69,50 @@ impl NodeMonitor {
         self.node_info.clone()
     }
 }
+
+/// Indices of nodes, the healthiest first: reachable ones before unreachable,
+/// then by the tip slot, then by the sync progress. Ties keep the configured
+/// order.
+fn rank_nodes(node_infos: &[Result<NodeInfo, BlockfrostError>]) -> Vec<usize> {
+    let mut ranking: Vec<usize> = (0..node_infos.len()).collect();
+
+    ranking.sort_by(|&a, &b| match (&node_infos[a], &node_infos[b]) {
+        (Ok(a), Ok(b)) => b
+            .slot
+            .cmp(&a.slot)
+            .then(b.sync_progress.total_cmp(&a.sync_progress)),
+        (Ok(_), Err(_)) => Ordering::Less,
+        (Err(_), Ok(_)) => Ordering::Greater,
+        (Err(_), Err(_)) => Ordering::Equal,
+    });
+
+    ranking
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node_info(slot: u64, sync_progress: f64) -> Result<NodeInfo, BlockfrostError> {
+        Ok(NodeInfo {
+            block: String::new(),
+            epoch: 0,
+            era: 6,
+            slot,
+            slot_time: 0,
+            sync_progress,
+        })
+    }
+
+    #[test]
+    fn test_rank_nodes() {
+        let node_infos = vec![
+            Err(BlockfrostError::internal_server_error("down".to_string())),
+            node_info(100, 99.0),
+            node_info(120, 100.0),
+            node_info(120, 100.0),
+        ];
+
+        assert_eq!(rank_nodes(&node_infos), vec![2, 3, 1, 0]);
+    }
+}
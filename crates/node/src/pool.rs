@@ -1,40 +1,218 @@
-use super::pool_manager::NodePoolManager;
+use super::{connection::NodeClient, pool_manager::NodePoolManager, transactions::TxSubmitError};
 use bf_common::errors::AppError;
-use deadpool::managed::{Object, Pool};
+use deadpool::managed::{Manager, Object, Pool};
+use metrics::gauge;
+use std::{
+    ops::{Deref, DerefMut},
+    sync::{Arc, RwLock},
+};
+use tracing::warn;
 
-/// This represents a pool of `NodeToClient` connections to a single `cardano-node`.
+/// A [`super::connection::NodeClient`] borrowed from a [`NodePool`]. It goes
+/// back to the pool when dropped.
+pub type NodeConnection = Object<NodePoolManager>;
+
+/// A [`NodeClient`] opened outside of the [`NodePool`], for long-lived
+/// stateful mini-protocols (like a chain-sync session), which would otherwise
+/// starve the pool. It’s closed when dropped.
+pub struct DedicatedConnection(Option<NodeClient>);
+
+impl Deref for DedicatedConnection {
+    type Target = NodeClient;
+
+    fn deref(&self) -> &NodeClient {
+        self.0.as_ref().expect("DedicatedConnection already closed")
+    }
+}
+
+impl DerefMut for DedicatedConnection {
+    fn deref_mut(&mut self) -> &mut NodeClient {
+        self.0.as_mut().expect("DedicatedConnection already closed")
+    }
+}
+
+impl Drop for DedicatedConnection {
+    fn drop(&mut self) {
+        if let Some(mut node) = self.0.take()
+            && let Some(client) = node.client.take()
+        {
+            gauge!("cardano_node_connections").decrement(1);
+            // `abort` joins the multiplexer threads, otherwise they’d leak:
+            tokio::spawn(client.abort());
+        }
+    }
+}
+
+/// This represents pools of `NodeToClient` connections to one or more
+/// `cardano-node`s, one pool per node.
 ///
 /// It can be safely cloned to multiple threads, while still sharing the same
-/// set of underlying connections to the node.
+/// set of underlying connections to the nodes.
 #[derive(Clone)]
 pub struct NodePool {
+    nodes: Arc<Vec<Node>>,
+    /// Indices into `nodes`, the healthiest first. It’s kept up to date by the
+    /// [`super::monitoring::node_monitor::NodeMonitor`].
+    ranking: Arc<RwLock<Vec<usize>>>,
+}
+
+struct Node {
+    socket_path: String,
     pool_manager: Pool<NodePoolManager>,
 }
 
 impl NodePool {
-    /// Creates a new pool of [`super::connection::NodeClient`] connections.
+    /// Creates a new pool of [`super::connection::NodeClient`] connections for
+    /// each of `socket_paths`. Until the first health check, the nodes are
+    /// preferred in the given order.
     pub fn new(
         network_magic: u64,
-        socket_path: String,
+        socket_paths: Vec<String>,
         max_pool_connections: usize,
     ) -> Result<Self, AppError> {
-        let manager = NodePoolManager {
-            network_magic,
-            socket_path,
-        };
-        let pool_manager = deadpool::managed::Pool::builder(manager)
-            .max_size(max_pool_connections)
-            .build()
-            .map_err(|err| AppError::Node(err.to_string()))?;
+        if socket_paths.is_empty() {
+            return Err(AppError::Node(
+                "At least one node socket path is required".to_string(),
+            ));
+        }
+
+        let nodes = socket_paths
+            .into_iter()
+            .map(|socket_path| {
+                let manager = NodePoolManager {
+                    network_magic,
+                    socket_path: socket_path.clone(),
+                    phase_one_params: Default::default(),
+                    query_cache: Default::default(),
+                };
+                let pool_manager = deadpool::managed::Pool::builder(manager)
+                    .max_size(max_pool_connections)
+                    .build()
+                    .map_err(|err| AppError::Node(err.to_string()))?;
 
-        Ok(Self { pool_manager })
+                Ok(Node {
+                    socket_path,
+                    pool_manager,
+                })
+            })
+            .collect::<Result<Vec<_>, AppError>>()?;
+
+        let ranking = (0..nodes.len()).collect();
+
+        Ok(Self {
+            nodes: Arc::new(nodes),
+            ranking: Arc::new(RwLock::new(ranking)),
+        })
     }
 
-    /// Borrows a single [`super::connection::NodeClient`] connection from the pool.
-    pub async fn get(&self) -> Result<Object<NodePoolManager>, AppError> {
-        self.pool_manager
+    /// Borrows a single [`super::connection::NodeClient`] connection from the
+    /// healthiest node, or from the next one, if that can’t be reached.
+    pub async fn get(&self) -> Result<NodeConnection, AppError> {
+        self.get_excluding(&[]).await
+    }
+
+    /// Like [`Self::get`], but never from the nodes at `excluded` socket paths.
+    pub async fn get_excluding(&self, excluded: &[String]) -> Result<NodeConnection, AppError> {
+        let ranking = self
+            .ranking
+            .read()
+            .expect("NodePool ranking poisoned")
+            .clone();
+
+        let mut last_error = None;
+
+        for node in ranking.iter().map(|&index| &self.nodes[index]) {
+            if excluded.contains(&node.socket_path) {
+                continue;
+            }
+
+            match node.pool_manager.get().await {
+                Ok(connection) => return Ok(connection),
+                Err(err) => {
+                    last_error = Some(AppError::Node(format!(
+                        "NodeConnPool: {}: {err}",
+                        node.socket_path
+                    )))
+                },
+            }
+        }
+
+        Err(last_error
+            .unwrap_or_else(|| AppError::Node("NodeConnPool: no other node available".to_string())))
+    }
+
+    /// Opens a new [`DedicatedConnection`] to the healthiest node, or to the
+    /// next one, if that can’t be reached. It doesn’t count towards the pool
+    /// size.
+    pub async fn dedicated(&self) -> Result<DedicatedConnection, AppError> {
+        let ranking = self
+            .ranking
+            .read()
+            .expect("NodePool ranking poisoned")
+            .clone();
+
+        let mut last_error = None;
+
+        for node in ranking.iter().map(|&index| &self.nodes[index]) {
+            match node.pool_manager.manager().create().await {
+                Ok(connection) => return Ok(DedicatedConnection(Some(connection))),
+                Err(err) => last_error = Some(err),
+            }
+        }
+
+        Err(last_error
+            .unwrap_or_else(|| AppError::Node("NodeConnPool: no node available".to_string())))
+    }
+
+    /// Socket paths of all nodes, in the order they were given.
+    pub fn socket_paths(&self) -> Vec<String> {
+        self.nodes
+            .iter()
+            .map(|node| node.socket_path.clone())
+            .collect()
+    }
+
+    /// Borrows a connection from a specific node, by its index in
+    /// [`Self::socket_paths`].
+    pub(crate) async fn get_from(&self, index: usize) -> Result<NodeConnection, AppError> {
+        let node = &self.nodes[index];
+
+        node.pool_manager
             .get()
             .await
-            .map_err(|err| AppError::Node(format!("NodeConnPool: {err}")))
+            .map_err(|err| AppError::Node(format!("NodeConnPool: {}: {err}", node.socket_path)))
+    }
+
+    /// Sets the order of preference of the nodes, by their indices in
+    /// [`Self::socket_paths`].
+    pub(crate) fn rank(&self, ranking: Vec<usize>) {
+        *self.ranking.write().expect("NodePool ranking poisoned") = ranking;
+    }
+
+    /// Submits a transaction to the healthiest node. If that node can’t be
+    /// reached, or the connection breaks before the transaction is sent, it’s
+    /// retried on the other nodes in turn. A rejection by the ledger, or a
+    /// connection broken while waiting for the reply, is never retried.
+    pub async fn submit_transaction(&self, tx: Vec<u8>) -> Result<String, TxSubmitError> {
+        let mut tried = Vec::new();
+
+        loop {
+            let mut node = self
+                .get_excluding(&tried)
+                .await
+                .map_err(|err| TxSubmitError::Connection(err.into()))?;
+
+            match node.submit_transaction(tx.clone()).await {
+                Err(TxSubmitError::Connection(err)) if tried.len() + 1 < self.nodes.len() => {
+                    warn!(
+                        "Submission to {} failed, retrying on another node: {}",
+                        node.socket_path(),
+                        err.message
+                    );
+                    tried.push(node.socket_path().to_string());
+                },
+                result => return result,
+            }
+        }
     }
 }
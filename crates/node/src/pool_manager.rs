@@ -1,14 +1,18 @@
-use super::connection::NodeClient;
+use super::{
+    cbor::validation::PhaseOneParamsCache, connection::NodeClient, facade::NodeClientFacade,
+    query_cache::QueryCache,
+};
 use bf_common::errors::AppError;
 use deadpool::managed::{Manager, Metrics, RecycleError, RecycleResult};
 use metrics::{counter, gauge};
-use pallas_network::facades::NodeClient as NodeClientFacade;
 use std::sync::atomic;
 use tracing::{error, info};
 
 pub struct NodePoolManager {
     pub network_magic: u64,
     pub socket_path: String,
+    pub(crate) phase_one_params: PhaseOneParamsCache,
+    pub(crate) query_cache: QueryCache,
 }
 
 static N2C_CONNECTION_COUNTER: atomic::AtomicU64 = atomic::AtomicU64::new(0);
@@ -35,7 +39,9 @@ impl Manager for NodePoolManager {
                     client: Some(connection),
                     connection_id,
                     unrecoverable_error_happened: false,
-                    network_magic: self.network_magic,
+                    socket_path: self.socket_path.clone(),
+                    phase_one_params: self.phase_one_params.clone(),
+                    query_cache: self.query_cache.clone(),
                 })
             },
             Err(err) => {
@@ -50,10 +56,11 @@ impl Manager for NodePoolManager {
     }
 
     /// Pallas decided to make the
-    /// [`pallas_network::facades::NodeClient::abort`] take ownership of `self`.
-    /// That’s why we need our `NodeClient::client` to be an [`Option`], because
-    /// in here we only get a mutable reference. If the connection is broken, we
-    /// have to call [`pallas_network::facades::NodeClient::abort`], because it
+    /// [`pallas_network::multiplexer::RunningPlexer::abort`] take ownership of
+    /// `self`, and so does our [`NodeClientFacade::abort`]. That’s why we need
+    /// our `NodeClient::client` to be an [`Option`], because in here we only
+    /// get a mutable reference. If the connection is broken, we have to call
+    /// [`NodeClientFacade::abort`], because it
     /// joins certain multiplexer threads. Otherwise, it’s a resource leak.
     async fn recycle(&self, node: &mut NodeClient, metrics: &Metrics) -> RecycleResult<AppError> {
         let can_communicate = if node.unrecoverable_error_happened {
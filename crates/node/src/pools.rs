@@ -0,0 +1,296 @@
+use super::connection::NodeClient;
+use crate::ledger_query::{RawArg, decode_error, query_if_current, skip_set_tag};
+use bech32::{Bech32, Hrp};
+use bf_api_provider::types::TxsPoolCertsInnerRelaysResponse;
+use bf_common::{
+    errors::BlockfrostError,
+    pagination::{ApplyPagination, Pagination},
+};
+use pallas_codec::minicbor::{self, Decoder, data::Type};
+use pallas_network::miniprotocols::localstate::{self, queries_v16};
+use serde_json::{Value, json};
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+const GET_STAKE_POOLS: u16 = 16;
+const GET_STAKE_POOL_PARAMS: u16 = 17;
+
+impl NodeClient {
+    /// Lists all registered stake pools with the `GetStakePools` local-state query.
+    ///
+    /// **Note**: the node doesn’t know when a pool was registered, so results
+    /// are ordered by their bech32 pool id.
+    pub async fn pools(&mut self, pagination: &Pagination) -> Result<Vec<String>, BlockfrostError> {
+        let result = self
+            .with_cached_statequery(
+                "pools".to_string(),
+                |generic_client: &mut localstate::GenericClient| {
+                    Box::pin(async move {
+                        let era = queries_v16::get_current_era(generic_client).await?;
+
+                        query_if_current(generic_client, era, GET_STAKE_POOLS, vec![]).await
+                    })
+                },
+            )
+            .await?;
+
+        let hrp = Hrp::parse("pool")?;
+
+        let mut pools = decode_pool_ids(&result)
+            .map_err(decode_error)?
+            .iter()
+            .map(|pool_hash| bech32::encode::<Bech32>(hrp, pool_hash))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        pools.sort();
+        pools.apply_pagination(pagination);
+
+        Ok(pools)
+    }
+
+    /// Lists relays of a registered stake pool with the `GetStakePoolParams`
+    /// local-state query. `pool_id` must be in bech32.
+    pub async fn pool_relays(
+        &mut self,
+        pool_id: &str,
+    ) -> Result<TxsPoolCertsInnerRelaysResponse, BlockfrostError> {
+        let pool_hash = decode_pool_id(pool_id)?;
+
+        let mut pools = Vec::new();
+        minicbor::Encoder::new(&mut pools)
+            .array(1)
+            .and_then(|e| e.bytes(&pool_hash))
+            .map_err(|e| {
+                BlockfrostError::internal_server_error(format!("CBOR encoding failed: {e}"))
+            })?;
+
+        let key = format!("pool_relays:{}", hex::encode(&pool_hash));
+
+        let result = self
+            .with_cached_statequery(key, |generic_client: &mut localstate::GenericClient| {
+                Box::pin(async move {
+                    let era = queries_v16::get_current_era(generic_client).await?;
+
+                    query_if_current(
+                        generic_client,
+                        era,
+                        GET_STAKE_POOL_PARAMS,
+                        vec![RawArg(pools)],
+                    )
+                    .await
+                })
+            })
+            .await?;
+
+        let Some(relays) = decode_pool_relays(&result).map_err(decode_error)? else {
+            return Err(BlockfrostError::not_found());
+        };
+
+        serde_json::from_value(Value::Array(relays)).map_err(|e| {
+            BlockfrostError::internal_server_error(format!("Failed to convert relays: {e}"))
+        })
+    }
+}
+
+/// Decodes a bech32 pool id into its 28-byte hash, which the node would fail to
+/// decode the whole query without.
+fn decode_pool_id(pool_id: &str) -> Result<Vec<u8>, BlockfrostError> {
+    match bech32::decode(pool_id) {
+        Ok((hrp, pool_hash)) if hrp.as_str() == "pool" && pool_hash.len() == 28 => Ok(pool_hash),
+        _ => Err(BlockfrostError::invalid_pool_id()),
+    }
+}
+
+/// Decodes `Set (KeyHash StakePool)`.
+fn decode_pool_ids(cbor: &[u8]) -> Result<Vec<Vec<u8>>, minicbor::decode::Error> {
+    let mut d = Decoder::new(cbor);
+
+    skip_set_tag(&mut d)?;
+
+    d.array_iter::<minicbor::bytes::ByteVec>()?
+        .map(|pool_hash| pool_hash.map(|h| h.to_vec()))
+        .collect()
+}
+
+/// Decodes relays out of `Map (KeyHash StakePool) PoolParams` with at most one
+/// entry, in the Blockfrost format.
+fn decode_pool_relays(cbor: &[u8]) -> Result<Option<Vec<Value>>, minicbor::decode::Error> {
+    let mut d = Decoder::new(cbor);
+
+    if d.map()? == Some(0) {
+        return Ok(None);
+    }
+
+    d.skip()?; // pool id
+
+    // [operator, vrf_keyhash, pledge, cost, margin, reward_account, owners, relays, metadata]
+    d.array()?;
+    for _ in 0..7 {
+        d.skip()?;
+    }
+
+    let len = d
+        .array()?
+        .ok_or_else(|| minicbor::decode::Error::message("indefinite relays"))?;
+
+    let mut relays = Vec::new();
+
+    for _ in 0..len {
+        d.array()?;
+
+        let relay = match d.u8()? {
+            // single_host_addr
+            0 => {
+                let port = decode_nullable(&mut d, |d| d.u16())?;
+                let ipv4 = decode_nullable(&mut d, |d| d.bytes().map(decode_ipv4))?;
+                let ipv6 = decode_nullable(&mut d, |d| d.bytes().map(decode_ipv6))?;
+
+                json!({
+                    "ipv4": ipv4.flatten(),
+                    "ipv6": ipv6.flatten(),
+                    "dns": null,
+                    "dns_srv": null,
+                    "port": port.unwrap_or_default(),
+                })
+            },
+            // single_host_name
+            1 => {
+                let port = decode_nullable(&mut d, |d| d.u16())?;
+                let dns = d.str()?;
+
+                json!({
+                    "ipv4": null,
+                    "ipv6": null,
+                    "dns": dns,
+                    "dns_srv": null,
+                    "port": port.unwrap_or_default(),
+                })
+            },
+            // multi_host_name
+            2 => {
+                let dns_srv = d.str()?;
+
+                json!({
+                    "ipv4": null,
+                    "ipv6": null,
+                    "dns": null,
+                    "dns_srv": dns_srv,
+                    "port": 0,
+                })
+            },
+            _ => return Err(minicbor::decode::Error::message("unknown relay type")),
+        };
+
+        relays.push(relay);
+    }
+
+    Ok(Some(relays))
+}
+
+fn decode_nullable<'b, T>(
+    d: &mut Decoder<'b>,
+    f: impl FnOnce(&mut Decoder<'b>) -> Result<T, minicbor::decode::Error>,
+) -> Result<Option<T>, minicbor::decode::Error> {
+    if d.datatype()? == Type::Null {
+        d.null()?;
+        Ok(None)
+    } else {
+        f(d).map(Some)
+    }
+}
+
+fn decode_ipv4(bytes: &[u8]) -> Option<String> {
+    let octets: [u8; 4] = bytes.try_into().ok()?;
+
+    Some(Ipv4Addr::from(octets).to_string())
+}
+
+/// The ledger stores IPv6 addresses as four little-endian 32-bit words.
+fn decode_ipv6(bytes: &[u8]) -> Option<String> {
+    let bytes: [u8; 16] = bytes.try_into().ok()?;
+    let mut octets = [0u8; 16];
+
+    for (i, chunk) in bytes.chunks_exact(4).enumerate() {
+        for (j, byte) in chunk.iter().rev().enumerate() {
+            octets[i * 4 + j] = *byte;
+        }
+    }
+
+    Some(Ipv6Addr::from(octets).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_pool_id() {
+        let cases = [
+            (
+                "pool1pu5jlj4q9w9jlxeu370a3c9myx47md5j5m2str0naunn2q3lkdy",
+                Some("0f292fcaa02b8b2f9b3c8f9fd8e0bb21abedb692a6d5058df3ef2735"),
+            ),
+            // 27 bytes:
+            (
+                "pool1pu5jlj4q9w9jlxeu370a3c9myx47md5j5m2str0naunsrja7gj",
+                None,
+            ),
+            (
+                "drep1y246h2at4w46h2at4w46h2at4w46h2at4w46h2at4w46h2caa85du",
+                None,
+            ),
+            ("stonks_pool", None),
+        ];
+
+        for (pool_id, expected) in cases {
+            assert_eq!(
+                decode_pool_id(pool_id).ok().map(hex::encode).as_deref(),
+                expected,
+                "{pool_id}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_decode_ipv6() {
+        let bytes = hex::decode("b80d0120000000000000000001000000").unwrap();
+
+        assert_eq!(decode_ipv6(&bytes), Some("2001:db8::1".to_string()));
+    }
+
+    #[test]
+    fn test_decode_pool_relays() {
+        // {h'00…': [op, vrf, 1, 2, 30([1, 2]), h'e0', [], [[0, 3001, h'7f000001', null],
+        //   [1, null, "relay.example.com"], [2, "example.com"]], null]}
+        let cbor = hex::decode(
+            [
+                "a1",
+                "581c",
+                &"00".repeat(28),
+                "89",
+                "581c",
+                &"00".repeat(28),
+                "5820",
+                &"00".repeat(32),
+                "0102d81e82010241e080",
+                "83",
+                "8400190bb9447f000001f6",
+                "8301f671",
+                &hex::encode("relay.example.com"),
+                "82026b",
+                &hex::encode("example.com"),
+                "f6",
+            ]
+            .concat(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            decode_pool_relays(&cbor).unwrap(),
+            Some(vec![
+                json!({"ipv4": "127.0.0.1", "ipv6": null, "dns": null, "dns_srv": null, "port": 3001}),
+                json!({"ipv4": null, "ipv6": null, "dns": "relay.example.com", "dns_srv": null, "port": 0}),
+                json!({"ipv4": null, "ipv6": null, "dns": null, "dns_srv": "example.com", "port": 0}),
+            ])
+        );
+    }
+}
@@ -0,0 +1,258 @@
+use super::connection::NodeClient;
+use bf_api_provider::types::EpochsParamResponse;
+use bf_common::errors::BlockfrostError;
+use pallas_network::miniprotocols::localstate::{self, queries_v16};
+use serde_json::{Value, json};
+
+impl NodeClient {
+    /// Fetches protocol parameters of the current epoch with the
+    /// `GetCurrentPParams` local-state query, in the Blockfrost format.
+    ///
+    /// **Note**: the node does not know the epoch nonce, nor the names of the
+    /// cost model parameters, so `nonce` is empty and `cost_models` is `null`.
+    /// Only `cost_models_raw` is filled in.
+    pub async fn protocol_parameters(&mut self) -> Result<EpochsParamResponse, BlockfrostError> {
+        let (epoch, pparams) = self.current_pparams().await?;
+
+        serde_json::from_value(pparams_to_json(epoch, pparams)).map_err(|e| {
+            BlockfrostError::internal_server_error(format!(
+                "Failed to convert protocol parameters: {e}"
+            ))
+        })
+    }
+
+    /// Protocol parameters of the current epoch in the Ogmios v6 format.
+    ///
+    /// **Note**: unlike Ogmios, cost models are plain arrays of integers, and
+    /// rationals come directly from the ledger, so they aren’t normalized.
+    pub async fn protocol_parameters_ogmios(&mut self) -> Result<Value, BlockfrostError> {
+        let (_, pparams) = self.current_pparams().await?;
+
+        Ok(pparams_to_ogmios(&pparams))
+    }
+
+    async fn current_pparams(
+        &mut self,
+    ) -> Result<(u32, queries_v16::ProtocolParam), BlockfrostError> {
+        self.with_cached_statequery(
+            "protocol_parameters".to_string(),
+            |generic_client: &mut localstate::GenericClient| {
+                Box::pin(async move {
+                    let era = queries_v16::get_current_era(generic_client).await?;
+                    let epoch = queries_v16::get_block_epoch_number(generic_client, era).await?;
+                    let pparams = queries_v16::get_current_pparams(generic_client, era).await?;
+
+                    Ok((epoch, pparams))
+                })
+            },
+        )
+        .await
+    }
+}
+
+fn ratio(r: &queries_v16::RationalNumber) -> f64 {
+    if r.denominator == 0 {
+        0.0
+    } else {
+        r.numerator as f64 / r.denominator as f64
+    }
+}
+
+fn opt_ratio(r: Option<&queries_v16::RationalNumber>) -> Value {
+    r.map(ratio).map_or(Value::Null, |f| json!(f))
+}
+
+fn opt_string<T: ToString>(value: Option<T>) -> Value {
+    value.map_or(Value::Null, |v| json!(v.to_string()))
+}
+
+fn pparams_to_json(epoch: u32, p: queries_v16::ProtocolParam) -> Value {
+    let coins_per_utxo_size = p.ada_per_utxo_byte.map(u64::from);
+    let (protocol_major_ver, protocol_minor_ver) = p.protocol_version.unwrap_or_default();
+
+    let cost_models_raw = p.cost_models_for_script_languages.as_ref().map(|models| {
+        let mut raw = serde_json::Map::new();
+
+        for (name, model) in [
+            ("PlutusV1", &models.plutus_v1),
+            ("PlutusV2", &models.plutus_v2),
+            ("PlutusV3", &models.plutus_v3),
+        ] {
+            if let Some(model) = model {
+                raw.insert(name.to_string(), json!(model));
+            }
+        }
+
+        raw
+    });
+
+    let pvt = p.pool_voting_thresholds.as_ref();
+    let dvt = p.drep_voting_thresholds.as_ref();
+
+    json!({
+        "epoch": epoch,
+        "min_fee_a": p.minfee_a.unwrap_or_default(),
+        "min_fee_b": p.minfee_b.unwrap_or_default(),
+        "max_block_size": p.max_block_body_size.unwrap_or_default(),
+        "max_tx_size": p.max_transaction_size.unwrap_or_default(),
+        "max_block_header_size": p.max_block_header_size.unwrap_or_default(),
+        "key_deposit": p.key_deposit.map(u64::from).unwrap_or_default().to_string(),
+        "pool_deposit": p.pool_deposit.map(u64::from).unwrap_or_default().to_string(),
+        "e_max": p.maximum_epoch.unwrap_or_default(),
+        "n_opt": p.desired_number_of_stake_pools.unwrap_or_default(),
+        "a0": p.pool_pledge_influence.as_ref().map(ratio).unwrap_or_default(),
+        "rho": p.expansion_rate.as_ref().map(ratio).unwrap_or_default(),
+        "tau": p.treasury_growth_rate.as_ref().map(ratio).unwrap_or_default(),
+        // There is no decentralization since Babbage:
+        "decentralisation_param": 0,
+        "extra_entropy": null,
+        "protocol_major_ver": protocol_major_ver,
+        "protocol_minor_ver": protocol_minor_ver,
+        "min_utxo": coins_per_utxo_size.unwrap_or_default().to_string(),
+        "min_pool_cost": p.min_pool_cost.map(u64::from).unwrap_or_default().to_string(),
+        "nonce": "",
+        "cost_models": null,
+        "cost_models_raw": cost_models_raw,
+        "price_mem": opt_ratio(p.execution_costs.as_ref().map(|c| &c.mem_price)),
+        "price_step": opt_ratio(p.execution_costs.as_ref().map(|c| &c.step_price)),
+        "max_tx_ex_mem": opt_string(p.max_tx_ex_units.as_ref().map(|u| u.mem)),
+        "max_tx_ex_steps": opt_string(p.max_tx_ex_units.as_ref().map(|u| u.steps)),
+        "max_block_ex_mem": opt_string(p.max_block_ex_units.as_ref().map(|u| u.mem)),
+        "max_block_ex_steps": opt_string(p.max_block_ex_units.as_ref().map(|u| u.steps)),
+        "max_val_size": opt_string(p.max_value_size),
+        "collateral_percent": p.collateral_percentage,
+        "max_collateral_inputs": p.max_collateral_inputs,
+        "coins_per_utxo_size": opt_string(coins_per_utxo_size),
+        "coins_per_utxo_word": opt_string(coins_per_utxo_size),
+        "pvt_motion_no_confidence": opt_ratio(pvt.map(|t| &t.motion_no_confidence)),
+        "pvt_committee_normal": opt_ratio(pvt.map(|t| &t.committee_normal)),
+        "pvt_committee_no_confidence": opt_ratio(pvt.map(|t| &t.committee_no_confidence)),
+        "pvt_hard_fork_initiation": opt_ratio(pvt.map(|t| &t.hard_fork_initiation)),
+        "pvtpp_security_group": opt_ratio(pvt.map(|t| &t.pp_security_group)),
+        "pvt_p_p_security_group": opt_ratio(pvt.map(|t| &t.pp_security_group)),
+        "dvt_motion_no_confidence": opt_ratio(dvt.map(|t| &t.motion_no_confidence)),
+        "dvt_committee_normal": opt_ratio(dvt.map(|t| &t.committee_normal)),
+        "dvt_committee_no_confidence": opt_ratio(dvt.map(|t| &t.committee_no_confidence)),
+        "dvt_update_to_constitution": opt_ratio(dvt.map(|t| &t.update_to_constitution)),
+        "dvt_hard_fork_initiation": opt_ratio(dvt.map(|t| &t.hard_fork_initiation)),
+        "dvt_p_p_network_group": opt_ratio(dvt.map(|t| &t.pp_network_group)),
+        "dvt_p_p_economic_group": opt_ratio(dvt.map(|t| &t.pp_economic_group)),
+        "dvt_p_p_technical_group": opt_ratio(dvt.map(|t| &t.pp_technical_group)),
+        "dvt_p_p_gov_group": opt_ratio(dvt.map(|t| &t.pp_gov_group)),
+        "dvt_treasury_withdrawal": opt_ratio(dvt.map(|t| &t.treasury_withdrawal)),
+        "committee_min_size": opt_string(p.min_committee_size),
+        "committee_max_term_length": opt_string(p.committee_term_limit),
+        "gov_action_lifetime": opt_string(p.governance_action_validity_period),
+        "gov_action_deposit": opt_string(p.governance_action_deposit.map(u64::from)),
+        "drep_deposit": opt_string(p.drep_deposit.map(u64::from)),
+        "drep_activity": opt_string(p.drep_inactivity_period),
+        "min_fee_ref_script_cost_per_byte":
+            opt_ratio(p.minfee_refscript_cost_per_byte.as_ref()),
+    })
+}
+
+fn ogmios_ratio(r: &queries_v16::RationalNumber) -> Value {
+    json!(format!("{}/{}", r.numerator, r.denominator))
+}
+
+fn lovelace<T: Into<u64>>(value: Option<T>) -> Value {
+    value.map_or(Value::Null, |v| json!({ "ada": { "lovelace": v.into() } }))
+}
+
+fn bytes<T: serde::Serialize>(value: Option<T>) -> Value {
+    value.map_or(Value::Null, |v| json!({ "bytes": v }))
+}
+
+fn pparams_to_ogmios(p: &queries_v16::ProtocolParam) -> Value {
+    let plutus_cost_models = p.cost_models_for_script_languages.as_ref().map(|models| {
+        let mut result = serde_json::Map::new();
+
+        for (name, model) in [
+            ("plutus:v1", &models.plutus_v1),
+            ("plutus:v2", &models.plutus_v2),
+            ("plutus:v3", &models.plutus_v3),
+        ] {
+            if let Some(model) = model {
+                result.insert(name.to_string(), json!(model));
+            }
+        }
+
+        result
+    });
+
+    let pvt = p.pool_voting_thresholds.as_ref();
+    let dvt = p.drep_voting_thresholds.as_ref();
+
+    let (major, minor) = p.protocol_version.unwrap_or_default();
+
+    json!({
+        "minFeeCoefficient": p.minfee_a,
+        "minFeeConstant": lovelace(p.minfee_b),
+        // `range` and `multiplier` are hard-coded in the ledger:
+        "minFeeReferenceScripts": p.minfee_refscript_cost_per_byte.as_ref().map(|r| json!({
+            "base": ratio(r),
+            "range": 25_600,
+            "multiplier": 1.2,
+        })),
+        "maxBlockBodySize": bytes(p.max_block_body_size),
+        "maxBlockHeaderSize": bytes(p.max_block_header_size),
+        "maxTransactionSize": bytes(p.max_transaction_size),
+        "maxValueSize": bytes(p.max_value_size),
+        "stakeCredentialDeposit": lovelace(p.key_deposit.map(u64::from)),
+        "stakePoolDeposit": lovelace(p.pool_deposit.map(u64::from)),
+        "stakePoolRetirementEpochBound": p.maximum_epoch,
+        "desiredNumberOfStakePools": p.desired_number_of_stake_pools,
+        "stakePoolPledgeInfluence": p.pool_pledge_influence.as_ref().map(ogmios_ratio),
+        "monetaryExpansion": p.expansion_rate.as_ref().map(ogmios_ratio),
+        "treasuryExpansion": p.treasury_growth_rate.as_ref().map(ogmios_ratio),
+        "minStakePoolCost": lovelace(p.min_pool_cost.map(u64::from)),
+        // Only the coefficient remains since Babbage:
+        "minUtxoDepositConstant": { "ada": { "lovelace": 0 } },
+        "minUtxoDepositCoefficient": p.ada_per_utxo_byte.map(u64::from),
+        "plutusCostModels": plutus_cost_models,
+        "scriptExecutionPrices": p.execution_costs.as_ref().map(|c| json!({
+            "memory": ogmios_ratio(&c.mem_price),
+            "cpu": ogmios_ratio(&c.step_price),
+        })),
+        "maxExecutionUnitsPerTransaction": p.max_tx_ex_units.as_ref()
+            .map(|u| json!({ "memory": u.mem, "cpu": u.steps })),
+        "maxExecutionUnitsPerBlock": p.max_block_ex_units.as_ref()
+            .map(|u| json!({ "memory": u.mem, "cpu": u.steps })),
+        "collateralPercentage": p.collateral_percentage,
+        "maxCollateralInputs": p.max_collateral_inputs,
+        "version": { "major": major, "minor": minor },
+        "stakePoolVotingThresholds": pvt.map(|t| json!({
+            "noConfidence": ogmios_ratio(&t.motion_no_confidence),
+            "constitutionalCommittee": {
+                "default": ogmios_ratio(&t.committee_normal),
+                "stateOfNoConfidence": ogmios_ratio(&t.committee_no_confidence),
+            },
+            "hardForkInitiation": ogmios_ratio(&t.hard_fork_initiation),
+            "protocolParametersUpdate": {
+                "security": ogmios_ratio(&t.pp_security_group),
+            },
+        })),
+        "delegateRepresentativeVotingThresholds": dvt.map(|t| json!({
+            "noConfidence": ogmios_ratio(&t.motion_no_confidence),
+            "constitutionalCommittee": {
+                "default": ogmios_ratio(&t.committee_normal),
+                "stateOfNoConfidence": ogmios_ratio(&t.committee_no_confidence),
+            },
+            "constitution": ogmios_ratio(&t.update_to_constitution),
+            "hardForkInitiation": ogmios_ratio(&t.hard_fork_initiation),
+            "protocolParametersUpdate": {
+                "network": ogmios_ratio(&t.pp_network_group),
+                "economic": ogmios_ratio(&t.pp_economic_group),
+                "technical": ogmios_ratio(&t.pp_technical_group),
+                "governance": ogmios_ratio(&t.pp_gov_group),
+            },
+            "treasuryWithdrawals": ogmios_ratio(&t.treasury_withdrawal),
+        })),
+        "constitutionalCommitteeMinSize": p.min_committee_size,
+        "constitutionalCommitteeMaxTermLength": p.committee_term_limit,
+        "governanceActionLifetime": p.governance_action_validity_period,
+        "governanceActionDeposit": lovelace(p.governance_action_deposit.map(u64::from)),
+        "delegateRepresentativeDeposit": lovelace(p.drep_deposit.map(u64::from)),
+        "delegateRepresentativeMaxIdleTime": p.drep_inactivity_period,
+    })
+}
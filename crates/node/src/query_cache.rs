@@ -0,0 +1,135 @@
+use super::connection::NodeClient;
+use bf_common::errors::BlockfrostError;
+use metrics::counter;
+use pallas_network::miniprotocols::{
+    Point,
+    localstate::{self, queries_v16},
+};
+use std::{
+    any::Any,
+    collections::HashMap,
+    pin::Pin,
+    sync::{Arc, Mutex},
+};
+
+/// A tip changes every ~20 seconds, and then all entries are dropped, but
+/// queries with arguments could still pile up in the meantime.
+const MAX_ENTRIES: usize = 10_000;
+
+/// Results of local-state queries, valid for a single chain point. Shared by
+/// all connections to the same node.
+#[derive(Clone, Default)]
+pub(crate) struct QueryCache {
+    entries: Arc<Mutex<Entries>>,
+}
+
+#[derive(Default)]
+struct Entries {
+    /// `(slot, block hash)`, empty for the origin.
+    point: Option<(u64, Vec<u8>)>,
+    values: HashMap<String, Arc<dyn Any + Send + Sync>>,
+}
+
+impl QueryCache {
+    fn get<A: Clone + 'static>(&self, point: &(u64, Vec<u8>), key: &str) -> Option<A> {
+        let entries = self.entries.lock().expect("QueryCache poisoned");
+
+        if entries.point.as_ref() != Some(point) {
+            return None;
+        }
+
+        entries
+            .values
+            .get(key)
+            .and_then(|value| value.downcast_ref::<A>())
+            .cloned()
+    }
+
+    fn insert<A: Send + Sync + 'static>(&self, point: (u64, Vec<u8>), key: String, value: A) {
+        let mut entries = self.entries.lock().expect("QueryCache poisoned");
+
+        if entries.point.as_ref() != Some(&point) || entries.values.len() >= MAX_ENTRIES {
+            entries.values.clear();
+            entries.point = Some(point);
+        }
+
+        entries.values.insert(key, Arc::new(value));
+    }
+}
+
+impl NodeClient {
+    /// Like [`Self::with_statequery`], but the result is cached under `key`
+    /// until the chain point changes. The `key` has to identify both the query
+    /// and all its arguments.
+    ///
+    /// **Note**: a hit still costs an acquisition and a `GetChainPoint` query,
+    /// so that we never return results for a stale chain point.
+    pub(crate) async fn with_cached_statequery<A, F>(
+        &mut self,
+        key: String,
+        action: F,
+    ) -> Result<A, BlockfrostError>
+    where
+        A: Clone + Send + Sync + 'static,
+        F: for<'a> FnOnce(
+                &'a mut localstate::GenericClient,
+            ) -> Pin<
+                Box<
+                    dyn std::future::Future<Output = Result<A, BlockfrostError>> + 'a + Sync + Send,
+                >,
+            > + Send
+            + Sync
+            + 'static,
+    {
+        let cache = self.query_cache.clone();
+
+        self.with_statequery(move |generic_client: &mut localstate::GenericClient| {
+            Box::pin(async move {
+                let point = match queries_v16::get_chain_point(generic_client).await? {
+                    Point::Origin => (0, vec![]),
+                    Point::Specific(slot, hash) => (slot, hash),
+                };
+
+                if let Some(value) = cache.get(&point, &key) {
+                    counter!("cardano_node_query_cache_hits").increment(1);
+                    return Ok(value);
+                }
+
+                counter!("cardano_node_query_cache_misses").increment(1);
+
+                let value = action(generic_client).await?;
+                cache.insert(point, key, value.clone());
+
+                Ok(value)
+            })
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_query_cache() {
+        let cache = QueryCache::default();
+        let point = (10, vec![0xaa]);
+
+        assert_eq!(cache.get::<u32>(&point, "era"), None);
+
+        cache.insert(point.clone(), "era".to_string(), 6u32);
+        assert_eq!(cache.get::<u32>(&point, "era"), Some(6));
+
+        // The same key with another type is a miss:
+        assert_eq!(cache.get::<u64>(&point, "era"), None);
+
+        // A new chain point invalidates everything:
+        let next_point = (11, vec![0xbb]);
+        assert_eq!(cache.get::<u32>(&next_point, "era"), None);
+
+        cache.insert(next_point.clone(), "epoch".to_string(), 500u32);
+        assert_eq!(cache.get::<u32>(&next_point, "era"), None);
+        assert_eq!(cache.get::<u32>(&point, "epoch"), None);
+    }
+}
@@ -1,8 +1,7 @@
-use super::connection::NodeClient;
+use super::{connection::NodeClient, era_history::query_era_history};
 use bf_common::errors::BlockfrostError;
-use chrono::{Duration, TimeZone, Utc};
+use chrono::Utc;
 use pallas_network::{miniprotocols, miniprotocols::localstate};
-use pallas_traverse::wellknown;
 use serde::{Deserialize, Serialize};
 use std::boxed::Box;
 
@@ -12,96 +11,43 @@ pub struct NodeInfo {
     pub epoch: u32,
     pub era: u16,
     pub slot: u64,
+    /// UNIX time of `slot`, in seconds.
+    #[serde(default)]
+    pub slot_time: i64,
     pub sync_progress: f64,
 }
 
 impl NodeClient {
     /// Reports the sync progress of the node.
     pub async fn sync_progress(&mut self) -> Result<NodeInfo, BlockfrostError> {
-        let network_magic = self.network_magic;
         self.with_statequery(|generic_client: &mut localstate::GenericClient| {
             Box::pin(async move {
-                // FIXME: this is debatable, because it won’t work for custom networks; we should rather
-                // get this information by calling `Ouroboros.Consensus.HardFork.History.Qry.slotToWallclock`
-                // like both cardano-cli (through cardano-api) and Ogmios do, but it’s not implemented
-                // in pallas_network yet.
-                let wellknown_genesis = wellknown::GenesisValues::from_magic(
-                    network_magic,
-                )
-                .ok_or_else(|| {
-                    BlockfrostError::internal_server_error(format!(
-                        "Only well-known networks are supported (unsupported network magic: {network_magic})"
-                    ))
-                })?;
-
-                let system_start =
-                    localstate::queries_v16::get_system_start(generic_client).await?;
+                let era_history = query_era_history(generic_client).await?;
                 let chain_point = localstate::queries_v16::get_chain_point(generic_client).await?;
                 let slot = chain_point.slot_or_default();
 
-                fn big_int_to_i128(i: localstate::queries_v16::BigInt) -> Result<i128, String> {
-                    match i {
-                        localstate::queries_v16::BigInt::Int(ii) => Ok(i128::from(ii)),
-                        _ => Err(format!("cannot convert {i:?} to i128")),
-                    }
-                }
-
-                let year: i32 = big_int_to_i128(system_start.year)
-                    .and_then(|i| i32::try_from(i).map_err(|err| err.to_string()))
-                    .map_err(|e| {
-                        BlockfrostError::internal_server_error(format!(
-                            "Failed to convert year: {e}"
-                        ))
-                    })?;
-
-                let base_date = Utc
-                    .with_ymd_and_hms(year, 1, 1, 0, 0, 0)
-                    .single()
-                    .ok_or_else(|| {
-                        BlockfrostError::internal_server_error("Invalid base date".to_string())
-                    })?;
-
-                let days = Duration::days(system_start.day_of_year - 1);
-
-                let nanoseconds: i64 = big_int_to_i128(system_start.picoseconds_of_day)
-                    .map(|i| i / 1_000)
-                    .and_then(|i| i64::try_from(i).map_err(|err| err.to_string()))
-                    .map_err(|e| {
-                        BlockfrostError::internal_server_error(format!(
-                            "Failed to convert picoseconds: {e}"
-                        ))
-                    })?;
-
-                let duration_ns = Duration::nanoseconds(nanoseconds);
-
-                let utc_start = base_date + days + duration_ns;
+                let utc_start = era_history.system_start;
 
                 let current_era = localstate::queries_v16::get_current_era(generic_client).await?;
 
-                let epoch = if current_era == 0 {
-                    // Byron is special…
-                    let (epoch, _) = wellknown_genesis.absolute_slot_to_relative(slot);
-                    epoch as u32
-                } else {
-                    localstate::queries_v16::get_block_epoch_number(generic_client, current_era)
-                        .await?
+                let out_of_range = || {
+                    BlockfrostError::internal_server_error(format!(
+                        "Slot {slot} is out of the era history range"
+                    ))
                 };
 
-                let slot_time_secs: i64 = wellknown_genesis
-                    .slot_to_wallclock(slot)
-                    .try_into()
-                    .map_err(|e| {
-                        BlockfrostError::internal_server_error(format!(
-                            "Failed to convert slot time: {e}"
-                        ))
+                let epoch = era_history
+                    .slot_to_epoch(slot)
+                    .ok_or_else(out_of_range)
+                    .and_then(|epoch| {
+                        u32::try_from(epoch).map_err(|e| {
+                            BlockfrostError::internal_server_error(format!(
+                                "Failed to convert epoch: {e}"
+                            ))
+                        })
                     })?;
 
-                let utc_slot = Utc
-                    .timestamp_opt(slot_time_secs, 0)
-                    .single()
-                    .ok_or_else(|| {
-                        BlockfrostError::internal_server_error("Invalid slot timestamp".to_string())
-                    })?;
+                let utc_slot = era_history.slot_to_time(slot).ok_or_else(out_of_range)?;
 
                 let utc_now = Utc::now();
 
@@ -128,6 +74,7 @@ impl NodeClient {
                     era: current_era,
                     epoch,
                     slot,
+                    slot_time: utc_slot.timestamp(),
                     block,
                 })
             })
@@ -1,14 +1,56 @@
-use super::connection::NodeClient;
-use crate::cbor::validation::validate_tx_cbor;
+use super::{connection::NodeClient, facade::RawRejection};
+use crate::cbor::validation::{PhaseOneContext, PhaseOneParams, check_phase_one, validate_tx_cbor};
 use bf_common::errors::BlockfrostError;
-use pallas_crypto::hash::Hasher;
+use pallas_codec::minicbor;
+use pallas_crypto::hash::{Hash, Hasher};
 use pallas_hardano::display::haskell_error::as_node_submit_error;
 use pallas_network::miniprotocols::{
-    localstate,
-    localtxsubmission::{EraTx, Response},
+    localstate::{self, queries_v16},
+    localtxsubmission::{EraTx, Response, TxValidationError},
 };
+use pallas_traverse::{Era, MultiEraOutput, MultiEraTx};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
 use tracing::{error, info, warn};
 
+/// Why [`NodeClient::submit_transaction`] failed.
+#[derive(Debug)]
+pub enum TxSubmitError {
+    /// The node rejected the transaction. Besides our Haskell-like display of
+    /// the `ApplyTxErr`, we keep its raw CBOR, so that it can be decoded into
+    /// the exact cardano-submit-api JSON by `bf_error_decoder`.
+    Rejected { display: String, cbor: Vec<u8> },
+    /// Our local phase-1 checks failed, so the transaction wasn’t sent. It’s
+    /// the same cardano-submit-api JSON that a node rejection decodes to.
+    PhaseOne(Value),
+    /// The node couldn’t be asked, i.e. the connection broke before the
+    /// transaction was sent, so it’s safe to retry on another node.
+    Connection(BlockfrostError),
+    /// The connection broke after the transaction was sent, but before the
+    /// node replied, so it may or may not have been accepted. It’s not retried.
+    Interrupted(BlockfrostError),
+    /// Anything else, e.g. malformed CBOR.
+    Other(BlockfrostError),
+}
+
+impl From<BlockfrostError> for TxSubmitError {
+    fn from(err: BlockfrostError) -> Self {
+        Self::Other(err)
+    }
+}
+
+impl From<TxSubmitError> for BlockfrostError {
+    fn from(err: TxSubmitError) -> Self {
+        match err {
+            TxSubmitError::Rejected { display, .. } => BlockfrostError::custom_400(display),
+            TxSubmitError::PhaseOne(json) => BlockfrostError::custom_400(json.to_string()),
+            TxSubmitError::Connection(err)
+            | TxSubmitError::Interrupted(err)
+            | TxSubmitError::Other(err) => err,
+        }
+    }
+}
+
 impl NodeClient {
     /// Submits a transaction to the connected Cardano node.
     /// This API meant to be fully compatible with cardano-submit-api.
@@ -16,24 +58,79 @@ impl NodeClient {
     /// If the transaction was rejected, should return HTTP 400 with a JSON body:
     /// * Swagger: <https://github.com/IntersectMBO/cardano-node/blob/6e969c6bcc0f07bd1a69f4d76b85d6fa9371a90b/cardano-submit-api/swagger.yaml#L52>
     /// * Haskell code: <https://github.com/IntersectMBO/cardano-node/blob/6e969c6bcc0f07bd1a69f4d76b85d6fa9371a90b/cardano-submit-api/src/Cardano/TxSubmit/Web.hs#L158>
-    pub async fn submit_transaction(&mut self, tx: Vec<u8>) -> Result<String, BlockfrostError> {
+    pub async fn submit_transaction(&mut self, tx: Vec<u8>) -> Result<String, TxSubmitError> {
         validate_tx_cbor(&tx)?;
 
-        let current_era = self
-            .with_statequery(|generic_client: &mut localstate::GenericClient| {
-                Box::pin(async {
-                    Ok(localstate::queries_v16::get_current_era(generic_client).await?)
-                })
-            })
-            .await?;
+        let (current_era, params, contexts) = self
+            .phase_one_inputs(std::slice::from_ref(&tx))
+            .await
+            .map_err(TxSubmitError::Connection)?;
+
+        check_phase_one(&tx, &params, &contexts[0])?;
+
+        self.submit_era_tx(current_era, tx).await
+    }
+
+    /// Submits several transactions in order, e.g. a chain of dependent ones,
+    /// with a single era and protocol parameters query. Returns a result for
+    /// every transaction that was attempted: with `stop_on_failure`, nothing
+    /// after the first failure is, so the results can be shorter than `txs`.
+    ///
+    /// **Note**: if the connection breaks, we always stop, because the node
+    /// can’t be asked about the rest anyway.
+    pub async fn submit_transactions(
+        &mut self,
+        txs: Vec<Vec<u8>>,
+        stop_on_failure: bool,
+    ) -> Result<Vec<Result<String, TxSubmitError>>, BlockfrostError> {
+        let (current_era, params, contexts) = self.phase_one_inputs(&txs).await?;
+
+        let mut results = Vec::with_capacity(txs.len());
 
+        for (tx, context) in txs.into_iter().zip(contexts) {
+            let result = match validate_tx_cbor(&tx)
+                .map_err(TxSubmitError::Other)
+                .and_then(|_| check_phase_one(&tx, &params, &context))
+            {
+                Ok(()) => self.submit_era_tx(current_era, tx).await,
+                Err(err) => Err(err),
+            };
+
+            let stop = match &result {
+                Ok(_) => false,
+                Err(TxSubmitError::Connection(_) | TxSubmitError::Interrupted(_)) => true,
+                Err(_) => stop_on_failure,
+            };
+
+            results.push(result);
+
+            if stop {
+                break;
+            }
+        }
+
+        Ok(results)
+    }
+
+    async fn submit_era_tx(
+        &mut self,
+        current_era: u16,
+        tx: Vec<u8>,
+    ) -> Result<String, TxSubmitError> {
         let era_tx = EraTx(current_era, tx.clone());
 
         // Connect to the node
         let submission_client = self.client.as_mut().unwrap().submission();
 
-        // Submit the transaction
-        match submission_client.submit_tx(era_tx).await {
+        // Only an error before the transaction was written to the node can be
+        // safely retried elsewhere:
+        if let Err(e) = submission_client.send_submit_tx(era_tx).await {
+            return Err(TxSubmitError::Connection(
+                self.submission_failure(format!("{e:?}"), &tx),
+            ));
+        }
+
+        match submission_client.recv_submit_tx_response().await {
             Ok(Response::Accepted) => {
                 let txid = hex::encode(Hasher::<256>::hash_cbor(&tx));
 
@@ -43,39 +140,190 @@ impl NodeClient {
                 );
                 Ok(txid)
             },
-            Ok(Response::Rejected(reason)) => {
-                let haskell_display = as_node_submit_error(reason)
-                    .unwrap_or_else(|e| format!("Failed to format submit error: {e}"));
+            Ok(Response::Rejected(RawRejection(cbor))) => {
+                let haskell_display = display_rejection(&cbor);
                 warn!(
                     connection_id = self.connection_id,
                     "TxSubmitFail: {}, CBOR: {}",
                     haskell_display,
                     hex::encode(&tx)
                 );
-                Err(BlockfrostError::custom_400(haskell_display))
-            },
-            Err(e) => {
-                let error_message = format!(
-                    "Error during transaction submission: {:?}, CBOR: {}",
-                    e,
-                    hex::encode(&tx)
-                );
-                self.invalidate_connection(&error_message); // Never use this connection again.
-                error!(
-                    "{}: {}, CBOR: {}",
-                    "TxSubmitFail",
-                    error_message,
-                    hex::encode(&tx)
-                );
-
-                Err(BlockfrostError::custom_400(error_message))
+                Err(TxSubmitError::Rejected {
+                    display: haskell_display,
+                    cbor,
+                })
             },
+            Err(e) => Err(TxSubmitError::Interrupted(
+                self.submission_failure(format!("{e:?}"), &tx),
+            )),
         }
     }
+
+    fn submission_failure(&mut self, err: String, tx: &[u8]) -> BlockfrostError {
+        let error_message = format!(
+            "Error during transaction submission: {}, CBOR: {}",
+            err,
+            hex::encode(tx)
+        );
+        self.invalidate_connection(&error_message); // Never use this connection again.
+        error!(
+            "{}: {}, CBOR: {}",
+            "TxSubmitFail",
+            error_message,
+            hex::encode(tx)
+        );
+
+        BlockfrostError::custom_400(error_message)
+    }
+
+    /// Fetches everything [`check_phase_one`] needs for `txs` in a single
+    /// local-state acquisition: the current era, the protocol parameters (only
+    /// once per epoch), the tip slot, and the collateral inputs. Returns one
+    /// [`PhaseOneContext`] per transaction.
+    pub(crate) async fn phase_one_inputs(
+        &mut self,
+        txs: &[Vec<u8>],
+    ) -> Result<(u16, PhaseOneParams, Vec<PhaseOneContext>), BlockfrostError> {
+        // Undecodable transactions will fail `validate_tx_cbor` later anyway:
+        let collaterals: Vec<Vec<(Hash<32>, u64)>> = txs
+            .iter()
+            .map(|tx| {
+                MultiEraTx::decode(tx)
+                    .map(|tx| {
+                        tx.collateral()
+                            .iter()
+                            .map(|input| (*input.hash(), input.index()))
+                            .collect()
+                    })
+                    .unwrap_or_default()
+            })
+            .collect();
+
+        let to_resolve: BTreeSet<queries_v16::TransactionInput> = collaterals
+            .iter()
+            .flatten()
+            .map(|&(transaction_id, index)| queries_v16::TransactionInput {
+                transaction_id,
+                index,
+            })
+            .collect();
+
+        let cached_params = self
+            .phase_one_params
+            .read()
+            .expect("phase-1 params cache poisoned")
+            .clone();
+
+        let (era, params, tip_slot, resolved) = self
+            .with_statequery(|generic_client: &mut localstate::GenericClient| {
+                Box::pin(async move {
+                    let era = queries_v16::get_current_era(generic_client).await?;
+                    let epoch = queries_v16::get_block_epoch_number(generic_client, era).await?;
+                    let tip = queries_v16::get_chain_point(generic_client).await?;
+
+                    let params = match cached_params.filter(|params| params.epoch == epoch) {
+                        Some(params) => params,
+                        None => {
+                            let pparams =
+                                queries_v16::get_current_pparams(generic_client, era).await?;
+
+                            PhaseOneParams::new(epoch, era, &pparams)
+                        },
+                    };
+
+                    let resolved = if to_resolve.is_empty() {
+                        Vec::new()
+                    } else {
+                        queries_v16::get_utxo_by_txin(generic_client, era, to_resolve)
+                            .await?
+                            .to_vec()
+                    };
+
+                    Ok((era, params, tip.slot_or_default(), resolved))
+                })
+            })
+            .await?;
+
+        *self
+            .phase_one_params
+            .write()
+            .expect("phase-1 params cache poisoned") = Some(params.clone());
+
+        let resolved_coins: BTreeMap<(Hash<32>, u64), u64> = resolved
+            .iter()
+            .filter_map(|(utxo, output)| {
+                let cbor = minicbor::to_vec(output).ok()?;
+                let output = MultiEraOutput::decode(Era::Conway, &cbor).ok()?;
+
+                Some((
+                    (utxo.transaction_id, u64::from(utxo.index)),
+                    output.value().coin(),
+                ))
+            })
+            .collect();
+
+        let contexts = collaterals
+            .iter()
+            .map(|collateral| PhaseOneContext {
+                slot: tip_slot + 1,
+                // If some are missing, the node will report them better:
+                collateral_inputs_coin: (!collateral.is_empty())
+                    .then(|| {
+                        collateral
+                            .iter()
+                            .map(|input| resolved_coins.get(input).copied())
+                            .sum::<Option<u64>>()
+                    })
+                    .flatten(),
+            })
+            .collect();
+
+        Ok((era, params, contexts))
+    }
 }
+
+/// Our Haskell-like display of a node rejection, from its raw CBOR.
+fn display_rejection(cbor: &[u8]) -> String {
+    match minicbor::decode::<TxValidationError>(cbor) {
+        Ok(reason) => as_node_submit_error(reason)
+            .unwrap_or_else(|e| format!("Failed to format submit error: {e}")),
+        Err(e) => format!(
+            "Failed to decode submit error: {e}, CBOR: {}",
+            hex::encode(cbor)
+        ),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use pallas_network::miniprotocols::localtxsubmission;
+
+    #[test]
+    fn test_raw_rejection_keeps_node_bytes() {
+        // `MsgRejectTx` with a Conway `WithdrawalsNotInRewardsCERTS`:
+        let rejection = hex::decode("8182068182028200a0").unwrap();
+        let message = [hex::decode("8202").unwrap(), rejection.clone()].concat();
+
+        let decoded: localtxsubmission::Message<EraTx, RawRejection> =
+            minicbor::decode(&message).unwrap();
+
+        match decoded {
+            localtxsubmission::Message::RejectTx(RawRejection(raw)) => assert_eq!(raw, rejection),
+            _ => panic!("expected a rejection"),
+        }
+    }
+
+    #[test]
+    fn test_display_rejection() {
+        let rejection = hex::decode("8182068182028200a0").unwrap();
+        assert!(display_rejection(&rejection).contains("WithdrawalsNotInRewardsCERTS"));
+
+        assert_eq!(
+            display_rejection(&[0x82, 0x01]),
+            "Failed to decode submit error: unexpected type u8 at position 1: expected array, CBOR: 8201"
+        );
+    }
 
     /// This test makes sure in case of invalid CBOR transaction, submit_transaction returns an error without even going to the node.
     #[tokio::test]
@@ -84,7 +332,9 @@ mod tests {
             client: None,
             connection_id: 0,
             unrecoverable_error_happened: false,
-            network_magic: 2,
+            socket_path: "/path/to/socket".to_string(),
+            phase_one_params: Default::default(),
+            query_cache: Default::default(),
         };
 
         // Test invalid CBOR transaction
@@ -0,0 +1,391 @@
+use super::{
+    chain_sync::{BlockHeader, ChainEvent, ChainFollower, ChainPoint, MAX_ROLLBACK_DEPTH},
+    pool::NodePool,
+};
+use bf_common::errors::BlockfrostError;
+use pallas_traverse::MultiEraTx;
+use serde::Serialize;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+use tokio::{
+    sync::{RwLock, broadcast::error::RecvError},
+    time::{Duration, Instant, interval},
+};
+use tracing::{info, warn};
+
+const MEMPOOL_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Transactions that never settle (e.g. without a TTL, and dropped from the
+/// mempool) are forgotten after this long.
+const MAX_UNSETTLED_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TxStatus {
+    /// Submitted, but not (or no longer) seen in the mempool.
+    Pending,
+    InMempool,
+    Confirmed,
+    /// The block with the transaction was rolled back.
+    RolledBack,
+    /// Past its TTL (`invalid_hereafter`) without being included in a block.
+    Expired,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct TxStatusResponse {
+    pub tx_hash: String,
+    pub status: TxStatus,
+    pub block: Option<String>,
+    pub block_height: Option<u64>,
+    pub slot: Option<u64>,
+    /// Number of blocks on top of (and including) the confirming block.
+    pub confirmations: Option<u64>,
+    pub invalid_hereafter: Option<u64>,
+    pub resubmissions: u32,
+}
+
+struct TrackedTx {
+    cbor: Vec<u8>,
+    ttl: Option<u64>,
+    status: TxStatus,
+    block: Option<BlockHeader>,
+    resubmissions: u32,
+    tracked_at: Instant,
+    /// Tip height when the transaction got confirmed or expired.
+    settled_at: Option<u64>,
+}
+
+/// Tracks submitted transactions until they are deep enough in the chain,
+/// watching the mempool (local-tx-monitor) and new blocks ([`ChainFollower`]).
+///
+/// It can be safely cloned.
+#[derive(Clone)]
+pub struct TxTracker {
+    txs: Arc<RwLock<TrackedTxs>>,
+    chain_follower: ChainFollower,
+}
+
+impl TxTracker {
+    /// Starts tracking in the background. With `resubmit_on_rollback`, the
+    /// transactions from rolled back blocks are submitted again.
+    pub fn spawn(
+        node: NodePool,
+        chain_follower: ChainFollower,
+        resubmit_on_rollback: bool,
+    ) -> Self {
+        let self_ = Self {
+            txs: Arc::new(RwLock::new(TrackedTxs::default())),
+            chain_follower,
+        };
+
+        let tracker = self_.clone();
+        let resubmit_node = resubmit_on_rollback.then(|| node.clone());
+        tokio::spawn(async move { tracker.follow_chain(resubmit_node).await });
+
+        let tracker = self_.clone();
+        tokio::spawn(async move { tracker.watch_mempool(node).await });
+
+        self_
+    }
+
+    /// Starts tracking a transaction which was just accepted by the node.
+    pub async fn track(&self, cbor: Vec<u8>) -> Result<(), BlockfrostError> {
+        let tx = MultiEraTx::decode(&cbor).map_err(|e| {
+            BlockfrostError::internal_server_error(format!("Failed to decode transaction: {e}"))
+        })?;
+
+        let tx_hash = tx.hash().to_string();
+        let ttl = tx.ttl();
+
+        self.txs
+            .write()
+            .await
+            .track(tx_hash, cbor, ttl, Instant::now());
+
+        Ok(())
+    }
+
+    /// Returns `None` for transactions which are not tracked.
+    pub async fn status(&self, tx_hash: &str) -> Option<TxStatusResponse> {
+        let tip_height = self.chain_follower.tip().await.map(|tip| tip.height);
+
+        self.txs.read().await.status(tx_hash, tip_height)
+    }
+
+    async fn follow_chain(&self, resubmit_node: Option<NodePool>) {
+        let mut events = self.chain_follower.subscribe();
+
+        loop {
+            match events.recv().await {
+                Ok(ChainEvent::RollForward { block, tx_hashes }) => {
+                    let mut txs = self.txs.write().await;
+
+                    txs.roll_forward(&block, &tx_hashes);
+                    txs.prune(block.height, Instant::now());
+                },
+                Ok(ChainEvent::RollBackward { point }) => {
+                    let rolled_back = self.txs.write().await.roll_backward(point.as_ref());
+
+                    if let Some(node) = &resubmit_node {
+                        self.resubmit(node, rolled_back).await;
+                    }
+                },
+                Err(RecvError::Lagged(skipped)) => {
+                    warn!("TxTracker: missed {skipped} chain events, some statuses may be stale");
+                },
+                Err(RecvError::Closed) => break,
+            }
+        }
+    }
+
+    async fn resubmit(&self, node: &NodePool, rolled_back: Vec<(String, Vec<u8>)>) {
+        for (tx_hash, cbor) in rolled_back {
+            match node.submit_transaction(cbor).await {
+                Ok(_) => {
+                    info!("TxTracker: resubmitted {tx_hash} after a rollback");
+                    self.txs.write().await.resubmitted(&tx_hash);
+                },
+                Err(err) => warn!("TxTracker: failed to resubmit {tx_hash}: {err:?}"),
+            }
+        }
+    }
+
+    async fn watch_mempool(&self, node: NodePool) {
+        let mut ticker = interval(MEMPOOL_POLL_INTERVAL);
+
+        loop {
+            ticker.tick().await;
+
+            if !self.txs.read().await.any_unconfirmed() {
+                continue;
+            }
+
+            let snapshot = async {
+                let mut node = node.get().await?;
+
+                node.mempool_snapshot().await
+            }
+            .await;
+
+            match snapshot {
+                Ok(snapshot) => {
+                    let in_mempool = snapshot.txs.into_iter().map(|tx| tx.hash).collect();
+
+                    self.txs.write().await.update_mempool(&in_mempool);
+                },
+                Err(err) => warn!("TxTracker: failed to read the mempool: {}", err.message),
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+struct TrackedTxs(HashMap<String, TrackedTx>);
+
+impl TrackedTxs {
+    fn track(&mut self, tx_hash: String, cbor: Vec<u8>, ttl: Option<u64>, now: Instant) {
+        self.0.insert(
+            tx_hash,
+            TrackedTx {
+                cbor,
+                ttl,
+                status: TxStatus::Pending,
+                block: None,
+                resubmissions: 0,
+                tracked_at: now,
+                settled_at: None,
+            },
+        );
+    }
+
+    fn status(&self, tx_hash: &str, tip_height: Option<u64>) -> Option<TxStatusResponse> {
+        let tx = self.0.get(tx_hash)?;
+
+        let confirmations = tx
+            .block
+            .as_ref()
+            .zip(tip_height)
+            .map(|(block, tip_height)| (tip_height + 1).saturating_sub(block.height));
+
+        Some(TxStatusResponse {
+            tx_hash: tx_hash.to_string(),
+            status: tx.status,
+            block: tx.block.as_ref().map(|block| block.hash.clone()),
+            block_height: tx.block.as_ref().map(|block| block.height),
+            slot: tx.block.as_ref().map(|block| block.slot),
+            confirmations,
+            invalid_hereafter: tx.ttl,
+            resubmissions: tx.resubmissions,
+        })
+    }
+
+    fn any_unconfirmed(&self) -> bool {
+        self.0.values().any(|tx| {
+            matches!(
+                tx.status,
+                TxStatus::Pending | TxStatus::InMempool | TxStatus::RolledBack
+            )
+        })
+    }
+
+    fn roll_forward(&mut self, block: &BlockHeader, tx_hashes: &[String]) {
+        let included: HashSet<&String> = tx_hashes.iter().collect();
+
+        for (tx_hash, tx) in self.0.iter_mut() {
+            if included.contains(tx_hash) {
+                tx.status = TxStatus::Confirmed;
+                tx.block = Some(block.clone());
+                tx.settled_at = Some(block.height);
+            } else if tx.status != TxStatus::Confirmed
+                && tx.status != TxStatus::Expired
+                && tx.ttl.is_some_and(|ttl| block.slot >= ttl)
+            {
+                tx.status = TxStatus::Expired;
+                tx.settled_at = Some(block.height);
+            }
+        }
+    }
+
+    /// Returns the transactions from rolled back blocks.
+    fn roll_backward(&mut self, point: Option<&ChainPoint>) -> Vec<(String, Vec<u8>)> {
+        let point_slot = point.map(|point| point.slot);
+        let is_rolled_back = |slot: u64| point_slot.is_none_or(|point_slot| slot > point_slot);
+
+        let mut rolled_back = Vec::new();
+
+        for (tx_hash, tx) in self.0.iter_mut() {
+            match tx.status {
+                TxStatus::Confirmed
+                    if tx.block.as_ref().is_some_and(|b| is_rolled_back(b.slot)) =>
+                {
+                    tx.status = TxStatus::RolledBack;
+                    tx.block = None;
+                    tx.settled_at = None;
+
+                    rolled_back.push((tx_hash.clone(), tx.cbor.clone()));
+                },
+                // Valid again, once the chain is back before its TTL:
+                TxStatus::Expired if tx.ttl.is_some_and(is_rolled_back) => {
+                    tx.status = TxStatus::Pending;
+                    tx.settled_at = None;
+                },
+                _ => {},
+            }
+        }
+
+        rolled_back
+    }
+
+    fn resubmitted(&mut self, tx_hash: &str) {
+        if let Some(tx) = self.0.get_mut(tx_hash)
+            && tx.status == TxStatus::RolledBack
+        {
+            tx.status = TxStatus::Pending;
+            tx.resubmissions += 1;
+        }
+    }
+
+    fn update_mempool(&mut self, in_mempool: &HashSet<String>) {
+        for (tx_hash, tx) in self.0.iter_mut() {
+            match tx.status {
+                TxStatus::Pending | TxStatus::RolledBack if in_mempool.contains(tx_hash) => {
+                    tx.status = TxStatus::InMempool;
+                },
+                TxStatus::InMempool if !in_mempool.contains(tx_hash) => {
+                    tx.status = TxStatus::Pending;
+                },
+                _ => {},
+            }
+        }
+    }
+
+    /// Forgets transactions which can no longer change their status.
+    fn prune(&mut self, tip_height: u64, now: Instant) {
+        self.0.retain(|_, tx| match tx.settled_at {
+            Some(settled_at) => tip_height <= settled_at + MAX_ROLLBACK_DEPTH as u64,
+            None => now.duration_since(tx.tracked_at) < MAX_UNSETTLED_AGE,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block(height: u64) -> BlockHeader {
+        BlockHeader {
+            hash: format!("{height:064x}"),
+            height,
+            slot: height * 20,
+            era: 6,
+        }
+    }
+
+    fn tracked(ttl: Option<u64>) -> TrackedTxs {
+        let mut txs = TrackedTxs::default();
+        txs.track("aa".to_string(), vec![0x84], ttl, Instant::now());
+        txs
+    }
+
+    fn status(txs: &TrackedTxs, tip_height: u64) -> TxStatusResponse {
+        txs.status("aa", Some(tip_height)).unwrap()
+    }
+
+    #[test]
+    fn test_confirmation_and_rollback() {
+        let mut txs = tracked(None);
+
+        txs.update_mempool(&HashSet::from(["aa".to_string()]));
+        assert_eq!(status(&txs, 9).status, TxStatus::InMempool);
+
+        txs.roll_forward(&block(10), &["aa".to_string()]);
+        let response = status(&txs, 12);
+        assert_eq!(response.status, TxStatus::Confirmed);
+        assert_eq!(response.slot, Some(200));
+        assert_eq!(response.confirmations, Some(3));
+
+        let rolled_back = txs.roll_backward(Some(&ChainPoint {
+            hash: block(9).hash,
+            slot: 180,
+        }));
+        assert_eq!(rolled_back, vec![("aa".to_string(), vec![0x84])]);
+        assert_eq!(status(&txs, 9).status, TxStatus::RolledBack);
+        assert_eq!(status(&txs, 9).confirmations, None);
+
+        txs.resubmitted("aa");
+        assert_eq!(status(&txs, 9).status, TxStatus::Pending);
+        assert_eq!(status(&txs, 9).resubmissions, 1);
+    }
+
+    #[test]
+    fn test_expiry() {
+        let mut txs = tracked(Some(210));
+
+        txs.roll_forward(&block(10), &[]);
+        assert_eq!(status(&txs, 10).status, TxStatus::Pending);
+
+        txs.roll_forward(&block(11), &[]);
+        assert_eq!(status(&txs, 11).status, TxStatus::Expired);
+
+        txs.roll_backward(Some(&ChainPoint {
+            hash: block(10).hash,
+            slot: 200,
+        }));
+        assert_eq!(status(&txs, 10).status, TxStatus::Pending);
+    }
+
+    #[test]
+    fn test_prune() {
+        let mut txs = tracked(None);
+
+        txs.roll_forward(&block(10), &["aa".to_string()]);
+        txs.prune(10 + MAX_ROLLBACK_DEPTH as u64, Instant::now());
+        assert!(txs.status("aa", None).is_some());
+
+        txs.prune(11 + MAX_ROLLBACK_DEPTH as u64, Instant::now());
+        assert!(txs.status("aa", None).is_none());
+    }
+}
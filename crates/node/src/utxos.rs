@@ -0,0 +1,230 @@
+use super::connection::NodeClient;
+use crate::ledger_query::{RawArg, decode_error, encode_error, encode_set, query_if_current};
+use bf_api_provider::types::AddressesUtxosResponse;
+use bf_common::{
+    errors::BlockfrostError,
+    pagination::{ApplyPagination, Pagination},
+};
+use pallas_addresses::Address;
+use pallas_codec::minicbor::{self, Decoder, data::Type};
+use pallas_crypto::hash::Hasher;
+use pallas_network::miniprotocols::localstate::{self, queries_v16};
+use pallas_primitives::conway::{DatumOption, ScriptRef};
+use pallas_traverse::{Era, MultiEraOutput, OriginalHash};
+use serde_json::{Value, json};
+use std::str::FromStr;
+
+const GET_UTXO_BY_ADDRESS: u16 = 6;
+
+impl NodeClient {
+    /// Lists UTxOs of an address with the `GetUTxOByAddress` local-state query.
+    /// Outputs are kept exactly as the node sent them, so that datum hashes are
+    /// those of the original bytes.
+    ///
+    /// **Note**: the node doesn’t know in which block an output was created, so
+    /// results are ordered by `(tx_hash, output_index)` instead of by block, and
+    /// `block` is left empty.
+    pub async fn addresses_utxos(
+        &mut self,
+        address: &str,
+        pagination: &Pagination,
+    ) -> Result<AddressesUtxosResponse, BlockfrostError> {
+        // `addr_vkh` and `script` aren’t addresses, so the node can’t look them up:
+        let address_bytes = Address::from_str(address)
+            .map_err(|_| {
+                BlockfrostError::custom_400(
+                    "Querying UTxOs by payment credential requires a data node".to_string(),
+                )
+            })?
+            .to_vec();
+
+        let addresses = encode_set(&[encode_bytes(&address_bytes)?])?;
+
+        let result = self
+            .with_statequery(|generic_client: &mut localstate::GenericClient| {
+                Box::pin(async move {
+                    let era = queries_v16::get_current_era(generic_client).await?;
+
+                    query_if_current(
+                        generic_client,
+                        era,
+                        GET_UTXO_BY_ADDRESS,
+                        vec![RawArg(addresses)],
+                    )
+                    .await
+                })
+            })
+            .await?;
+
+        let mut utxos = decode_utxos(&result).map_err(decode_error)?;
+
+        utxos.sort_by(|a, b| (&a.0, a.1).cmp(&(&b.0, b.1)));
+        utxos.apply_pagination(pagination);
+
+        let response = utxos
+            .iter()
+            .map(|(tx_hash, index, cbor)| output_to_json(address, tx_hash, *index, cbor))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        serde_json::from_value(Value::Array(response)).map_err(|e| {
+            BlockfrostError::internal_server_error(format!("Failed to convert UTxOs: {e}"))
+        })
+    }
+}
+
+fn output_to_json(
+    address: &str,
+    tx_hash: &str,
+    index: u64,
+    cbor: &[u8],
+) -> Result<Value, BlockfrostError> {
+    let output = MultiEraOutput::decode(Era::Conway, cbor).map_err(|e| {
+        BlockfrostError::internal_server_error(format!("Failed to decode UTxO: {e}"))
+    })?;
+
+    let (data_hash, inline_datum) = output_datum(&output);
+
+    Ok(json!({
+        "address": address,
+        "tx_hash": tx_hash,
+        "tx_index": index,
+        "output_index": index,
+        "amount": output_amount(&output),
+        "block": "",
+        "data_hash": data_hash,
+        "inline_datum": inline_datum,
+        "reference_script_hash": output_reference_script_hash(&output),
+    }))
+}
+
+/// Decodes `Map TxIn TxOut` into `(tx_hash, output_index, output)`, with the
+/// raw CBOR of each output.
+fn decode_utxos(cbor: &[u8]) -> Result<Vec<(String, u64, Vec<u8>)>, minicbor::decode::Error> {
+    let mut d = Decoder::new(cbor);
+    let mut utxos = Vec::new();
+
+    let mut remaining = d.map()?;
+
+    loop {
+        match remaining {
+            Some(0) => break,
+            Some(n) => remaining = Some(n - 1),
+            None if d.datatype()? == Type::Break => break,
+            None => {},
+        }
+
+        d.array()?;
+        let tx_hash = hex::encode(d.bytes()?);
+        let index = d.u64()?;
+
+        let start = d.position();
+        d.skip()?;
+        let end = d.position();
+
+        utxos.push((tx_hash, index, cbor[start..end].to_vec()));
+    }
+
+    Ok(utxos)
+}
+
+fn encode_bytes(bytes: &[u8]) -> Result<Vec<u8>, BlockfrostError> {
+    let mut buffer = Vec::new();
+
+    minicbor::Encoder::new(&mut buffer)
+        .bytes(bytes)
+        .map_err(encode_error)?;
+
+    Ok(buffer)
+}
+
+/// Lovelace followed by native assets, in the Blockfrost format.
+pub(crate) fn output_amount(output: &MultiEraOutput) -> Vec<Value> {
+    let value = output.value();
+    let mut amount = vec![json!({ "unit": "lovelace", "quantity": value.coin().to_string() })];
+
+    for policy_assets in value.assets() {
+        for asset in policy_assets.assets() {
+            amount.push(json!({
+                "unit": format!("{}{}", policy_assets.policy(), hex::encode(asset.name())),
+                "quantity": asset.any_coin().to_string(),
+            }));
+        }
+    }
+
+    amount
+}
+
+/// Returns `(data_hash, inline_datum)`.
+pub(crate) fn output_datum(output: &MultiEraOutput) -> (Option<String>, Option<String>) {
+    match output.datum() {
+        Some(DatumOption::Hash(hash)) => (Some(hash.to_string()), None),
+        Some(DatumOption::Data(data)) => (
+            Some(data.original_hash().to_string()),
+            Some(hex::encode(data.raw_cbor())),
+        ),
+        None => (None, None),
+    }
+}
+
+pub(crate) fn output_reference_script_hash(output: &MultiEraOutput) -> Option<String> {
+    output.script_ref().map(|script_ref| {
+        let (tag, bytes): (u8, &[u8]) = match &script_ref {
+            ScriptRef::NativeScript(script) => (0, script.raw_cbor()),
+            ScriptRef::PlutusV1Script(script) => (1, script.as_ref()),
+            ScriptRef::PlutusV2Script(script) => (2, script.as_ref()),
+            ScriptRef::PlutusV3Script(script) => (3, script.as_ref()),
+        };
+
+        Hasher::<224>::hash_tagged(bytes, tag).to_string()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_utxos_keeps_the_node_bytes() {
+        // An inline datum of `Constr 0 [1]`, as an indefinite-length list, which
+        // re-encoding would turn into a definite one:
+        let datum = "d8799f01ff";
+
+        // {[h'11…', 2]: {0: h'61…', 1: 1000000, 2: [1, 24(h'd8799f01ff')]}}
+        let cbor = hex::decode(
+            [
+                "a1",
+                "825820",
+                &"11".repeat(32),
+                "02",
+                "a3",
+                "00581d61",
+                &"22".repeat(28),
+                "011a000f4240",
+                "028201d81845",
+                datum,
+            ]
+            .concat(),
+        )
+        .unwrap();
+
+        let utxos = decode_utxos(&cbor).unwrap();
+        assert_eq!(utxos.len(), 1);
+
+        let (tx_hash, index, output) = &utxos[0];
+        assert_eq!(tx_hash, &"11".repeat(32));
+        assert_eq!(*index, 2);
+
+        let json = output_to_json("addr1", tx_hash, *index, output).unwrap();
+        let datum_bytes = hex::decode(datum).unwrap();
+
+        assert_eq!(json["inline_datum"], datum);
+        assert_eq!(
+            json["data_hash"],
+            Hasher::<256>::hash(&datum_bytes).to_string()
+        );
+        assert_eq!(
+            json["amount"],
+            json!([{ "unit": "lovelace", "quantity": "1000000" }])
+        );
+    }
+}
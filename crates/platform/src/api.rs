@@ -8,9 +8,11 @@ pub mod epochs;
 pub mod governance;
 pub mod health;
 pub mod ledger;
+pub mod mempool;
 pub mod metadata;
 pub mod metrics;
 pub mod network;
+pub mod ogmios;
 pub mod pools;
 pub mod root;
 pub mod scripts;
@@ -1,5 +1,5 @@
 use crate::accounts::{AccountData, AccountsPath};
-use crate::{BlockfrostError, api::ApiResult, server::state::AppState};
+use crate::{api::ApiResult, server::state::AppState};
 use axum::extract::{Path, State};
 use bf_api_provider::types::AccountsAddressesTotalResponse;
 
@@ -7,7 +7,11 @@ pub async fn route(
     Path(path): Path<AccountsPath>,
     State(state): State<AppState>,
 ) -> ApiResult<AccountsAddressesTotalResponse> {
-    let _ = AccountData::from_account_path(path.stake_address, &state.config.network)?;
+    let account = AccountData::from_account_path(path.stake_address, &state.config.network)?;
+    let data_node = state.data_node()?;
 
-    Err(BlockfrostError::not_found())
+    data_node
+        .accounts()
+        .addresses_total(&account.stake_address)
+        .await
 }
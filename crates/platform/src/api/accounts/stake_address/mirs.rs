@@ -1,5 +1,5 @@
 use crate::accounts::{AccountData, AccountsPath};
-use crate::{BlockfrostError, api::ApiResult, server::state::AppState};
+use crate::{api::ApiResult, server::state::AppState};
 use axum::extract::{Path, Query, State};
 use bf_api_provider::types::AccountsMirResponse;
 use bf_common::pagination::{Pagination, PaginationQuery};
@@ -9,8 +9,12 @@ pub async fn route(
     State(state): State<AppState>,
     Query(pagination_query): Query<PaginationQuery>,
 ) -> ApiResult<AccountsMirResponse> {
-    let _ = AccountData::from_account_path(path.stake_address, &state.config.network)?;
-    let _ = Pagination::from_query(pagination_query)?;
+    let account = AccountData::from_account_path(path.stake_address, &state.config.network)?;
+    let pagination = Pagination::from_query(pagination_query)?;
+    let data_node = state.data_node()?;
 
-    Err(BlockfrostError::not_found())
+    data_node
+        .accounts()
+        .mirs(&account.stake_address, &pagination)
+        .await
 }
@@ -1,17 +1,35 @@
 use crate::accounts::{AccountData, AccountsPath};
-use crate::{api::ApiResult, server::state::AppState};
-use axum::extract::{Path, State};
+use crate::{api::ApiResult, health_monitor::HealthMonitor, server::state::AppState};
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+};
 use bf_api_provider::types::AccountsResponse;
+use bf_node::pool::NodePool;
 
 pub async fn route(
     State(state): State<AppState>,
+    Extension(node): Extension<NodePool>,
+    Extension(health_monitor): Extension<HealthMonitor>,
     Path(path): Path<AccountsPath>,
 ) -> ApiResult<AccountsResponse> {
     let account = AccountData::from_account_path(path.stake_address, &state.config.network)?;
-    let data_node = state.data_node()?;
 
-    data_node
-        .accounts()
-        .stake_address(&account.stake_address)
-        .await
+    if let Some(data_node) = state.healthy_data_node(&health_monitor).await {
+        return data_node
+            .accounts()
+            .stake_address(&account.stake_address)
+            .await;
+    }
+
+    // XXX: Axum must not abort Ouroboros protocols in the middle, hence a separate Tokio task:
+    let response = tokio::spawn(async move {
+        let mut node = node.get().await?;
+
+        node.account(&account.stake_address).await
+    })
+    .await
+    .expect("account panic!")?;
+
+    Ok(Json(response))
 }
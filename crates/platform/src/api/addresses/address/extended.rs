@@ -1,5 +1,5 @@
 use crate::addresses::{AddressInfo, AddressesPath};
-use crate::{BlockfrostError, api::ApiResult, server::state::AppState};
+use crate::{api::ApiResult, server::state::AppState};
 use axum::extract::{Path, State};
 use bf_api_provider::types::AddressesContentExtendedResponse;
 
@@ -8,7 +8,8 @@ pub async fn route(
     State(app_state): State<AppState>,
 ) -> ApiResult<AddressesContentExtendedResponse> {
     let AddressesPath { address, asset: _ } = address_path;
-    let _ = AddressInfo::from_address(&address, app_state.config.network.clone())?;
+    let address_info = AddressInfo::from_address(&address, app_state.config.network.clone())?;
+    let data_node = app_state.data_node()?;
 
-    Err(BlockfrostError::not_found())
+    data_node.addresses().extended(&address_info.address).await
 }
@@ -1,24 +1,43 @@
 use crate::addresses::{AddressInfo, AddressesPath};
-use crate::server::state::AppState;
-use axum::extract::{Path, Query, State};
+use crate::{health_monitor::HealthMonitor, server::state::AppState};
+use axum::{
+    Extension, Json,
+    extract::{Path, Query, State},
+};
 use bf_api_provider::types::AddressesUtxosResponse;
 use bf_common::{
     pagination::{Pagination, PaginationQuery},
     types::ApiResult,
 };
+use bf_node::pool::NodePool;
 
 pub async fn route(
     State(state): State<AppState>,
+    Extension(node): Extension<NodePool>,
+    Extension(health_monitor): Extension<HealthMonitor>,
     Path(address_path): Path<AddressesPath>,
     Query(pagination_query): Query<PaginationQuery>,
 ) -> ApiResult<AddressesUtxosResponse> {
     let AddressesPath { address, asset: _ } = address_path;
     let pagination = Pagination::from_query(pagination_query)?;
     let address_info = AddressInfo::from_address(&address, state.config.network.clone())?;
-    let data_node = state.data_node()?;
 
-    data_node
-        .addresses()
-        .utxos(&address_info.address, &pagination)
-        .await
+    if let Some(data_node) = state.healthy_data_node(&health_monitor).await {
+        return data_node
+            .addresses()
+            .utxos(&address_info.address, &pagination)
+            .await;
+    }
+
+    // XXX: Axum must not abort Ouroboros protocols in the middle, hence a separate Tokio task:
+    let utxos = tokio::spawn(async move {
+        let mut node = node.get().await?;
+
+        node.addresses_utxos(&address_info.address, &pagination)
+            .await
+    })
+    .await
+    .expect("addresses_utxos panic!")?;
+
+    Ok(Json(utxos))
 }
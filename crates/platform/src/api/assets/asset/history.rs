@@ -1,15 +1,20 @@
 use crate::assets::{AssetData, AssetsPath};
-use crate::{BlockfrostError, api::ApiResult};
-use axum::extract::{Path, Query};
+use crate::{api::ApiResult, server::state::AppState};
+use axum::extract::{Path, Query, State};
 use bf_api_provider::types::AssetsHistoryResponse;
 use bf_common::pagination::{Pagination, PaginationQuery};
 
 pub async fn route(
+    State(state): State<AppState>,
     Path(path): Path<AssetsPath>,
     Query(pagination_query): Query<PaginationQuery>,
 ) -> ApiResult<AssetsHistoryResponse> {
-    let _ = AssetData::from_query(path.asset)?;
-    let _ = Pagination::from_query(pagination_query)?;
+    let asset_data = AssetData::from_query(path.asset)?;
+    let pagination = Pagination::from_query(pagination_query)?;
+    let data_node = state.data_node()?;
 
-    Err(BlockfrostError::not_found())
+    data_node
+        .assets()
+        .history(&asset_data.asset, &pagination)
+        .await
 }
@@ -1,12 +1,20 @@
-use crate::{BlockfrostError, api::ApiResult};
-use axum::extract::Query;
+use crate::assets::{PolicyData, PolicyPath};
+use crate::{api::ApiResult, server::state::AppState};
+use axum::extract::{Path, Query, State};
 use bf_api_provider::types::AssetsPolicyResponse;
 use bf_common::pagination::{Pagination, PaginationQuery};
 
 pub async fn route(
+    State(state): State<AppState>,
+    Path(path): Path<PolicyPath>,
     Query(pagination_query): Query<PaginationQuery>,
 ) -> ApiResult<AssetsPolicyResponse> {
-    let _ = Pagination::from_query(pagination_query)?;
+    let policy_data = PolicyData::from_path(path.policy_id)?;
+    let pagination = Pagination::from_query(pagination_query)?;
+    let data_node = state.data_node()?;
 
-    Err(BlockfrostError::not_found())
+    data_node
+        .assets()
+        .policy(&policy_data.policy_id, &pagination)
+        .await
 }
@@ -1,13 +1,17 @@
-use crate::BlockfrostError;
-use axum::extract::Query;
+use crate::server::state::AppState;
+use axum::extract::{Query, State};
 use bf_api_provider::types::AssetsResponse;
 use bf_common::{
     pagination::{Pagination, PaginationQuery},
     types::ApiResult,
 };
 
-pub async fn route(Query(pagination_query): Query<PaginationQuery>) -> ApiResult<AssetsResponse> {
-    let _ = Pagination::from_query(pagination_query)?;
+pub async fn route(
+    State(state): State<AppState>,
+    Query(pagination_query): Query<PaginationQuery>,
+) -> ApiResult<AssetsResponse> {
+    let pagination = Pagination::from_query(pagination_query)?;
+    let data_node = state.data_node()?;
 
-    Err(BlockfrostError::not_found())
+    data_node.assets().list(&pagination).await
 }
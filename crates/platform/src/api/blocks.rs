@@ -2,3 +2,4 @@ pub mod epoch;
 pub mod hash_or_number;
 pub mod latest;
 pub mod slot;
+pub mod stream;
@@ -1,6 +1,39 @@
-use crate::{BlockfrostError, api::ApiResult};
-use bf_api_provider::types::BlocksResponse;
+use crate::blocks::BlocksEpochSlotPath;
+use crate::epochs::EpochData;
+use crate::{api::ApiResult, server::state::AppState};
+use axum::{
+    Extension,
+    extract::{Path, State},
+};
+use bf_api_provider::types::BlocksSingleResponse;
+use bf_common::errors::BlockfrostError;
+use bf_node::pool::NodePool;
 
-pub async fn route() -> ApiResult<BlocksResponse> {
-    Err(BlockfrostError::not_found())
+pub async fn route(
+    State(state): State<AppState>,
+    Extension(node): Extension<NodePool>,
+    Path(path): Path<BlocksEpochSlotPath>,
+) -> ApiResult<BlocksSingleResponse> {
+    let epoch_data = EpochData::from_path(
+        path.epoch_number,
+        &state.config.network,
+        &state.config.genesis,
+    )?;
+    let epoch = u64::try_from(epoch_data.epoch_number)?;
+    let slot_in_epoch = path.slot_number.parse::<u64>().map_err(|_| {
+        BlockfrostError::custom_400("Missing, out of range or malformed slot number.".to_string())
+    })?;
+    let data_node = state.data_node()?;
+
+    // Epoch lengths differ between eras, so this needs the era history.
+    // XXX: Axum must not abort Ouroboros protocols in the middle, hence a separate Tokio task:
+    let slot = tokio::spawn(async move {
+        let mut node = node.get().await?;
+
+        node.epoch_slot_to_slot(epoch, slot_in_epoch).await
+    })
+    .await
+    .expect("epoch_slot_to_slot panic!")?;
+
+    data_node.blocks().by_slot(&slot.to_string()).await
 }
@@ -1,15 +1,20 @@
 use crate::blocks::{BlockData, BlocksPath};
-use crate::{BlockfrostError, api::ApiResult, server::state::AppState};
+use crate::{api::ApiResult, server::state::AppState};
 use axum::extract::{Path, Query, State};
 use bf_api_provider::types::BlocksAddressesContentResponse;
-use bf_common::pagination::PaginationQuery;
+use bf_common::pagination::{Pagination, PaginationQuery};
 
 pub async fn route(
-    State(_state): State<AppState>,
-    Query(_pagination_query): Query<PaginationQuery>,
+    State(state): State<AppState>,
+    Query(pagination_query): Query<PaginationQuery>,
     Path(blocks_path): Path<BlocksPath>,
 ) -> ApiResult<BlocksAddressesContentResponse> {
-    let _ = BlockData::from_string(blocks_path.hash_or_number)?;
+    let block_data = BlockData::from_string(blocks_path.hash_or_number)?;
+    let pagination = Pagination::from_query(pagination_query)?;
+    let data_node = state.data_node()?;
 
-    Err(BlockfrostError::not_found())
+    data_node
+        .blocks()
+        .addresses(&block_data.hash_or_number, &pagination)
+        .await
 }
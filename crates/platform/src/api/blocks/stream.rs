@@ -0,0 +1,57 @@
+use axum::{
+    Extension,
+    extract::ws::{CloseFrame, Message, WebSocket, WebSocketUpgrade, close_code},
+    response::IntoResponse,
+};
+use bf_node::chain_sync::ChainFollower;
+use tokio::sync::broadcast::error::RecvError;
+
+/// Streams [`bf_node::chain_sync::ChainEvent`]s as JSON text messages, i.e.
+/// headers and transaction hashes of new blocks, and rollbacks.
+pub async fn route(
+    ws: WebSocketUpgrade,
+    Extension(chain_follower): Extension<ChainFollower>,
+) -> impl IntoResponse {
+    ws.on_upgrade(move |socket| stream_events(socket, chain_follower))
+}
+
+async fn stream_events(mut socket: WebSocket, chain_follower: ChainFollower) {
+    let mut events = chain_follower.subscribe();
+
+    loop {
+        tokio::select! {
+            event = events.recv() => match event {
+                Ok(event) => {
+                    let json = match serde_json::to_string(&event) {
+                        Ok(json) => json,
+                        Err(err) => {
+                            tracing::error!("Failed to serialize a chain event: {err}");
+                            continue;
+                        },
+                    };
+
+                    if socket.send(Message::Text(json.into())).await.is_err() {
+                        break;
+                    }
+                },
+                // A client that missed some events would have a wrong view of the chain:
+                Err(RecvError::Lagged(skipped)) => {
+                    tracing::warn!("Closing a lagging chain event stream after {skipped} events");
+
+                    let close_frame = CloseFrame {
+                        code: close_code::AGAIN,
+                        reason: "Too slow, please reconnect".into(),
+                    };
+                    let _ = socket.send(Message::Close(Some(close_frame))).await;
+                    break;
+                },
+                Err(RecvError::Closed) => break,
+            },
+            message = socket.recv() => match message {
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                // We don’t expect any requests, pings are answered by Axum:
+                Some(Ok(_)) => {},
+            },
+        }
+    }
+}
@@ -1,10 +1,26 @@
-use crate::server::state::AppState;
-use axum::extract::State;
+use crate::{health_monitor::HealthMonitor, server::state::AppState};
+use axum::{Extension, Json, extract::State};
 use bf_api_provider::types::EpochsParamResponse;
 use bf_common::types::ApiResult;
+use bf_node::pool::NodePool;
 
-pub async fn route(State(state): State<AppState>) -> ApiResult<EpochsParamResponse> {
-    let data_node = state.data_node()?;
+pub async fn route(
+    State(state): State<AppState>,
+    Extension(node): Extension<NodePool>,
+    Extension(health_monitor): Extension<HealthMonitor>,
+) -> ApiResult<EpochsParamResponse> {
+    if let Some(data_node) = state.healthy_data_node(&health_monitor).await {
+        return data_node.epochs().latest_parameters().await;
+    }
 
-    data_node.epochs().latest_parameters().await
+    // XXX: Axum must not abort Ouroboros protocols in the middle, hence a separate Tokio task:
+    let parameters = tokio::spawn(async move {
+        let mut node = node.get().await?;
+
+        node.protocol_parameters().await
+    })
+    .await
+    .expect("protocol_parameters panic!")?;
+
+    Ok(Json(parameters))
 }
@@ -1,6 +1,36 @@
-use crate::{BlockfrostError, api::ApiResult};
-use bf_api_provider::types::EpochsResponse;
+use crate::epochs::latest_epoch_from_node;
+use crate::{api::ApiResult, health_monitor::HealthMonitor, server::state::AppState};
+use axum::{Extension, Json, extract::State};
+use bf_common::errors::BlockfrostError;
+use bf_node::{chain_sync::ChainFollower, pool::NodePool};
+use serde_json::Value;
 
-pub async fn route() -> ApiResult<EpochsResponse> {
-    Err(BlockfrostError::not_found())
+pub async fn route(
+    State(state): State<AppState>,
+    Extension(health_monitor): Extension<HealthMonitor>,
+    Extension(chain_follower): Extension<ChainFollower>,
+    Extension(node): Extension<NodePool>,
+) -> ApiResult<Value> {
+    if let Some(data_node) = state.healthy_data_node(&health_monitor).await {
+        let epoch = data_node.epochs().latest().await?;
+
+        return Ok(Json(serde_json::to_value(epoch.0)?));
+    }
+
+    // XXX: Axum must not abort Ouroboros protocols in the middle, hence a separate Tokio task:
+    let (epoch, era_history) = tokio::spawn(async move {
+        let mut node = node.get().await?;
+
+        let epoch = node.sync_progress().await?.epoch;
+        let era_history = node.era_history().await?;
+
+        Ok::<_, BlockfrostError>((epoch, era_history))
+    })
+    .await
+    .expect("latest_epoch panic!")?;
+
+    let totals = chain_follower.epoch_totals().await;
+    let epoch = latest_epoch_from_node(u64::from(epoch), totals.as_ref(), &era_history)?;
+
+    Ok(Json(epoch))
 }
@@ -1,11 +1,18 @@
 use crate::epochs::{EpochData, EpochsPath};
-use crate::server::state::AppState;
-use axum::extract::{Path, State};
+use crate::{health_monitor::HealthMonitor, server::state::AppState};
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+};
 use bf_api_provider::types::EpochsParamResponse;
-use bf_common::types::ApiResult;
+use bf_common::{errors::BlockfrostError, types::ApiResult};
+use bf_node::pool::NodePool;
+use std::cmp::Ordering;
 
 pub async fn route(
     State(state): State<AppState>,
+    Extension(node): Extension<NodePool>,
+    Extension(health_monitor): Extension<HealthMonitor>,
     Path(epochs_path): Path<EpochsPath>,
 ) -> ApiResult<EpochsParamResponse> {
     let epoch_data = EpochData::from_path(
@@ -13,10 +20,53 @@ pub async fn route(
         &state.config.network,
         &state.config.genesis,
     )?;
-    let data_node = state.data_node()?;
 
-    data_node
-        .epochs()
-        .parameters(&epoch_data.epoch_number)
-        .await
+    if let Some(data_node) = state.healthy_data_node(&health_monitor).await {
+        return data_node
+            .epochs()
+            .parameters(&epoch_data.epoch_number)
+            .await;
+    }
+
+    // XXX: Axum must not abort Ouroboros protocols in the middle, hence a separate Tokio task:
+    let parameters = tokio::spawn(async move {
+        let mut node = node.get().await?;
+
+        node.protocol_parameters().await
+    })
+    .await
+    .expect("protocol_parameters panic!")?;
+
+    check_node_epoch(epoch_data.epoch_number, parameters.epoch)?;
+
+    Ok(Json(parameters))
+}
+
+/// The node only knows the parameters of the current epoch.
+fn check_node_epoch(requested: i32, current: i32) -> Result<(), BlockfrostError> {
+    match requested.cmp(&current) {
+        Ordering::Equal => Ok(()),
+        Ordering::Greater => Err(BlockfrostError::not_found()),
+        Ordering::Less => Err(BlockfrostError::custom_400(
+            "Parameters of past epochs are only available with a data node".to_string(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(500, Ok(()))]
+    #[case(501, Err(404))]
+    #[case(499, Err(400))]
+    fn test_check_node_epoch(#[case] requested: i32, #[case] expected: Result<(), u16>) {
+        assert_eq!(
+            check_node_epoch(requested, 500).map_err(|err| err.status_code),
+            expected
+        );
+    }
 }
@@ -1,7 +1,24 @@
-use bf_api_provider::types::EpochsStakeResponse;
+use crate::epochs::{EpochData, EpochsPath};
+use crate::{api::ApiResult, server::state::AppState};
+use axum::extract::{Path, Query, State};
+use bf_api_provider::types::EpochsListResponse;
+use bf_common::pagination::{Pagination, PaginationQuery};
 
-use crate::{BlockfrostError, api::ApiResult};
+pub async fn route(
+    State(state): State<AppState>,
+    Path(epochs_path): Path<EpochsPath>,
+    Query(pagination_query): Query<PaginationQuery>,
+) -> ApiResult<EpochsListResponse> {
+    let epoch_data = EpochData::from_path(
+        epochs_path.epoch_number,
+        &state.config.network,
+        &state.config.genesis,
+    )?;
+    let pagination = Pagination::from_query(pagination_query)?;
+    let data_node = state.data_node()?;
 
-pub async fn route() -> ApiResult<EpochsStakeResponse> {
-    Err(BlockfrostError::not_found())
+    data_node
+        .epochs()
+        .previous(&epoch_data.epoch_number, &pagination)
+        .await
 }
@@ -1,6 +1,18 @@
-use crate::{BlockfrostError, api::ApiResult};
-use bf_api_provider::types::EpochsStakeResponse;
+use crate::epochs::{EpochData, EpochsPath};
+use crate::{api::ApiResult, server::state::AppState};
+use axum::extract::{Path, State};
+use bf_api_provider::types::EpochsResponse;
 
-pub async fn route() -> ApiResult<EpochsStakeResponse> {
-    Err(BlockfrostError::not_found())
+pub async fn route(
+    State(state): State<AppState>,
+    Path(epochs_path): Path<EpochsPath>,
+) -> ApiResult<EpochsResponse> {
+    let epoch_data = EpochData::from_path(
+        epochs_path.epoch_number,
+        &state.config.network,
+        &state.config.genesis,
+    )?;
+    let data_node = state.data_node()?;
+
+    data_node.epochs().by_number(&epoch_data.epoch_number).await
 }
@@ -1,6 +1,26 @@
-use crate::{BlockfrostError, api::ApiResult};
+use crate::epochs::{EpochData, EpochsPoolPath};
+use crate::pools::PoolData;
+use crate::{api::ApiResult, server::state::AppState};
+use axum::extract::{Path, Query, State};
 use bf_api_provider::types::EpochStakePoolResponse;
+use bf_common::pagination::{Pagination, PaginationQuery};
 
-pub async fn route() -> ApiResult<EpochStakePoolResponse> {
-    Err(BlockfrostError::not_found())
+pub async fn route(
+    State(state): State<AppState>,
+    Path(epochs_path): Path<EpochsPoolPath>,
+    Query(pagination_query): Query<PaginationQuery>,
+) -> ApiResult<EpochStakePoolResponse> {
+    let epoch_data = EpochData::from_path(
+        epochs_path.epoch_number,
+        &state.config.network,
+        &state.config.genesis,
+    )?;
+    let pool_data = PoolData::from_path(&epochs_path.pool_id)?;
+    let pagination = Pagination::from_query(pagination_query)?;
+    let data_node = state.data_node()?;
+
+    data_node
+        .epochs()
+        .stakes_by_pool(&epoch_data.epoch_number, &pool_data.pool_id, &pagination)
+        .await
 }
@@ -1,7 +1,23 @@
+use crate::dreps::{DRepData, DrepsPath};
+use crate::server::state::AppState;
+use axum::extract::{Path, Query, State};
 use bf_api_provider::types::DrepsDelegatorsResponse;
+use bf_common::{
+    pagination::{Pagination, PaginationQuery},
+    types::ApiResult,
+};
 
-use crate::{BlockfrostError, api::ApiResult};
+pub async fn route(
+    State(state): State<AppState>,
+    Path(drep_path): Path<DrepsPath>,
+    Query(pagination_query): Query<PaginationQuery>,
+) -> ApiResult<DrepsDelegatorsResponse> {
+    let drep = DRepData::new(drep_path.drep_id)?;
+    let pagination = Pagination::from_query(pagination_query)?;
+    let data_node = state.data_node()?;
 
-pub async fn route() -> ApiResult<DrepsDelegatorsResponse> {
-    Err(BlockfrostError::not_found())
+    data_node
+        .governance()
+        .drep_delegators(&drep.drep_id, &pagination)
+        .await
 }
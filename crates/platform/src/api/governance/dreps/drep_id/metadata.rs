@@ -1,6 +1,15 @@
-use crate::{BlockfrostError, api::ApiResult};
+use crate::dreps::{DRepData, DrepsPath};
+use crate::server::state::AppState;
+use axum::extract::{Path, State};
 use bf_api_provider::types::DrepsMetadataResponse;
+use bf_common::types::ApiResult;
 
-pub async fn route() -> ApiResult<DrepsMetadataResponse> {
-    Err(BlockfrostError::not_found())
+pub async fn route(
+    State(state): State<AppState>,
+    Path(drep_path): Path<DrepsPath>,
+) -> ApiResult<DrepsMetadataResponse> {
+    let drep = DRepData::new(drep_path.drep_id)?;
+    let data_node = state.data_node()?;
+
+    data_node.governance().drep_metadata(&drep.drep_id).await
 }
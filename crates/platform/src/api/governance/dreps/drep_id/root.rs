@@ -1,14 +1,33 @@
-use crate::dreps::DrepsPath;
-use crate::server::state::AppState;
-use axum::extract::{Path, State};
+use crate::dreps::{DRepData, DrepsPath};
+use crate::{health_monitor::HealthMonitor, server::state::AppState};
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+};
 use bf_api_provider::types::DrepsSingleResponse;
 use bf_common::types::ApiResult;
+use bf_node::pool::NodePool;
 
 pub async fn route(
     Path(drep_path): Path<DrepsPath>,
     State(state): State<AppState>,
+    Extension(node): Extension<NodePool>,
+    Extension(health_monitor): Extension<HealthMonitor>,
 ) -> ApiResult<DrepsSingleResponse> {
-    let data_node = state.data_node()?;
+    let drep = DRepData::new(drep_path.drep_id)?;
 
-    data_node.governance().drep(&drep_path.drep_id).await
+    if let Some(data_node) = state.healthy_data_node(&health_monitor).await {
+        return data_node.governance().drep(&drep.drep_id).await;
+    }
+
+    // XXX: Axum must not abort Ouroboros protocols in the middle, hence a separate Tokio task:
+    let response = tokio::spawn(async move {
+        let mut node = node.get().await?;
+
+        node.drep(&drep.drep_id).await
+    })
+    .await
+    .expect("drep panic!")?;
+
+    Ok(Json(response))
 }
@@ -1,6 +1,23 @@
-use crate::{BlockfrostError, api::ApiResult};
+use crate::dreps::{DRepData, DrepsPath};
+use crate::server::state::AppState;
+use axum::extract::{Path, Query, State};
 use bf_api_provider::types::DrepsUpdatesResponse;
+use bf_common::{
+    pagination::{Pagination, PaginationQuery},
+    types::ApiResult,
+};
 
-pub async fn route() -> ApiResult<DrepsUpdatesResponse> {
-    Err(BlockfrostError::not_found())
+pub async fn route(
+    State(state): State<AppState>,
+    Path(drep_path): Path<DrepsPath>,
+    Query(pagination_query): Query<PaginationQuery>,
+) -> ApiResult<DrepsUpdatesResponse> {
+    let drep = DRepData::new(drep_path.drep_id)?;
+    let pagination = Pagination::from_query(pagination_query)?;
+    let data_node = state.data_node()?;
+
+    data_node
+        .governance()
+        .drep_updates(&drep.drep_id, &pagination)
+        .await
 }
@@ -1,12 +1,17 @@
-use crate::dreps::DrepsPath;
 use crate::server::state::AppState;
-use axum::extract::{Path, State};
-use bf_api_provider::types::DrepsSingleResponse;
-use bf_common::{errors::BlockfrostError, types::ApiResult};
+use axum::extract::{Query, State};
+use bf_api_provider::types::DrepsResponse;
+use bf_common::{
+    pagination::{Pagination, PaginationQuery},
+    types::ApiResult,
+};
 
 pub async fn route(
-    Path(_drep_path): Path<DrepsPath>,
-    State(_state): State<AppState>,
-) -> ApiResult<DrepsSingleResponse> {
-    Err(BlockfrostError::not_found())
+    State(state): State<AppState>,
+    Query(pagination_query): Query<PaginationQuery>,
+) -> ApiResult<DrepsResponse> {
+    let pagination = Pagination::from_query(pagination_query)?;
+    let data_node = state.data_node()?;
+
+    data_node.governance().dreps(&pagination).await
 }
@@ -1,7 +1,35 @@
+use crate::{health_monitor::HealthMonitor, server::state::AppState};
+use axum::{
+    Extension, Json,
+    extract::{Query, State},
+};
 use bf_api_provider::types::DrepsProposalsResponse;
+use bf_common::{
+    pagination::{Pagination, PaginationQuery},
+    types::ApiResult,
+};
+use bf_node::pool::NodePool;
 
-use crate::{BlockfrostError, api::ApiResult};
+pub async fn route(
+    State(state): State<AppState>,
+    Extension(node): Extension<NodePool>,
+    Extension(health_monitor): Extension<HealthMonitor>,
+    Query(pagination_query): Query<PaginationQuery>,
+) -> ApiResult<DrepsProposalsResponse> {
+    let pagination = Pagination::from_query(pagination_query)?;
 
-pub async fn route() -> ApiResult<DrepsProposalsResponse> {
-    Err(BlockfrostError::not_found())
+    if let Some(data_node) = state.healthy_data_node(&health_monitor).await {
+        return data_node.governance().proposals(&pagination).await;
+    }
+
+    // XXX: Axum must not abort Ouroboros protocols in the middle, hence a separate Tokio task:
+    let proposals = tokio::spawn(async move {
+        let mut node = node.get().await?;
+
+        node.proposals(&pagination).await
+    })
+    .await
+    .expect("proposals panic!")?;
+
+    Ok(Json(proposals))
 }
@@ -1,7 +1,18 @@
-use bf_api_provider::types::DrepsMetadataResponse;
+use crate::proposals::{ProposalData, ProposalsPath};
+use crate::server::state::AppState;
+use axum::extract::{Path, State};
+use bf_api_provider::types::DrepsProposalMetadataResponse;
+use bf_common::types::ApiResult;
 
-use crate::{BlockfrostError, api::ApiResult};
+pub async fn route(
+    State(state): State<AppState>,
+    Path(proposals_path): Path<ProposalsPath>,
+) -> ApiResult<DrepsProposalMetadataResponse> {
+    let proposal = ProposalData::from_path(proposals_path)?;
+    let data_node = state.data_node()?;
 
-pub async fn route() -> ApiResult<DrepsMetadataResponse> {
-    Err(BlockfrostError::not_found())
+    data_node
+        .governance()
+        .proposal_metadata(&proposal.tx_hash, proposal.cert_index)
+        .await
 }
@@ -1,6 +1,18 @@
-use crate::{BlockfrostError, api::ApiResult};
+use crate::proposals::{ProposalData, ProposalsPath};
+use crate::server::state::AppState;
+use axum::extract::{Path, State};
 use bf_api_provider::types::DrepsSingleProposalResponse;
+use bf_common::types::ApiResult;
 
-pub async fn route() -> ApiResult<DrepsSingleProposalResponse> {
-    Err(BlockfrostError::not_found())
+pub async fn route(
+    State(state): State<AppState>,
+    Path(proposals_path): Path<ProposalsPath>,
+) -> ApiResult<DrepsSingleProposalResponse> {
+    let proposal = ProposalData::from_path(proposals_path)?;
+    let data_node = state.data_node()?;
+
+    data_node
+        .governance()
+        .proposal(&proposal.tx_hash, proposal.cert_index)
+        .await
 }
@@ -1,6 +1,23 @@
-use crate::{BlockfrostError, api::ApiResult};
+use crate::proposals::{ProposalData, ProposalsPath};
+use crate::server::state::AppState;
+use axum::extract::{Path, Query, State};
 use bf_api_provider::types::DrepsProposalWithdrawalsResponse;
+use bf_common::{
+    pagination::{Pagination, PaginationQuery},
+    types::ApiResult,
+};
 
-pub async fn route() -> ApiResult<DrepsProposalWithdrawalsResponse> {
-    Err(BlockfrostError::not_found())
+pub async fn route(
+    State(state): State<AppState>,
+    Path(proposals_path): Path<ProposalsPath>,
+    Query(pagination_query): Query<PaginationQuery>,
+) -> ApiResult<DrepsProposalWithdrawalsResponse> {
+    let proposal = ProposalData::from_path(proposals_path)?;
+    let pagination = Pagination::from_query(pagination_query)?;
+    let data_node = state.data_node()?;
+
+    data_node
+        .governance()
+        .proposal_withdrawals(&proposal.tx_hash, proposal.cert_index, &pagination)
+        .await
 }
@@ -40,7 +40,7 @@ mod tests {
             server_port: 3000,
             server_concurrency_limit: 2048,
             log_level: LogLevel::Info.into(),
-            node_socket_path: "/path/to/socket".to_string(),
+            node_socket_paths: vec!["/path/to/socket".to_string()],
             mode: Mode::Compact,
             icebreakers_config: None,
             max_pool_connections: 10,
@@ -51,11 +51,13 @@ mod tests {
             genesis: registry,
             data_node: None,
             hydra: None,
+            resubmit_on_rollback: false,
         };
 
         AppState {
             config: Arc::new(config),
             data_node: None,
+            error_decoder: None,
         }
     }
 
@@ -0,0 +1,4 @@
+pub mod addresses;
+pub mod hash;
+pub mod root;
+pub mod size;
@@ -0,0 +1,31 @@
+use crate::addresses::{AddressInfo, AddressesPath};
+use crate::{api::ApiResult, server::state::AppState};
+use axum::{
+    Extension, Json,
+    extract::{Path, Query, State},
+};
+use bf_common::pagination::{Pagination, PaginationQuery};
+use bf_node::{mempool::MempoolTxHash, pool::NodePool};
+
+pub async fn route(
+    State(state): State<AppState>,
+    Extension(node): Extension<NodePool>,
+    Path(address_path): Path<AddressesPath>,
+    Query(pagination_query): Query<PaginationQuery>,
+) -> ApiResult<Vec<MempoolTxHash>> {
+    let AddressesPath { address, asset: _ } = address_path;
+    let pagination = Pagination::from_query(pagination_query)?;
+    let address_info = AddressInfo::from_address(&address, state.config.network.clone())?;
+
+    // XXX: Axum must not abort Ouroboros protocols in the middle, hence a separate Tokio task:
+    let txs = tokio::spawn(async move {
+        let mut node = node.get().await?;
+
+        node.mempool_txs_by_address(&address_info.address, &pagination)
+            .await
+    })
+    .await
+    .expect("mempool_txs_by_address panic!")?;
+
+    Ok(Json(txs))
+}
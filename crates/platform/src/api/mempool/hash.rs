@@ -0,0 +1,20 @@
+use crate::{api::ApiResult, txs::TxsPath};
+use axum::{Extension, Json, extract::Path};
+use bf_node::pool::NodePool;
+use serde_json::Value;
+
+pub async fn route(
+    Extension(node): Extension<NodePool>,
+    Path(path): Path<TxsPath>,
+) -> ApiResult<Value> {
+    // XXX: Axum must not abort Ouroboros protocols in the middle, hence a separate Tokio task:
+    let tx = tokio::spawn(async move {
+        let mut node = node.get().await?;
+
+        node.mempool_tx(&path.hash).await
+    })
+    .await
+    .expect("mempool_tx panic!")?;
+
+    Ok(Json(tx))
+}
@@ -0,0 +1,22 @@
+use crate::api::ApiResult;
+use axum::{Extension, Json, extract::Query};
+use bf_common::pagination::{Pagination, PaginationQuery};
+use bf_node::{mempool::MempoolTxHash, pool::NodePool};
+
+pub async fn route(
+    Extension(node): Extension<NodePool>,
+    Query(pagination_query): Query<PaginationQuery>,
+) -> ApiResult<Vec<MempoolTxHash>> {
+    let pagination = Pagination::from_query(pagination_query)?;
+
+    // XXX: Axum must not abort Ouroboros protocols in the middle, hence a separate Tokio task:
+    let txs = tokio::spawn(async move {
+        let mut node = node.get().await?;
+
+        node.mempool_txs(&pagination).await
+    })
+    .await
+    .expect("mempool_txs panic!")?;
+
+    Ok(Json(txs))
+}
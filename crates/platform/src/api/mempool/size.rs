@@ -0,0 +1,16 @@
+use crate::api::ApiResult;
+use axum::{Extension, Json};
+use bf_node::{mempool::MempoolSize, pool::NodePool};
+
+pub async fn route(Extension(node): Extension<NodePool>) -> ApiResult<MempoolSize> {
+    // XXX: Axum must not abort Ouroboros protocols in the middle, hence a separate Tokio task:
+    let size = tokio::spawn(async move {
+        let mut node = node.get().await?;
+
+        node.mempool_size().await
+    })
+    .await
+    .expect("mempool_size panic!")?;
+
+    Ok(Json(size))
+}
@@ -0,0 +1,46 @@
+use crate::{ogmios::Session, server::state::AppState};
+use axum::{
+    Extension,
+    extract::{
+        State,
+        ws::{Message, WebSocket, WebSocketUpgrade},
+    },
+    response::IntoResponse,
+};
+use bf_node::{chain_sync::ChainFollower, pool::NodePool, tx_tracker::TxTracker};
+
+/// Speaks the Ogmios v6 JSON-RPC protocol, see [`crate::ogmios`]. Requests
+/// are answered one by one, in order.
+pub async fn route(
+    ws: WebSocketUpgrade,
+    State(state): State<AppState>,
+    Extension(node): Extension<NodePool>,
+    Extension(chain_follower): Extension<ChainFollower>,
+    Extension(tx_tracker): Extension<TxTracker>,
+) -> impl IntoResponse {
+    let session = Session::new(state, node, chain_follower, tx_tracker);
+
+    ws.on_upgrade(move |socket| serve(socket, session))
+}
+
+async fn serve(mut socket: WebSocket, mut session: Session) {
+    while let Some(message) = socket.recv().await {
+        let request = match message {
+            Ok(Message::Text(text)) => text.to_string(),
+            Ok(Message::Binary(bytes)) => String::from_utf8_lossy(&bytes).into_owned(),
+            Ok(Message::Close(_)) | Err(_) => break,
+            // Pings are answered by Axum:
+            Ok(_) => continue,
+        };
+
+        let response = session.handle(&request).await;
+
+        if socket
+            .send(Message::Text(response.to_string().into()))
+            .await
+            .is_err()
+        {
+            break;
+        }
+    }
+}
@@ -1,6 +1,32 @@
-use crate::{BlockfrostError, api::ApiResult};
+use crate::pools::{PoolData, PoolsPath};
+use crate::{api::ApiResult, health_monitor::HealthMonitor, server::state::AppState};
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+};
 use bf_api_provider::types::TxsPoolCertsInnerRelaysResponse;
+use bf_node::pool::NodePool;
 
-pub async fn route() -> ApiResult<TxsPoolCertsInnerRelaysResponse> {
-    Err(BlockfrostError::not_found())
+pub async fn route(
+    State(state): State<AppState>,
+    Extension(node): Extension<NodePool>,
+    Extension(health_monitor): Extension<HealthMonitor>,
+    Path(pools_path): Path<PoolsPath>,
+) -> ApiResult<TxsPoolCertsInnerRelaysResponse> {
+    let pool_data = PoolData::from_path(&pools_path.pool_id)?;
+
+    if let Some(data_node) = state.healthy_data_node(&health_monitor).await {
+        return data_node.pools().relays(&pool_data.pool_id).await;
+    }
+
+    // XXX: Axum must not abort Ouroboros protocols in the middle, hence a separate Tokio task:
+    let relays = tokio::spawn(async move {
+        let mut node = node.get().await?;
+
+        node.pool_relays(&pool_data.pool_id).await
+    })
+    .await
+    .expect("pool_relays panic!")?;
+
+    Ok(Json(relays))
 }
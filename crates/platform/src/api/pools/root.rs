@@ -1,5 +1,31 @@
-use crate::{BlockfrostError, api::ApiResult};
+use crate::{api::ApiResult, health_monitor::HealthMonitor, server::state::AppState};
+use axum::{
+    Extension, Json,
+    extract::{Query, State},
+};
+use bf_common::pagination::{Pagination, PaginationQuery};
+use bf_node::pool::NodePool;
 
-pub async fn route() -> ApiResult<Vec<String>> {
-    Err(BlockfrostError::not_found())
+pub async fn route(
+    State(state): State<AppState>,
+    Extension(node): Extension<NodePool>,
+    Extension(health_monitor): Extension<HealthMonitor>,
+    Query(pagination_query): Query<PaginationQuery>,
+) -> ApiResult<Vec<String>> {
+    let pagination = Pagination::from_query(pagination_query)?;
+
+    if let Some(data_node) = state.healthy_data_node(&health_monitor).await {
+        return data_node.pools().list(&pagination).await;
+    }
+
+    // XXX: Axum must not abort Ouroboros protocols in the middle, hence a separate Tokio task:
+    let pools = tokio::spawn(async move {
+        let mut node = node.get().await?;
+
+        node.pools(&pagination).await
+    })
+    .await
+    .expect("pools panic!")?;
+
+    Ok(Json(pools))
 }
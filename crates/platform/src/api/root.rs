@@ -1,5 +1,6 @@
-use crate::health_monitor::HealthMonitor;
-use axum::{Extension, Json, http::StatusCode, response::IntoResponse};
+use crate::{health_monitor::HealthMonitor, server::state::AppState};
+use axum::{Extension, Json, extract::State, http::StatusCode, response::IntoResponse};
+use bf_common::route_groups::RouteGroup;
 use bf_data_node::api::root::DataNodeRootResponse;
 use bf_node::sync_progress::NodeInfo;
 use serde::{Deserialize, Serialize};
@@ -13,10 +14,16 @@ pub struct RootResponse {
     pub node_info: Option<NodeInfo>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub data_node: Option<DataNodeRootResponse>,
+    /// Route groups served in the configured mode.
+    #[serde(default)]
+    pub capabilities: Vec<RouteGroup>,
     pub errors: Vec<String>,
 }
 
-pub async fn route(Extension(health_monitor): Extension<HealthMonitor>) -> impl IntoResponse {
+pub async fn route(
+    State(state): State<AppState>,
+    Extension(health_monitor): Extension<HealthMonitor>,
+) -> impl IntoResponse {
     let status = health_monitor.current_status().await;
 
     let http_status = if status.healthy {
@@ -32,6 +39,7 @@ pub async fn route(Extension(health_monitor): Extension<HealthMonitor>) -> impl
         node_info: status.node_info,
         data_node: status.data_node_info,
         healthy: status.healthy,
+        capabilities: state.config.route_groups(),
         errors: status.errors.into_iter().map(|e| e.to_string()).collect(),
     };
 
@@ -1,6 +1,18 @@
-use crate::{BlockfrostError, api::ApiResult};
+use crate::{api::ApiResult, server::state::AppState};
+use axum::extract::{Path, Query, State};
 use bf_api_provider::types::ScriptsRedeemersInnerResponse;
+use bf_common::pagination::{Pagination, PaginationQuery};
 
-pub async fn route() -> ApiResult<ScriptsRedeemersInnerResponse> {
-    Err(BlockfrostError::not_found())
+pub async fn route(
+    State(state): State<AppState>,
+    Path(script_hash): Path<String>,
+    Query(pagination_query): Query<PaginationQuery>,
+) -> ApiResult<ScriptsRedeemersInnerResponse> {
+    let pagination = Pagination::from_query(pagination_query)?;
+    let data_node = state.data_node()?;
+
+    data_node
+        .scripts()
+        .redeemers(&script_hash, &pagination)
+        .await
 }
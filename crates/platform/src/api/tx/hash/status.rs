@@ -0,0 +1,17 @@
+use crate::{api::ApiResult, txs::TxsPath};
+use axum::{Extension, Json, extract::Path};
+use bf_common::errors::BlockfrostError;
+use bf_node::tx_tracker::{TxStatusResponse, TxTracker};
+
+/// Status of a transaction submitted through this instance. Others are not
+/// known here, so they are reported as not found.
+pub async fn route(
+    Extension(tx_tracker): Extension<TxTracker>,
+    Path(path): Path<TxsPath>,
+) -> ApiResult<TxStatusResponse> {
+    tx_tracker
+        .status(&path.hash)
+        .await
+        .map(Json)
+        .ok_or_else(BlockfrostError::not_found)
+}
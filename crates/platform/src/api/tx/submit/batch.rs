@@ -0,0 +1,200 @@
+use super::root::{binary_or_hex_heuristic, decode_rejection};
+use crate::{api::ApiResult, server::state::AppState, validation::validate_content_type};
+use axum::{
+    Extension, Json,
+    extract::{Query, State},
+    http::{HeaderMap, header::CONTENT_TYPE},
+};
+use bf_common::errors::BlockfrostError;
+use bf_node::{pool::NodePool, transactions::TxSubmitError, tx_tracker::TxTracker};
+use metrics::counter;
+use pallas_codec::minicbor::{self, Decoder, data::Type};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// More than that should rather be split into several requests.
+const MAX_BATCH_SIZE: usize = 64;
+
+#[derive(Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchMode {
+    /// Nothing after the first failure is submitted, which suits chains of
+    /// dependent transactions.
+    #[default]
+    StopOnFailure,
+    Continue,
+}
+
+#[derive(Deserialize)]
+pub struct BatchQuery {
+    #[serde(default)]
+    pub mode: BatchMode,
+}
+
+/// The result of a single transaction of the batch, in the same order.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum BatchTxResult {
+    Accepted {
+        tx_hash: String,
+    },
+    /// Rejected by the ledger, or by our local checks, in the
+    /// cardano-submit-api shape where possible.
+    Rejected {
+        error: Value,
+    },
+    /// The node couldn’t be asked.
+    Failed {
+        error: String,
+    },
+    /// Not attempted, because an earlier transaction failed.
+    Skipped,
+}
+
+/// Submits several transactions in order, on a single node connection. The
+/// body is either a CBOR array of transactions (`application/cbor`), or a JSON
+/// array of hex-encoded ones (`application/json`).
+pub async fn route(
+    State(state): State<AppState>,
+    Extension(node): Extension<NodePool>,
+    Extension(tx_tracker): Extension<TxTracker>,
+    Query(query): Query<BatchQuery>,
+    headers: HeaderMap,
+    body: axum::body::Bytes,
+) -> ApiResult<Vec<BatchTxResult>> {
+    validate_content_type(&headers, &["application/cbor", "application/json"])?;
+
+    let txs = if headers
+        .get(CONTENT_TYPE)
+        .is_some_and(|content_type| content_type == "application/json")
+    {
+        decode_json_batch(&body)?
+    } else {
+        decode_cbor_batch(&binary_or_hex_heuristic(&body)).map_err(|e| {
+            BlockfrostError::custom_400(format!("Invalid CBOR array of transactions: {e}"))
+        })?
+    };
+
+    if txs.is_empty() || txs.len() > MAX_BATCH_SIZE {
+        return Err(BlockfrostError::custom_400(format!(
+            "A batch must contain between 1 and {MAX_BATCH_SIZE} transactions"
+        )));
+    }
+
+    let count = txs.len();
+    let tracked_txs = txs.clone();
+
+    // XXX: Axum must not abort Ouroboros protocols in the middle, hence a separate Tokio task:
+    let results = tokio::spawn(async move {
+        let mut node = node.get().await?;
+
+        node.submit_transactions(txs, query.mode == BatchMode::StopOnFailure)
+            .await
+    })
+    .await
+    .expect("submit_transactions panic!")?;
+
+    let mut response = Vec::with_capacity(count);
+
+    for (result, tx) in results.into_iter().zip(tracked_txs) {
+        if result.is_ok() {
+            counter!("tx_submit_success").increment(1)
+        } else {
+            counter!("tx_submit_failure").increment(1)
+        }
+
+        response.push(match result {
+            Ok(tx_hash) => {
+                if let Err(err) = tx_tracker.track(tx).await {
+                    tracing::warn!("Failed to track transaction {tx_hash}: {}", err.message);
+                }
+
+                BatchTxResult::Accepted { tx_hash }
+            },
+            Err(TxSubmitError::Rejected { display, cbor }) => BatchTxResult::Rejected {
+                error: decode_rejection(&state, &cbor)
+                    .await
+                    .unwrap_or(Value::String(display)),
+            },
+            Err(TxSubmitError::PhaseOne(json)) => BatchTxResult::Rejected { error: json },
+            Err(TxSubmitError::Other(err)) => BatchTxResult::Rejected {
+                error: Value::String(err.message),
+            },
+            Err(TxSubmitError::Connection(err) | TxSubmitError::Interrupted(err)) => {
+                BatchTxResult::Failed { error: err.message }
+            },
+        });
+    }
+
+    response.resize(count, BatchTxResult::Skipped);
+
+    Ok(Json(response))
+}
+
+fn decode_json_batch(body: &[u8]) -> Result<Vec<Vec<u8>>, BlockfrostError> {
+    let txs: Vec<String> = serde_json::from_slice(body).map_err(|e| {
+        BlockfrostError::custom_400(format!("Expected a JSON array of hex strings: {e}"))
+    })?;
+
+    txs.iter()
+        .enumerate()
+        .map(|(index, tx)| {
+            hex::decode(tx).map_err(|e| {
+                BlockfrostError::custom_400(format!("Transaction {index} is not valid hex: {e}"))
+            })
+        })
+        .collect()
+}
+
+/// Transactions can be embedded in the array as they are, or wrapped in byte
+/// strings.
+fn decode_cbor_batch(body: &[u8]) -> Result<Vec<Vec<u8>>, minicbor::decode::Error> {
+    let mut d = Decoder::new(body);
+    let len = d.array()?;
+    let mut txs = Vec::new();
+
+    loop {
+        match len {
+            Some(len) if txs.len() as u64 == len => break,
+            None if d.datatype()? == Type::Break => break,
+            _ => {},
+        }
+
+        if d.datatype()? == Type::Bytes {
+            txs.push(d.bytes()?.to_vec());
+        } else {
+            let start = d.position();
+            d.skip()?;
+            txs.push(body[start..d.position()].to_vec());
+        }
+    }
+
+    Ok(txs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::embedded("82820102820304", vec!["820102", "820304"])]
+    #[case::wrapped("824382010243820304", vec!["820102", "820304"])]
+    #[case::indefinite("9f820102ff", vec!["820102"])]
+    fn test_decode_cbor_batch(#[case] body: &str, #[case] expected: Vec<&str>) {
+        let txs = decode_cbor_batch(&hex::decode(body).unwrap()).unwrap();
+
+        assert_eq!(txs.iter().map(hex::encode).collect::<Vec<_>>(), expected);
+    }
+
+    #[test]
+    fn test_decode_json_batch() {
+        assert_eq!(
+            decode_json_batch(br#"["8201", "02"]"#).unwrap(),
+            vec![vec![0x82, 0x01], vec![0x02]]
+        );
+        assert!(decode_json_batch(br#"["zz"]"#).is_err());
+        assert!(decode_json_batch(br#"{"tx": "00"}"#).is_err());
+    }
+}
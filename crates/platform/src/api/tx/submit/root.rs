@@ -0,0 +1,252 @@
+use crate::{server::state::AppState, validation::validate_content_type};
+use axum::{
+    Extension, Json,
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+};
+use bf_common::errors::BlockfrostError;
+use bf_node::{pool::NodePool, transactions::TxSubmitError, tx_tracker::TxTracker};
+use metrics::counter;
+use serde_json::Value;
+
+pub async fn route(
+    State(state): State<AppState>,
+    Extension(node): Extension<NodePool>,
+    Extension(tx_tracker): Extension<TxTracker>,
+    headers: HeaderMap,
+    body: axum::body::Bytes,
+) -> Result<Response, BlockfrostError> {
+    // Allow only application/cbor content type
+    validate_content_type(&headers, &["application/cbor"])?;
+
+    // Allow both hex-encoded and raw binary bodies
+    let binary_tx = binary_or_hex_heuristic(body.as_ref());
+    let tracked_tx = binary_tx.clone();
+
+    // XXX: Axum must not abort Ouroboros protocols in the middle, hence a separate Tokio task:
+    let response = tokio::spawn(async move {
+        // Submit transaction, on another node, if the first one can’t be reached
+        let response = node.submit_transaction(binary_tx).await;
+
+        if response.is_ok() {
+            counter!("tx_submit_success").increment(1)
+        } else {
+            counter!("tx_submit_failure").increment(1)
+        }
+
+        response
+    })
+    .await
+    .expect("submit_transaction panic!");
+
+    let response_body = match response {
+        Ok(txid) => {
+            if let Err(err) = tx_tracker.track(tracked_tx).await {
+                tracing::warn!("Failed to track transaction {txid}: {}", err.message);
+            }
+
+            txid
+        },
+        Err(TxSubmitError::Rejected { display, cbor }) => {
+            return Ok(rejection_response(&state, display, &cbor).await);
+        },
+        Err(TxSubmitError::PhaseOne(json)) => {
+            return Ok((StatusCode::BAD_REQUEST, Json(json)).into_response());
+        },
+        Err(
+            TxSubmitError::Connection(err)
+            | TxSubmitError::Interrupted(err)
+            | TxSubmitError::Other(err),
+        ) => return Err(err),
+    };
+
+    let mut response_headers = HeaderMap::new();
+
+    response_headers.insert(
+        "blockfrost-platform-response",
+        response_body.to_string().parse()?,
+    );
+
+    Ok((response_headers, Json(response_body)).into_response())
+}
+
+/// Returns the same JSON as cardano-submit-api would, or our own display of
+/// the error, if the external decoder is not available.
+async fn rejection_response(state: &AppState, display: String, cbor: &[u8]) -> Response {
+    match decode_rejection(state, cbor).await {
+        Some(json) => (StatusCode::BAD_REQUEST, Json(json)).into_response(),
+        None => BlockfrostError::custom_400(display).into_response(),
+    }
+}
+
+/// Decodes the CBOR of a node rejection into the cardano-submit-api JSON, if
+/// the external decoder is available.
+pub(crate) async fn decode_rejection(state: &AppState, cbor: &[u8]) -> Option<Value> {
+    let error_decoder = state.error_decoder.as_ref()?;
+
+    match error_decoder.decode(cbor).await {
+        Ok(json) => Some(json),
+        Err(err) => {
+            tracing::warn!("Failed to decode transaction rejection: {err}");
+            None
+        },
+    }
+}
+
+/// This function allows us to take both hex-encoded and raw bytes. It has
+/// to be a heuristic: if there are input bytes that are not `[0-9a-f]`,
+/// then it must be a binary string. Otherwise, we assume it’s hex encoded.
+///
+/// **Note**: there is a small probability that the user gave us a binary
+/// string that only _looked_ like a hex-encoded one, but it’s rare enough
+/// to ignore it.
+pub fn binary_or_hex_heuristic(xs: &[u8]) -> Vec<u8> {
+    let even_length = xs.len().is_multiple_of(2);
+
+    if !even_length || xs.iter().any(|&x| !x.is_ascii_hexdigit()) {
+        xs.to_vec()
+    } else {
+        hex::decode(xs).unwrap_or_else(|_| unreachable!())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{Config, Mode};
+    use bf_common::types::{LogLevel, Network};
+    use bf_error_decoder::external::ExternalDecoder;
+    use pretty_assertions::assert_eq;
+    use proptest::prelude::*;
+    use serde_json::json;
+    use std::sync::Arc;
+
+    /// `ApplyTxErr` with a Conway `WithdrawalsNotInRewardsCERTS`, as sent by the node.
+    const REJECTION: &str = "8182068182028200a0";
+
+    fn app_state(error_decoder: Option<ExternalDecoder>) -> AppState {
+        let config = Config {
+            server_address: "0.0.0.0".parse().unwrap(),
+            server_port: 3000,
+            server_concurrency_limit: 2048,
+            log_level: LogLevel::Info.into(),
+            node_socket_paths: vec!["/path/to/socket".to_string()],
+            mode: Mode::Compact,
+            icebreakers_config: None,
+            max_pool_connections: 10,
+            max_response_body_bytes: bf_common::DEFAULT_MAX_BODY_BYTES,
+            no_metrics: false,
+            network: Network::Preview,
+            custom_genesis_config: None,
+            genesis: crate::genesis::genesis(),
+            data_node: None,
+            hydra: None,
+            resubmit_on_rollback: false,
+        };
+
+        AppState {
+            config: Arc::new(config),
+            data_node: None,
+            error_decoder,
+        }
+    }
+
+    async fn body_json(response: Response) -> Value {
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_decode_rejection() {
+        let state = app_state(Some(ExternalDecoder::spawn().unwrap()));
+
+        let json = decode_rejection(&state, &hex::decode(REJECTION).unwrap()).await;
+
+        assert_eq!(
+            json,
+            Some(json!({
+                "contents": {
+                    "contents": {
+                        "contents": {
+                            "era": "ShelleyBasedEraConway",
+                            "error": [
+                                "ConwayCertsFailure (WithdrawalsNotInRewardsCERTS (Withdrawals {unWithdrawals = fromList []}))"
+                            ],
+                            "kind": "ShelleyTxValidationError"
+                        },
+                        "tag": "TxValidationErrorInCardanoMode"
+                    },
+                    "tag": "TxCmdTxSubmitValidationError"
+                },
+                "tag": "TxSubmitFail"
+            }))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_decode_rejection_without_decoder() {
+        let state = app_state(None);
+
+        assert_eq!(
+            decode_rejection(&state, &hex::decode(REJECTION).unwrap()).await,
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rejection_response() {
+        let state = app_state(Some(ExternalDecoder::spawn().unwrap()));
+
+        let response = rejection_response(
+            &state,
+            "display".to_string(),
+            &hex::decode(REJECTION).unwrap(),
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(body_json(response).await["tag"], "TxSubmitFail");
+    }
+
+    #[tokio::test]
+    async fn test_rejection_response_falls_back_to_display() {
+        let state = app_state(None);
+
+        let response = rejection_response(
+            &state,
+            "display".to_string(),
+            &hex::decode(REJECTION).unwrap(),
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(
+            body_json(response).await,
+            json!({
+                "error": "Bad Request",
+                "message": "display",
+                "status_code": 400,
+            })
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn proptest_binary_or_hex_heuristic(
+            binary in prop::collection::vec(any::<u8>(), 0..=128)
+                .prop_filter("exclude values made up only of hex digits", |xs| {
+                    xs.iter().any(|&x| !x.is_ascii_hexdigit())
+                })
+        ) {
+            let hex_string = hex::encode(&binary);
+            assert_eq!(
+                binary_or_hex_heuristic(hex_string.as_bytes()),
+                binary_or_hex_heuristic(&binary)
+            )
+        }
+    }
+}
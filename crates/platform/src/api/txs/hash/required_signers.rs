@@ -1,6 +1,24 @@
-use crate::{BlockfrostError, api::ApiResult};
+use crate::txs::{TxsPath, required_signers_from_cbor};
+use crate::{api::ApiResult, server::state::AppState};
+use axum::{
+    Json,
+    extract::{Path, State},
+};
 use bf_api_provider::types::TxsContentRequiredSignersInner;
 
-pub async fn route() -> ApiResult<TxsContentRequiredSignersInner> {
-    Err(BlockfrostError::not_found())
+pub async fn route(
+    State(state): State<AppState>,
+    Path(path): Path<TxsPath>,
+) -> ApiResult<TxsContentRequiredSignersInner> {
+    let data_node = state.data_node()?;
+
+    match data_node.txs().required_signers(&path.hash).await {
+        // Older data nodes don’t have this route, but we can still read the signers off the CBOR:
+        Err(e) if e.status_code == 404 => {
+            let Json(tx) = data_node.txs().cbor(&path.hash).await?;
+
+            Ok(Json(required_signers_from_cbor(&tx.cbor)?))
+        },
+        other => other,
+    }
 }
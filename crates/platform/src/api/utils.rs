@@ -1 +1,3 @@
+pub mod slot;
+pub mod time;
 pub mod txs;
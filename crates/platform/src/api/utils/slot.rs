@@ -0,0 +1 @@
+pub mod slot_number;
@@ -0,0 +1,25 @@
+use crate::api::ApiResult;
+use axum::{Extension, Json, extract::Path};
+use bf_common::errors::BlockfrostError;
+use bf_node::{era_history::SlotTime, pool::NodePool};
+
+/// UNIX time and epoch of a slot, according to the node’s era history.
+pub async fn route(
+    Extension(node): Extension<NodePool>,
+    Path(slot_number): Path<String>,
+) -> ApiResult<SlotTime> {
+    let slot = slot_number.parse::<u64>().map_err(|_| {
+        BlockfrostError::custom_400("Missing, out of range or malformed slot number.".to_string())
+    })?;
+
+    // XXX: Axum must not abort Ouroboros protocols in the middle, hence a separate Tokio task:
+    let slot_time = tokio::spawn(async move {
+        let mut node = node.get().await?;
+
+        node.slot_to_time(slot).await
+    })
+    .await
+    .expect("slot_to_time panic!")?;
+
+    Ok(Json(slot_time))
+}
@@ -0,0 +1,26 @@
+use crate::api::ApiResult;
+use axum::{Extension, Json, extract::Path};
+use bf_common::errors::BlockfrostError;
+use bf_node::{era_history::SlotTime, pool::NodePool};
+
+/// The slot in progress at a UNIX time (in seconds), according to the node’s
+/// era history.
+pub async fn route(
+    Extension(node): Extension<NodePool>,
+    Path(unix_time): Path<String>,
+) -> ApiResult<SlotTime> {
+    let unix_time = unix_time.parse::<i64>().map_err(|_| {
+        BlockfrostError::custom_400("Missing, out of range or malformed UNIX time.".to_string())
+    })?;
+
+    // XXX: Axum must not abort Ouroboros protocols in the middle, hence a separate Tokio task:
+    let slot_time = tokio::spawn(async move {
+        let mut node = node.get().await?;
+
+        node.time_to_slot(unix_time).await
+    })
+    .await
+    .expect("time_to_slot panic!")?;
+
+    Ok(Json(slot_time))
+}
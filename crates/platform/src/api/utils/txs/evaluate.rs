@@ -1,2 +1,213 @@
 pub mod root;
 pub mod utxos;
+
+use bf_node::evaluation::{EvaluationFailure, EvaluationResult};
+use serde::Deserialize;
+use serde_json::{Map, Value, json};
+
+/// Blockfrost responds in the Ogmios v5 format by default, and in the Ogmios
+/// v6 format with `?version=6`.
+#[derive(Deserialize, Debug, Default)]
+pub struct EvaluateQuery {
+    pub version: Option<u8>,
+}
+
+pub fn to_ogmios_response(result: EvaluationResult, query: &EvaluateQuery) -> Value {
+    match query.version {
+        Some(6) => to_ogmios_v6(result),
+        _ => to_ogmios_v5(result),
+    }
+}
+
+fn to_ogmios_v5(result: EvaluationResult) -> Value {
+    let result = match result {
+        EvaluationResult::Success(redeemers) => {
+            let units: Map<String, Value> = redeemers
+                .into_iter()
+                .map(|r| {
+                    (
+                        format!("{}:{}", r.purpose.as_ogmios_v5(), r.index),
+                        json!({ "memory": r.memory, "steps": r.steps }),
+                    )
+                })
+                .collect();
+
+            json!({ "EvaluationResult": units })
+        },
+        EvaluationResult::Failure(EvaluationFailure::CannotCreateEvaluationContext(reason)) => {
+            json!({
+                "EvaluationFailure": { "CannotCreateEvaluationContext": { "reason": reason } }
+            })
+        },
+        EvaluationResult::Failure(EvaluationFailure::ScriptFailure {
+            purpose,
+            index,
+            error,
+            traces,
+        }) => {
+            let mut failures = Map::new();
+            failures.insert(
+                format!("{}:{}", purpose.as_ogmios_v5(), index),
+                json!([{ "validatorFailed": { "error": error, "traces": traces } }]),
+            );
+
+            json!({ "EvaluationFailure": { "ScriptFailures": failures } })
+        },
+    };
+
+    json!({
+        "type": "jsonwsp/response",
+        "version": "1.0",
+        "servicename": "ogmios",
+        "methodname": "EvaluateTx",
+        "result": result,
+        "reflection": null,
+    })
+}
+
+fn to_ogmios_v6(result: EvaluationResult) -> Value {
+    match result {
+        EvaluationResult::Success(redeemers) => {
+            let units: Vec<Value> = redeemers
+                .into_iter()
+                .map(|r| {
+                    json!({
+                        "validator": { "purpose": r.purpose.as_ogmios_v6(), "index": r.index },
+                        "budget": { "memory": r.memory, "cpu": r.steps },
+                    })
+                })
+                .collect();
+
+            json!({ "jsonrpc": "2.0", "method": "evaluateTransaction", "result": units })
+        },
+        EvaluationResult::Failure(EvaluationFailure::CannotCreateEvaluationContext(reason)) => {
+            json!({
+                "jsonrpc": "2.0",
+                "method": "evaluateTransaction",
+                "error": {
+                    "code": 3004,
+                    "message": "Unable to create the evaluation context from the given transaction.",
+                    "data": { "reason": reason },
+                },
+            })
+        },
+        EvaluationResult::Failure(EvaluationFailure::ScriptFailure {
+            purpose,
+            index,
+            error,
+            traces,
+        }) => json!({
+            "jsonrpc": "2.0",
+            "method": "evaluateTransaction",
+            "error": {
+                "code": 3010,
+                "message": "Some scripts of the transactions terminated with error(s).",
+                "data": [{
+                    "validator": { "purpose": purpose.as_ogmios_v6(), "index": index },
+                    "error": {
+                        "code": 3012,
+                        "message": "Some of the scripts failed to evaluate to a positive outcome.",
+                        "data": { "validationError": error, "traces": traces },
+                    },
+                }],
+            },
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bf_node::evaluation::{RedeemerEvaluation, RedeemerPurpose};
+    use pretty_assertions::assert_eq;
+
+    fn sample() -> EvaluationResult {
+        EvaluationResult::Success(vec![RedeemerEvaluation {
+            purpose: RedeemerPurpose::Withdrawal,
+            index: 1,
+            memory: 1700,
+            steps: 476468,
+        }])
+    }
+
+    #[test]
+    fn test_ogmios_v5_shape() {
+        let response = to_ogmios_response(sample(), &EvaluateQuery::default());
+
+        assert_eq!(
+            response["result"]["EvaluationResult"]["withdrawal:1"],
+            json!({ "memory": 1700, "steps": 476468 })
+        );
+    }
+
+    fn script_failure() -> EvaluationResult {
+        EvaluationResult::Failure(EvaluationFailure::ScriptFailure {
+            purpose: RedeemerPurpose::Spend,
+            index: 0,
+            error: "the validator crashed / exited prematurely".to_string(),
+            traces: vec!["not signed".to_string()],
+        })
+    }
+
+    #[test]
+    fn test_ogmios_v5_script_failures() {
+        let response = to_ogmios_response(script_failure(), &EvaluateQuery::default());
+
+        assert_eq!(
+            response["result"],
+            json!({
+                "EvaluationFailure": {
+                    "ScriptFailures": {
+                        "spend:0": [{
+                            "validatorFailed": {
+                                "error": "the validator crashed / exited prematurely",
+                                "traces": ["not signed"],
+                            }
+                        }]
+                    }
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn test_ogmios_v5_cannot_create_evaluation_context() {
+        let failure = EvaluationResult::Failure(EvaluationFailure::CannotCreateEvaluationContext(
+            "resolved Input not found".to_string(),
+        ));
+        let response = to_ogmios_response(failure, &EvaluateQuery::default());
+
+        assert_eq!(
+            response["result"],
+            json!({
+                "EvaluationFailure": {
+                    "CannotCreateEvaluationContext": { "reason": "resolved Input not found" }
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn test_ogmios_v6_script_failures() {
+        let response = to_ogmios_response(script_failure(), &EvaluateQuery { version: Some(6) });
+
+        assert_eq!(response["error"]["code"], 3010);
+        assert_eq!(
+            response["error"]["data"][0]["validator"],
+            json!({ "purpose": "spend", "index": 0 })
+        );
+    }
+
+    #[test]
+    fn test_ogmios_v6_shape() {
+        let response = to_ogmios_response(sample(), &EvaluateQuery { version: Some(6) });
+
+        assert_eq!(
+            response["result"][0],
+            json!({
+                "validator": { "purpose": "withdraw", "index": 1 },
+                "budget": { "memory": 1700, "cpu": 476468 },
+            })
+        );
+    }
+}
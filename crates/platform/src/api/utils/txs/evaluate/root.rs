@@ -1,6 +1,30 @@
-use crate::{BlockfrostError, api::ApiResult};
+use super::{EvaluateQuery, to_ogmios_response};
+use crate::{
+    api::ApiResult, api::tx::submit::root::binary_or_hex_heuristic,
+    validation::validate_content_type,
+};
+use axum::{Extension, Json, extract::Query, http::HeaderMap};
+use bf_node::pool::NodePool;
 use serde_json::Value;
 
-pub async fn route() -> ApiResult<Value> {
-    Err(BlockfrostError::not_found())
+pub async fn route(
+    Extension(node): Extension<NodePool>,
+    Query(query): Query<EvaluateQuery>,
+    headers: HeaderMap,
+    body: axum::body::Bytes,
+) -> ApiResult<Value> {
+    validate_content_type(&headers, &["application/cbor"])?;
+
+    let binary_tx = binary_or_hex_heuristic(body.as_ref());
+
+    // XXX: Axum must not abort Ouroboros protocols in the middle, hence a separate Tokio task:
+    let result = tokio::spawn(async move {
+        let mut node = node.get().await?;
+
+        node.evaluate_transaction(binary_tx, Vec::new()).await
+    })
+    .await
+    .expect("evaluate_transaction panic!")?;
+
+    Ok(Json(to_ogmios_response(result, &query)))
 }
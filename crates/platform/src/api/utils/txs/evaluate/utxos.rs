@@ -1,6 +1,41 @@
-use crate::{BlockfrostError, api::ApiResult};
+use super::{EvaluateQuery, to_ogmios_response};
+use crate::{BlockfrostError, api::ApiResult, validation::validate_content_type};
+use axum::{Extension, Json, extract::Query, http::HeaderMap};
+use bf_node::{evaluation::AdditionalUtxo, pool::NodePool};
+use serde::Deserialize;
 use serde_json::Value;
 
-pub async fn route() -> ApiResult<Value> {
-    Err(BlockfrostError::not_found())
+#[derive(Deserialize, Debug)]
+pub struct EvaluateUtxosBody {
+    /// Hex-encoded transaction CBOR.
+    pub cbor: String,
+    #[serde(rename = "additionalUtxoSet", default)]
+    pub additional_utxo_set: Vec<AdditionalUtxo>,
+}
+
+pub async fn route(
+    Extension(node): Extension<NodePool>,
+    Query(query): Query<EvaluateQuery>,
+    headers: HeaderMap,
+    body: axum::body::Bytes,
+) -> ApiResult<Value> {
+    validate_content_type(&headers, &["application/json"])?;
+
+    let body: EvaluateUtxosBody = serde_json::from_slice(&body)
+        .map_err(|e| BlockfrostError::custom_400(format!("Invalid request body: {e}")))?;
+
+    let binary_tx = hex::decode(&body.cbor)
+        .map_err(|_| BlockfrostError::custom_400("Invalid hex in cbor".to_string()))?;
+
+    // XXX: Axum must not abort Ouroboros protocols in the middle, hence a separate Tokio task:
+    let result = tokio::spawn(async move {
+        let mut node = node.get().await?;
+
+        node.evaluate_transaction(binary_tx, body.additional_utxo_set)
+            .await
+    })
+    .await
+    .expect("evaluate_transaction panic!")?;
+
+    Ok(Json(to_ogmios_response(result, &query)))
 }
@@ -12,6 +12,25 @@ pub struct AssetsPath {
     pub asset: String,
 }
 
+pub struct PolicyData {
+    pub policy_id: String,
+}
+
+#[derive(Deserialize)]
+pub struct PolicyPath {
+    pub policy_id: String,
+}
+
+impl PolicyData {
+    pub fn from_path(policy_id: String) -> Result<Self, BlockfrostError> {
+        if !validate_policy_id(&policy_id) {
+            return Err(BlockfrostError::invalid_policy_id());
+        }
+
+        Ok(PolicyData { policy_id })
+    }
+}
+
 impl AssetData {
     pub fn from_query(asset: String) -> Result<Self, BlockfrostError> {
         let is_valid = validate_asset_name(&asset);
@@ -41,6 +60,10 @@ pub fn validate_asset_name(asset_name: &str) -> bool {
     false
 }
 
+pub fn validate_policy_id(policy_id: &str) -> bool {
+    policy_id.len() == POLICY_ID_SIZE && hex::decode(policy_id).is_ok()
+}
+
 pub fn parse_asset(hex: &str) -> Result<ParsedAsset, BlockfrostError> {
     if hex.len() < POLICY_ID_SIZE {
         return Err(BlockfrostError::internal_server_error(format!(
@@ -111,4 +134,39 @@ mod tests {
             description
         );
     }
+
+    #[rstest]
+    #[case(
+        "Valid policy",
+        "00000002df633853f6a47465c9496721d2d5b1291b8398016c0e87ae",
+        true
+    )]
+    #[case(
+        "Invalid policy ( < length)",
+        "00000002df633853f6a47465c9496721d2d5b1291b8398016c0e87a",
+        false
+    )]
+    #[case(
+        "Invalid policy ( > length)",
+        "00000002df633853f6a47465c9496721d2d5b1291b8398016c0e87ae6e",
+        false
+    )]
+    #[case(
+        "Invalid policy (hex)",
+        "00000002df633853f6a47465c9496721d2d5b1291b8398016c0e87ag",
+        false
+    )]
+    #[case("Invalid policy (lovelace)", "lovelace", false)]
+    fn test_validate_policy_id(
+        #[case] description: &str,
+        #[case] input: &str,
+        #[case] expected: bool,
+    ) {
+        assert_eq!(
+            crate::assets::validate_policy_id(input),
+            expected,
+            "{}",
+            description
+        );
+    }
 }
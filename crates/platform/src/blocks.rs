@@ -13,6 +13,12 @@ pub struct BlocksSlotPath {
     pub slot_number: String,
 }
 
+#[derive(Deserialize)]
+pub struct BlocksEpochSlotPath {
+    pub epoch_number: String,
+    pub slot_number: String,
+}
+
 #[derive(Debug, PartialEq)]
 pub struct BlockData {
     pub hash_or_number: String,
@@ -1,10 +1,10 @@
 use crate::config::{Config, Mode};
 use anyhow::{Error, Result, anyhow};
 use bf_common::{errors::AppError, types::LogLevel};
-use clap::{CommandFactory, Parser, ValueEnum};
+use clap::{ArgAction, CommandFactory, Parser, ValueEnum};
 use inquire::validator::{ErrorMessage, Validation};
 use inquire::{Confirm, Select, Text};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 use std::fs;
 use std::io::Write;
 use std::net::IpAddr;
@@ -18,6 +18,29 @@ fn should_skip_serializng_fields<T>(_: &T) -> bool {
     SHOULD_SKIP_SERIALIZNG_FIELDS.load(Ordering::SeqCst)
 }
 
+/// Config files written before `--node-socket-path` could be repeated have a
+/// single string there. Also, the env and clap layers of `twelf` pass lists on
+/// as comma-separated strings.
+fn one_or_many<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: From<Vec<String>>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(String),
+        Many(Vec<String>),
+    }
+
+    let values = match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::One(value) => value.split(',').map(String::from).collect(),
+        OneOrMany::Many(values) => values,
+    };
+
+    Ok(T::from(values))
+}
+
 #[derive(Parser, Debug, Clone, Serialize, Deserialize, Default)]
 pub struct DataNodeArgs {
     pub endpoint: Option<String>,
@@ -48,8 +71,10 @@ pub struct Args {
     #[arg(long, default_value = "info")]
     pub log_level: LogLevel,
 
-    #[arg(long)]
-    pub node_socket_path: Option<String>,
+    /// Path to the Cardano node socket. Repeat it to fail over between nodes.
+    #[arg(long, action = ArgAction::Append)]
+    #[serde(default, deserialize_with = "one_or_many")]
+    pub node_socket_path: Vec<String>,
 
     #[arg(long, default_value = "compact")]
     pub mode: Mode,
@@ -90,6 +115,11 @@ pub struct Args {
     #[arg(long)]
     pub gateway_url: Option<String>,
 
+    /// Submit transactions again when their block gets rolled back, if they were
+    /// submitted through this instance.
+    #[arg(long)]
+    pub resubmit_on_rollback: bool,
+
     /// A prefunded L1 key file for paying the Hydra transaction fees on L1, ~13 ADA per L2 cycle.
     #[arg(long)]
     pub hydra_cardano_signing_key: Option<PathBuf>,
@@ -241,30 +271,40 @@ impl Args {
             })
             .prompt()?;
 
-        let data_node = {
-            let data_node_url = Text::new("Data node URL (empty to skip):").prompt()?;
+        // Only the light and full modes use a data node:
+        let data_node = if mode == Mode::Compact {
+            DataNodeArgs {
+                endpoint: None,
+                request_timeout: 0,
+            }
+        } else {
+            let data_node_url = Text::new("Data node URL:")
+                .with_help_message("Required in the light and full modes")
+                .with_validator(|input: &str| {
+                    if input.is_empty() {
+                        Ok(Validation::Invalid(ErrorMessage::Custom(
+                            "Invalid URL.".into(),
+                        )))
+                    } else {
+                        Ok(Validation::Valid)
+                    }
+                })
+                .prompt()?;
 
-            if data_node_url.is_empty() {
-                DataNodeArgs {
-                    endpoint: None,
-                    request_timeout: 0,
-                }
-            } else {
-                let data_node_timeout = Text::new("Data node timeout (s):")
-                    .with_default("30")
-                    .with_validator(|i: &str| match i.parse::<u64>() {
-                        Ok(t) if t > 0 => Ok(Validation::Valid),
-                        _ => Ok(Validation::Invalid(ErrorMessage::Custom(
-                            "Must be > 0".into(),
-                        ))),
-                    })
-                    .prompt()?
-                    .parse()?;
+            let data_node_timeout = Text::new("Data node timeout (s):")
+                .with_default("30")
+                .with_validator(|i: &str| match i.parse::<u64>() {
+                    Ok(t) if t > 0 => Ok(Validation::Valid),
+                    _ => Ok(Validation::Invalid(ErrorMessage::Custom(
+                        "Must be > 0".into(),
+                    ))),
+                })
+                .prompt()?
+                .parse()?;
 
-                DataNodeArgs {
-                    endpoint: Some(data_node_url),
-                    request_timeout: data_node_timeout,
-                }
+            DataNodeArgs {
+                endpoint: Some(data_node_url),
+                request_timeout: data_node_timeout,
             }
         };
 
@@ -277,7 +317,7 @@ impl Args {
             log_level,
             server_address,
             server_port,
-            node_socket_path: Some(node_socket_path),
+            node_socket_path: vec![node_socket_path],
             reward_address: None,
             secret: None,
             custom_genesis_config: None,
@@ -287,6 +327,7 @@ impl Args {
             max_response_body_bytes: bf_common::DEFAULT_MAX_BODY_BYTES,
             gateway_url: None,
             hydra_cardano_signing_key: None,
+            resubmit_on_rollback: false,
         };
 
         if !is_solitary {
@@ -345,7 +386,7 @@ mod tests {
     /// Builder for constructing cli arguments
     #[derive(Default)]
     struct TestArgsBuilder {
-        node_socket_path: Option<String>,
+        node_socket_paths: Vec<String>,
         server_address: Option<String>,
         server_port: Option<u16>,
         server_concurrency_limit: Option<usize>,
@@ -365,7 +406,7 @@ mod tests {
         }
 
         fn node_socket_path(mut self, path: &str) -> Self {
-            self.node_socket_path = Some(path.to_string());
+            self.node_socket_paths.push(path.to_string());
             self
         }
 
@@ -434,7 +475,9 @@ mod tests {
                 }
             };
 
-            push_opt("--node-socket-path", self.node_socket_path.clone());
+            for path in &self.node_socket_paths {
+                push_opt("--node-socket-path", Some(path.clone()));
+            }
             push_opt("--server-address", self.server_address.clone());
             push_opt("--server-port", self.server_port.map(|p| p.to_string()));
             push_opt(
@@ -481,7 +524,7 @@ mod tests {
             .await
             .expect("Config should be created successfully");
 
-        assert_eq!(config.node_socket_path, "/path/to/socket");
+        assert_eq!(config.node_socket_paths, vec!["/path/to/socket"]);
         assert_eq!(config.max_pool_connections, 10);
         assert_eq!(config.server_address.to_string(), "0.0.0.0");
         assert_eq!(config.server_port, 3000);
@@ -507,7 +550,7 @@ mod tests {
             .await
             .expect("Config should be created successfully");
 
-        assert_eq!(config.node_socket_path, "/path/to/socket");
+        assert_eq!(config.node_socket_paths, vec!["/path/to/socket"]);
         assert_eq!(config.max_pool_connections, 10);
         assert_eq!(config.server_address.to_string(), "0.0.0.0");
         assert_eq!(config.server_port, 3000);
@@ -518,6 +561,68 @@ mod tests {
         assert!(args.solitary);
     }
 
+    #[tokio::test]
+    async fn test_multiple_node_sockets_ok() {
+        let args = TestArgsBuilder::new()
+            .node_socket_path("/path/to/socket-a")
+            .node_socket_path("/path/to/socket-b")
+            .solitary()
+            .parse()
+            .unwrap();
+
+        let config = Config::from_args_with_detector(args, mock_detector)
+            .await
+            .expect("Config should be created successfully");
+
+        assert_eq!(
+            config.node_socket_paths,
+            vec!["/path/to/socket-a", "/path/to/socket-b"]
+        );
+    }
+
+    #[test]
+    fn test_repeated_node_socket_paths_through_twelf() {
+        let matches = Args::command().get_matches_from([
+            "testing",
+            "--node-socket-path",
+            "/path/to/socket-a",
+            "--node-socket-path",
+            "/path/to/socket-b",
+        ]);
+
+        let args = Args::with_layers(&[Layer::Clap(matches)]).unwrap();
+
+        assert_eq!(
+            args.node_socket_path,
+            vec!["/path/to/socket-a", "/path/to/socket-b"]
+        );
+    }
+
+    #[test]
+    fn test_node_socket_paths_in_config_file() {
+        for (name, contents, expected) in [
+            (
+                "single",
+                "node_socket_path = '/path/to/socket'",
+                vec!["/path/to/socket"],
+            ),
+            (
+                "list",
+                "node_socket_path = ['/path/to/socket-a', '/path/to/socket-b']",
+                vec!["/path/to/socket-a", "/path/to/socket-b"],
+            ),
+        ] {
+            let path = std::env::temp_dir().join(format!("bf_test_node_socket_path_{name}.toml"));
+            fs::write(&path, contents).unwrap();
+
+            // Clap provides the defaults of all other fields:
+            let matches = Args::command().get_matches_from(["testing"]);
+            let args = Args::with_layers(&[Layer::Toml(path), Layer::Clap(matches)]).unwrap();
+
+            assert_eq!(args.node_socket_path, expected);
+        }
+    }
+
     #[tokio::test]
     async fn test_no_metrics_ok() {
         let args = TestArgsBuilder::new()
@@ -543,6 +648,7 @@ mod tests {
             .server_port(5353)
             .log_level("debug")
             .mode("full")
+            .data_node("http://localhost:3010")
             .no_metrics()
             .solitary()
             .parse()
@@ -552,7 +658,7 @@ mod tests {
             .await
             .expect("Config should be created successfully");
 
-        assert_eq!(config.node_socket_path, "/path/to/socket");
+        assert_eq!(config.node_socket_paths, vec!["/path/to/socket"]);
         assert_eq!(config.max_pool_connections, 10);
         assert_eq!(config.server_address.to_string(), "192.168.1.1");
         assert_eq!(config.server_port, 5353);
@@ -563,6 +669,43 @@ mod tests {
         assert!(args.solitary);
     }
 
+    #[tokio::test]
+    async fn test_mode_without_data_node_fail() {
+        for mode in ["light", "full"] {
+            let args = TestArgsBuilder::new()
+                .node_socket_path("/path/to/socket")
+                .mode(mode)
+                .solitary()
+                .parse()
+                .unwrap();
+
+            let result = Config::from_args_with_detector(args, mock_detector).await;
+
+            assert_eq!(
+                result.unwrap_err().to_string(),
+                format!(
+                    "Server startup error: --mode {mode} requires a data node, please set --data-node, or use --mode compact"
+                )
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_compact_mode_with_data_node() {
+        let args = TestArgsBuilder::new()
+            .node_socket_path("/path/to/socket")
+            .solitary()
+            .data_node("http://localhost:3010")
+            .parse()
+            .unwrap();
+
+        let config = Config::from_args_with_detector(args, mock_detector)
+            .await
+            .unwrap();
+
+        assert_eq!(config.route_groups(), Mode::Full.route_groups());
+    }
+
     #[tokio::test]
     async fn test_solitary_conflict_fail() {
         let args = TestArgsBuilder::new()
@@ -652,6 +795,7 @@ mod tests {
         let args = TestArgsBuilder::new()
             .node_socket_path("/path/to/socket")
             .solitary()
+            .mode("light")
             .data_node("http://localhost:9000")
             .data_node_timeout_sec("60")
             .parse()
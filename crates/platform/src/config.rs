@@ -2,6 +2,7 @@ use crate::cli::Args;
 use crate::genesis::{GenesisRegistry, GenesisRegistryMut, genesis};
 use bf_api_provider::types::GenesisResponse;
 use bf_common::errors::AppError;
+use bf_common::route_groups::{Backend, RouteGroup};
 use bf_common::types::Network;
 use clap::ValueEnum;
 use futures::FutureExt; // for `.boxed()`
@@ -21,7 +22,8 @@ pub struct Config {
     pub server_concurrency_limit: usize,
     pub max_response_body_bytes: usize,
     pub log_level: Level,
-    pub node_socket_path: String,
+    /// The first one is used for network detection, and by Hydra.
+    pub node_socket_paths: Vec<String>,
     pub mode: Mode,
     pub icebreakers_config: Option<IcebreakersConfig>,
     pub max_pool_connections: usize,
@@ -31,6 +33,7 @@ pub struct Config {
     pub genesis: Vec<(Network, GenesisResponse)>,
     pub data_node: Option<DataNodeConfig>,
     pub hydra: Option<HydraConfig>,
+    pub resubmit_on_rollback: bool,
 }
 
 #[derive(Clone, Deserialize, Debug)]
@@ -69,14 +72,83 @@ impl std::fmt::Display for Mode {
     }
 }
 
+impl Mode {
+    /// Route groups served in this mode. Each mode extends the previous one.
+    pub fn route_groups(&self) -> Vec<RouteGroup> {
+        let compact = [
+            RouteGroup::Health,
+            RouteGroup::Genesis,
+            RouteGroup::TxSubmit,
+            RouteGroup::Utils,
+            RouteGroup::Mempool,
+            RouteGroup::Ogmios,
+            RouteGroup::ChainStream,
+            RouteGroup::LedgerState,
+        ];
+        let light = [
+            RouteGroup::Accounts,
+            RouteGroup::Addresses,
+            RouteGroup::Assets,
+            RouteGroup::Blocks,
+            RouteGroup::Epochs,
+            RouteGroup::Network,
+            RouteGroup::Pools,
+            RouteGroup::Txs,
+        ];
+        let full = [
+            RouteGroup::Governance,
+            RouteGroup::Metadata,
+            RouteGroup::Scripts,
+        ];
+
+        match self {
+            Mode::Compact => compact.to_vec(),
+            Mode::Light => [&compact[..], &light[..]].concat(),
+            Mode::Full => [&compact[..], &light[..], &full[..]].concat(),
+        }
+    }
+
+    /// Fails if a backend needed by one of [`Self::route_groups`] is not
+    /// configured. The node is always required.
+    pub fn check_backends(&self, data_node: Option<&DataNodeConfig>) -> Result<(), AppError> {
+        let needs_data_node = self
+            .route_groups()
+            .iter()
+            .any(|group| group.backend() == Backend::DataNode);
+
+        if needs_data_node && data_node.is_none() {
+            return Err(AppError::Server(format!(
+                "--mode {self} requires a data node, please set --data-node, or use --mode compact"
+            )));
+        }
+
+        Ok(())
+    }
+}
+
 impl Config {
+    /// Route groups served by this platform. Those of [`Self::mode`], and, in
+    /// the compact mode, also all the ones a configured data node enables.
+    pub fn route_groups(&self) -> Vec<RouteGroup> {
+        match (&self.mode, &self.data_node) {
+            (Mode::Compact, Some(_)) => Mode::Full.route_groups(),
+            (mode, _) => mode.route_groups(),
+        }
+    }
+
     pub async fn from_args_with_detector(
         args: Args,
         detector: impl for<'a> Fn(&'a str) -> BoxFuture<'a, Result<Network, AppError>>,
     ) -> Result<Self, AppError> {
-        let node_socket_path = args
+        let node_socket_paths: Vec<String> = args
             .node_socket_path
-            .ok_or(AppError::Server("--node-socket-path must be set".into()))?;
+            .into_iter()
+            .filter(|path| !path.is_empty())
+            .collect();
+
+        let Some(node_socket_path) = node_socket_paths.first() else {
+            return Err(AppError::Server("--node-socket-path must be set".into()));
+        };
 
         let icebreakers_config = if !args.solitary {
             let reward_address = args
@@ -109,7 +181,7 @@ impl Config {
                 genesis_registry.add(Network::Custom, custom);
                 Network::Custom
             },
-            None => detector(&node_socket_path).await?,
+            None => detector(node_socket_path).await?,
         };
 
         let data_node = args.data_node.map(|endpoint| {
@@ -121,6 +193,8 @@ impl Config {
             }
         });
 
+        args.mode.check_backends(data_node.as_ref())?;
+
         let hydra = args
             .hydra_cardano_signing_key
             .map(|cardano_signing_key| HydraConfig {
@@ -131,7 +205,7 @@ impl Config {
             server_address: args.server_address,
             server_port: args.server_port,
             log_level: args.log_level.into(),
-            node_socket_path,
+            node_socket_paths,
             mode: args.mode,
             icebreakers_config,
             max_pool_connections: 10,
@@ -141,6 +215,7 @@ impl Config {
             genesis: genesis_registry,
             data_node,
             hydra,
+            resubmit_on_rollback: args.resubmit_on_rollback,
             server_concurrency_limit: args.server_concurrency_limit,
             max_response_body_bytes: args.max_response_body_bytes,
         })
@@ -504,4 +579,35 @@ mod tests {
             let _ = fs::remove_file(&path);
         }
     }
+
+    #[test]
+    fn test_mode_route_groups() {
+        let compact = Mode::Compact.route_groups();
+        let light = Mode::Light.route_groups();
+        let full = Mode::Full.route_groups();
+
+        assert!(
+            compact
+                .iter()
+                .all(|group| group.backend() != Backend::DataNode)
+        );
+        assert!(compact.iter().all(|group| light.contains(group)));
+        assert!(light.iter().all(|group| full.contains(group)));
+        assert!(light.len() > compact.len() && full.len() > light.len());
+
+        let mut all = full;
+        all.sort();
+        assert_eq!(all, RouteGroup::ALL.to_vec());
+
+        let data_node = DataNodeConfig {
+            endpoint: "http://localhost:3010".to_string(),
+            request_timeout: Duration::from_secs(30),
+        };
+
+        assert!(Mode::Compact.check_backends(None).is_ok());
+        assert!(Mode::Compact.check_backends(Some(&data_node)).is_ok());
+        assert!(Mode::Light.check_backends(None).is_err());
+        assert!(Mode::Light.check_backends(Some(&data_node)).is_ok());
+        assert!(Mode::Full.check_backends(Some(&data_node)).is_ok());
+    }
 }
@@ -1,13 +1,21 @@
 use crate::genesis::GenesisRegistry;
 use bf_api_provider::types::GenesisResponse;
 use bf_common::{errors::BlockfrostError, types::Network};
+use bf_node::{chain_sync::EpochTotals, era_history::EraHistory};
 use serde::Deserialize;
+use serde_json::{Value, json};
 
 #[derive(Deserialize)]
 pub struct EpochsPath {
     pub epoch_number: String,
 }
 
+#[derive(Deserialize)]
+pub struct EpochsPoolPath {
+    pub epoch_number: String,
+    pub pool_id: String,
+}
+
 pub struct EpochData {
     pub epoch_number: i32,
     pub epoch_length: i32,
@@ -50,10 +58,57 @@ impl EpochData {
     }
 }
 
+/// Derives the latest epoch from the era history of the node and the totals
+/// of the chain follower, for when there’s no data node.
+///
+/// The epoch boundaries come from the era history, so they’re right on custom
+/// networks, too. The totals are only known once the follower has seen the
+/// whole epoch, so until then they’re `null`. The active stake needs a stake
+/// distribution snapshot, and is always `null`.
+pub fn latest_epoch_from_node(
+    epoch: u64,
+    totals: Option<&EpochTotals>,
+    era_history: &EraHistory,
+) -> Result<Value, BlockfrostError> {
+    let out_of_range = || {
+        BlockfrostError::internal_server_error(format!(
+            "Epoch {epoch} is out of the era history range"
+        ))
+    };
+    let epoch_start = |epoch: u64| {
+        era_history
+            .epoch_slot_to_slot(epoch, 0)
+            .and_then(|slot| slot_to_unix(era_history, slot))
+            .ok_or_else(out_of_range)
+    };
+    let block_time = |slot: u64| slot_to_unix(era_history, slot).ok_or_else(out_of_range);
+
+    let totals = totals.filter(|totals| totals.epoch == epoch && totals.complete);
+
+    Ok(json!({
+        "epoch": epoch,
+        "start_time": epoch_start(epoch)?,
+        "end_time": epoch_start(epoch + 1)?,
+        "first_block_time": totals.map(|totals| block_time(totals.first_block_slot)).transpose()?,
+        "last_block_time": totals.map(|totals| block_time(totals.last_block_slot)).transpose()?,
+        "block_count": totals.map(|totals| totals.block_count),
+        "tx_count": totals.map(|totals| totals.tx_count),
+        "output": totals.map(|totals| totals.output.to_string()),
+        "fees": totals.map(|totals| totals.fees.to_string()),
+        "active_stake": null,
+    }))
+}
+
+fn slot_to_unix(era_history: &EraHistory, slot: u64) -> Option<i64> {
+    era_history.slot_to_time(slot).map(|time| time.timestamp())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::genesis::{GenesisRegistryMut, genesis};
+    use bf_node::era_history::{EraBound, EraSummary};
+    use chrono::{TimeZone, Utc};
     use pretty_assertions::assert_eq;
     use rstest::rstest;
 
@@ -114,4 +169,98 @@ mod tests {
         let err = EpochData::from_path("abc".to_string(), &Network::Preview, &registry);
         assert!(err.is_err());
     }
+
+    /// Mainnet: Byron (20 s slots, 21600-slot epochs) until epoch 208, then
+    /// 1 s slots and 432000-slot epochs.
+    fn mainnet_era_history() -> EraHistory {
+        EraHistory {
+            system_start: Utc.timestamp_opt(1_506_203_091, 0).unwrap(),
+            eras: vec![
+                EraSummary {
+                    start: EraBound {
+                        time_ms: 0,
+                        slot: 0,
+                        epoch: 0,
+                    },
+                    end: Some(EraBound {
+                        time_ms: 89_856_000_000,
+                        slot: 4_492_800,
+                        epoch: 208,
+                    }),
+                    epoch_length: 21_600,
+                    slot_length_ms: 20_000,
+                },
+                EraSummary {
+                    start: EraBound {
+                        time_ms: 89_856_000_000,
+                        slot: 4_492_800,
+                        epoch: 208,
+                    },
+                    end: None,
+                    epoch_length: 432_000,
+                    slot_length_ms: 1_000,
+                },
+            ],
+        }
+    }
+
+    fn epoch_500_totals() -> EpochTotals {
+        EpochTotals {
+            epoch: 500,
+            first_block_slot: 130_636_820,
+            last_block_slot: 130_789_009,
+            block_count: 7_000,
+            tx_count: 120_000,
+            output: 45_000_000_000_000_000,
+            fees: 21_000_000_000,
+            complete: true,
+        }
+    }
+
+    #[test]
+    fn latest_epoch_from_node_uses_era_history_boundaries() {
+        let totals = epoch_500_totals();
+
+        let epoch = latest_epoch_from_node(500, Some(&totals), &mainnet_era_history()).unwrap();
+
+        assert_eq!(
+            epoch,
+            json!({
+                "epoch": 500,
+                "start_time": 1_722_203_091,
+                "end_time": 1_722_635_091,
+                "first_block_time": 1_722_203_111,
+                "last_block_time": 1_722_355_300,
+                "block_count": 7_000,
+                "tx_count": 120_000,
+                "output": "45000000000000000",
+                "fees": "21000000000",
+                "active_stake": null,
+            })
+        );
+    }
+
+    #[rstest]
+    #[case::not_followed_yet(None)]
+    #[case::incomplete(Some(EpochTotals { complete: false, ..epoch_500_totals() }))]
+    #[case::previous_epoch(Some(EpochTotals { epoch: 499, ..epoch_500_totals() }))]
+    fn latest_epoch_from_node_without_totals(#[case] totals: Option<EpochTotals>) {
+        let epoch = latest_epoch_from_node(500, totals.as_ref(), &mainnet_era_history()).unwrap();
+
+        assert_eq!(
+            epoch,
+            json!({
+                "epoch": 500,
+                "start_time": 1_722_203_091,
+                "end_time": 1_722_635_091,
+                "first_block_time": null,
+                "last_block_time": null,
+                "block_count": null,
+                "tx_count": null,
+                "output": null,
+                "fees": null,
+                "active_stake": null,
+            })
+        );
+    }
 }
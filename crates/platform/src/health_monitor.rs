@@ -25,6 +25,7 @@ pub struct HealthMonitor {
     sources: Arc<Mutex<Vec<ErrorSource>>>,
     node_info: Arc<Mutex<Option<NodeInfo>>>,
     data_node_info: Arc<Mutex<Option<DataNodeRootResponse>>>,
+    data_node_errors: ErrorSource,
 }
 
 impl HealthMonitor {
@@ -47,6 +48,12 @@ impl HealthMonitor {
         Self::collect_errors(&self.sources.lock().await).await.len() as u32
     }
 
+    /// Whether the data node passed its last health check. Handlers that have a
+    /// node-backed alternative use this to decide whether to fall back to it.
+    pub async fn is_data_node_healthy(&self) -> bool {
+        self.data_node_errors.lock().await.is_empty()
+    }
+
     /// Collect errors across multiple sources.
     async fn collect_errors(sources: &[Arc<Mutex<Vec<BlockfrostError>>>]) -> Vec<BlockfrostError> {
         let mut errors = vec![];
@@ -77,6 +84,7 @@ impl HealthMonitor {
             sources: Arc::new(Mutex::new(vec![])),
             node_info: node_mon.node_info(),
             data_node_info: data_node_mon.data_node_info(),
+            data_node_errors: data_node_mon.errors(),
         };
 
         self_.register_error_source(node_mon.errors()).await;
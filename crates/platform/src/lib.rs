@@ -14,8 +14,10 @@ pub mod icebreakers;
 pub mod load_balancer;
 pub mod metadata;
 pub mod middlewares;
+pub mod ogmios;
 pub mod payment_cred;
 pub mod pools;
+pub mod proposals;
 pub mod server;
 pub mod txs;
 pub mod validation;
@@ -109,7 +109,7 @@ async fn main() -> Result<(), AppError> {
                 hydra_config,
                 config.network,
                 hydra_genesis,
-                config.node_socket_path,
+                config.node_socket_paths[0].clone(),
                 icebreakers_config.reward_address,
                 health_errors,
                 kex_req_tx,
@@ -0,0 +1,278 @@
+//! A subset of the [Ogmios](https://ogmios.dev/api/) v6 JSON-RPC protocol,
+//! served over a WebSocket by [`crate::api::ogmios`], so that existing Ogmios
+//! clients can talk to the platform directly.
+
+pub mod chain_sync;
+pub mod ledger_state;
+pub mod mempool;
+pub mod transactions;
+
+use crate::server::state::AppState;
+use bf_common::errors::{AppError, BlockfrostError};
+use bf_node::{
+    chain_sync::ChainFollower,
+    pool::{DedicatedConnection, NodePool},
+    tx_tracker::TxTracker,
+};
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+pub const PARSE_ERROR: i64 = -32700;
+pub const INVALID_REQUEST: i64 = -32600;
+pub const METHOD_NOT_FOUND: i64 = -32601;
+pub const INVALID_PARAMS: i64 = -32602;
+pub const INTERNAL_ERROR: i64 = -32603;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RpcError {
+    pub code: i64,
+    pub message: String,
+    pub data: Option<Value>,
+}
+
+impl RpcError {
+    pub fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            data: None,
+        }
+    }
+
+    pub fn with_data(mut self, data: Value) -> Self {
+        self.data = Some(data);
+        self
+    }
+
+    pub fn invalid_params(message: impl Into<String>) -> Self {
+        Self::new(INVALID_PARAMS, message)
+    }
+}
+
+impl From<BlockfrostError> for RpcError {
+    fn from(err: BlockfrostError) -> Self {
+        let code = match err.status_code {
+            400 => INVALID_PARAMS,
+            _ => INTERNAL_ERROR,
+        };
+
+        Self::new(code, err.message)
+    }
+}
+
+impl From<AppError> for RpcError {
+    fn from(err: AppError) -> Self {
+        BlockfrostError::from(err).into()
+    }
+}
+
+pub type RpcResult = Result<Value, RpcError>;
+
+#[derive(Deserialize, Debug)]
+struct Request {
+    jsonrpc: String,
+    method: String,
+    #[serde(default)]
+    params: Value,
+    id: Option<Value>,
+}
+
+/// Everything a single WebSocket connection remembers between requests.
+pub struct Session {
+    pub(crate) state: AppState,
+    pub(crate) node: NodePool,
+    pub(crate) chain_follower: ChainFollower,
+    pub(crate) tx_tracker: TxTracker,
+    /// Opened on the first chain-sync request, see [`chain_sync`].
+    pub(crate) chain_sync: Option<DedicatedConnection>,
+    pub(crate) mempool: Option<mempool::AcquiredMempool>,
+}
+
+impl Session {
+    pub fn new(
+        state: AppState,
+        node: NodePool,
+        chain_follower: ChainFollower,
+        tx_tracker: TxTracker,
+    ) -> Self {
+        Self {
+            state,
+            node,
+            chain_follower,
+            tx_tracker,
+            chain_sync: None,
+            mempool: None,
+        }
+    }
+
+    /// Handles a single JSON-RPC request and returns its response.
+    pub async fn handle(&mut self, text: &str) -> Value {
+        let request: Value = match serde_json::from_str(text) {
+            Ok(request) => request,
+            Err(err) => {
+                return response(None, None, Err(RpcError::new(PARSE_ERROR, err.to_string())));
+            },
+        };
+
+        let id = request.get("id").cloned();
+
+        let request = match serde_json::from_value::<Request>(request) {
+            Ok(request) if request.jsonrpc == "2.0" => request,
+            Ok(_) => {
+                let err = RpcError::new(INVALID_REQUEST, "Only JSON-RPC 2.0 is supported");
+                return response(None, id, Err(err));
+            },
+            Err(err) => {
+                return response(
+                    None,
+                    id,
+                    Err(RpcError::new(INVALID_REQUEST, err.to_string())),
+                );
+            },
+        };
+
+        let result = self.dispatch(&request.method, request.params).await;
+
+        response(Some(&request.method), request.id, result)
+    }
+
+    async fn dispatch(&mut self, method: &str, params: Value) -> RpcResult {
+        match method {
+            "findIntersection" => self.find_intersection(params).await,
+            "nextBlock" => self.next_block().await,
+            "submitTransaction" => self.submit_transaction(params).await,
+            "evaluateTransaction" => self.evaluate_transaction(params).await,
+            "acquireMempool" => self.acquire_mempool().await,
+            "nextTransaction" => self.next_transaction(params),
+            "hasTransaction" => self.has_transaction(params),
+            "sizeOfMempool" => self.size_of_mempool(),
+            "releaseMempool" => self.release_mempool(),
+            _ => match method.split_once('/') {
+                Some(("queryLedgerState", query)) => self.query_ledger_state(query, params).await,
+                Some(("queryNetwork", query)) => self.query_network(query).await,
+                _ => Err(RpcError::new(
+                    METHOD_NOT_FOUND,
+                    format!("Unknown method: {method}"),
+                )),
+            },
+        }
+    }
+}
+
+fn response(method: Option<&str>, id: Option<Value>, result: RpcResult) -> Value {
+    let mut response = json!({ "jsonrpc": "2.0" });
+
+    if let Some(method) = method {
+        response["method"] = json!(method);
+    }
+
+    match result {
+        Ok(result) => response["result"] = result,
+        Err(err) => {
+            let mut error = json!({ "code": err.code, "message": err.message });
+            if let Some(data) = err.data {
+                error["data"] = data;
+            }
+            response["error"] = error;
+        },
+    }
+
+    if let Some(id) = id {
+        response["id"] = id;
+    }
+
+    response
+}
+
+/// Deserializes `params`, treating a missing object as an empty one.
+pub(crate) fn parse_params<T: serde::de::DeserializeOwned>(params: Value) -> Result<T, RpcError> {
+    let params = if params.is_null() { json!({}) } else { params };
+
+    serde_json::from_value(params).map_err(|err| RpcError::invalid_params(err.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(
+        Some("queryNetwork/tip"),
+        Some(json!(7)),
+        Ok(json!({ "slot": 1, "id": "ab" })),
+        json!({
+            "jsonrpc": "2.0",
+            "method": "queryNetwork/tip",
+            "result": { "slot": 1, "id": "ab" },
+            "id": 7,
+        })
+    )]
+    #[case(
+        Some("nextTransaction"),
+        None,
+        Err(RpcError::new(4000, "Mempool not acquired")),
+        json!({
+            "jsonrpc": "2.0",
+            "method": "nextTransaction",
+            "error": { "code": 4000, "message": "Mempool not acquired" },
+        })
+    )]
+    #[case(
+        None,
+        Some(json!("x")),
+        Err(RpcError::new(PARSE_ERROR, "eof").with_data(json!({ "line": 1 }))),
+        json!({
+            "jsonrpc": "2.0",
+            "error": { "code": PARSE_ERROR, "message": "eof", "data": { "line": 1 } },
+            "id": "x",
+        })
+    )]
+    fn test_response(
+        #[case] method: Option<&str>,
+        #[case] id: Option<Value>,
+        #[case] result: RpcResult,
+        #[case] expected: Value,
+    ) {
+        assert_eq!(response(method, id, result), expected);
+    }
+
+    #[rstest]
+    #[case(400, INVALID_PARAMS)]
+    #[case(404, INTERNAL_ERROR)]
+    #[case(500, INTERNAL_ERROR)]
+    fn test_rpc_error_from_blockfrost_error(#[case] status_code: u16, #[case] expected: i64) {
+        let err = BlockfrostError {
+            error: "error".to_string(),
+            message: "message".to_string(),
+            status_code,
+        };
+
+        assert_eq!(RpcError::from(err).code, expected);
+    }
+
+    #[test]
+    fn test_parse_params() {
+        #[derive(Deserialize, Debug, PartialEq)]
+        struct Params {
+            #[serde(default)]
+            fields: Option<String>,
+        }
+
+        assert_eq!(
+            parse_params::<Params>(Value::Null),
+            Ok(Params { fields: None })
+        );
+        assert_eq!(
+            parse_params::<Params>(json!({ "fields": "all" })),
+            Ok(Params {
+                fields: Some("all".to_string())
+            })
+        );
+        assert_eq!(
+            parse_params::<Params>(json!({ "fields": 1 })).map_err(|e| e.code),
+            Err(INVALID_PARAMS)
+        );
+    }
+}
@@ -0,0 +1,257 @@
+//! `findIntersection` and `nextBlock`.
+//!
+//! **Note**: the chain-sync state lives in the node connection, so a session
+//! opens its own [`DedicatedConnection`] on its first chain-sync request, and
+//! keeps it until the WebSocket is closed. It’s outside of the
+//! [`bf_node::pool::NodePool`], so that idle sessions can’t starve the pool.
+
+use super::{RpcError, RpcResult, Session, parse_params};
+use bf_common::errors::BlockfrostError;
+use bf_node::{
+    chain_sync::{ChainPoint, ChainSyncBlock, ChainSyncStep, ChainTip, Intersection},
+    pool::DedicatedConnection,
+};
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+/// Ogmios v6 error code for `findIntersection` without any common point.
+const INTERSECTION_NOT_FOUND: i64 = 1000;
+
+#[derive(Deserialize, Debug)]
+struct FindIntersectionParams {
+    points: Vec<Value>,
+}
+
+impl Session {
+    pub(crate) async fn find_intersection(&mut self, params: Value) -> RpcResult {
+        let params: FindIntersectionParams = parse_params(params)?;
+
+        let points = params
+            .points
+            .iter()
+            .map(parse_point)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let connection = self.chain_sync_connection().await?;
+
+        // XXX: Axum must not abort Ouroboros protocols in the middle, hence a separate Tokio task:
+        let (connection, result) = tokio::spawn(async move {
+            let mut connection = connection;
+            let result = connection.chainsync_find_intersection(&points).await;
+            (connection, result)
+        })
+        .await
+        .expect("chainsync_find_intersection panic!");
+
+        let (intersection, tip) = self.keep_chain_sync_connection(connection, result)?;
+
+        match intersection {
+            Intersection::Found(point) => Ok(json!({
+                "intersection": point_json(point.as_ref()),
+                "tip": tip_json(&tip),
+            })),
+            Intersection::NotFound => Err(RpcError::new(
+                INTERSECTION_NOT_FOUND,
+                "No intersection found with the requested points",
+            )
+            .with_data(json!({ "tip": tip_json(&tip) }))),
+        }
+    }
+
+    pub(crate) async fn next_block(&mut self) -> RpcResult {
+        let connection = self.chain_sync_connection().await?;
+
+        // XXX: Axum must not abort Ouroboros protocols in the middle, hence a separate Tokio task:
+        let (connection, result) = tokio::spawn(async move {
+            let mut connection = connection;
+            let result = connection.chainsync_next_block().await;
+            (connection, result)
+        })
+        .await
+        .expect("chainsync_next_block panic!");
+
+        Ok(step_json(
+            &self.keep_chain_sync_connection(connection, result)?,
+        ))
+    }
+
+    /// Takes the session’s connection, or opens a new one and resets its
+    /// chain-sync state to the genesis, like a fresh Ogmios client expects.
+    async fn chain_sync_connection(&mut self) -> Result<DedicatedConnection, RpcError> {
+        if let Some(connection) = self.chain_sync.take() {
+            return Ok(connection);
+        }
+
+        let node = self.node.clone();
+
+        // XXX: Axum must not abort Ouroboros protocols in the middle, hence a separate Tokio task:
+        let connection = tokio::spawn(async move {
+            let mut connection = node.dedicated().await?;
+            connection.chainsync_find_intersection(&[None]).await?;
+            Ok::<_, BlockfrostError>(connection)
+        })
+        .await
+        .expect("chainsync_find_intersection panic!")?;
+
+        Ok(connection)
+    }
+
+    /// A connection with a failed chain-sync request is in an unknown state, so
+    /// we close it, and the next request opens a new one.
+    fn keep_chain_sync_connection<A>(
+        &mut self,
+        connection: DedicatedConnection,
+        result: Result<A, BlockfrostError>,
+    ) -> Result<A, RpcError> {
+        if result.is_ok() {
+            self.chain_sync = Some(connection);
+        }
+
+        Ok(result?)
+    }
+}
+
+fn parse_point(point: &Value) -> Result<Option<ChainPoint>, RpcError> {
+    if point == "origin" {
+        return Ok(None);
+    }
+
+    match (point["slot"].as_u64(), point["id"].as_str()) {
+        (Some(slot), Some(id)) => Ok(Some(ChainPoint {
+            hash: id.to_string(),
+            slot,
+        })),
+        _ => Err(RpcError::invalid_params(format!(
+            "Invalid point: {point}, expected \"origin\" or {{ \"slot\", \"id\" }}"
+        ))),
+    }
+}
+
+pub(crate) fn point_json(point: Option<&ChainPoint>) -> Value {
+    match point {
+        Some(point) => json!({ "slot": point.slot, "id": point.hash }),
+        None => json!("origin"),
+    }
+}
+
+fn tip_json(tip: &ChainTip) -> Value {
+    match &tip.point {
+        Some(point) => json!({ "slot": point.slot, "id": point.hash, "height": tip.height }),
+        None => json!("origin"),
+    }
+}
+
+fn step_json(step: &ChainSyncStep) -> Value {
+    match step {
+        ChainSyncStep::RollForward { block, tip } => json!({
+            "direction": "forward",
+            "block": block_json(block),
+            "tip": tip_json(tip),
+        }),
+        ChainSyncStep::RollBackward { point, tip } => json!({
+            "direction": "backward",
+            "point": point_json(point.as_ref()),
+            "tip": tip_json(tip),
+        }),
+    }
+}
+
+/// **Note**: unlike in Ogmios, transactions only have their `id` and `cbor`,
+/// and blocks have no `issuer`, `protocol` nor `size`.
+fn block_json(block: &ChainSyncBlock) -> Value {
+    let era = match block.header.era {
+        0 => "byron",
+        1 => "shelley",
+        2 => "allegra",
+        3 => "mary",
+        4 => "alonzo",
+        5 => "babbage",
+        _ => "conway",
+    };
+
+    json!({
+        "type": if block.header.era == 0 { "bft" } else { "praos" },
+        "era": era,
+        "id": block.header.hash,
+        "ancestor": block.ancestor.as_deref().unwrap_or("genesis"),
+        "height": block.header.height,
+        "slot": block.header.slot,
+        "transactions": block.txs.iter().map(|tx| json!({
+            "id": tx.hash,
+            "cbor": hex::encode(&tx.cbor),
+        })).collect::<Vec<_>>(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bf_node::chain_sync::{BlockHeader, ChainSyncTx};
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(json!("origin"), Some(None))]
+    #[case(json!({ "slot": 10, "id": "ab" }), Some(Some(ChainPoint { hash: "ab".to_string(), slot: 10 })))]
+    #[case(json!({ "slot": 10 }), None)]
+    #[case(json!("tip"), None)]
+    fn test_parse_point(#[case] point: Value, #[case] expected: Option<Option<ChainPoint>>) {
+        assert_eq!(parse_point(&point).ok(), expected);
+    }
+
+    #[test]
+    fn test_step_json() {
+        let tip = ChainTip {
+            point: Some(ChainPoint {
+                hash: "ff".to_string(),
+                slot: 200,
+            }),
+            height: 20,
+        };
+
+        let step = ChainSyncStep::RollForward {
+            block: ChainSyncBlock {
+                header: BlockHeader {
+                    hash: "bb".to_string(),
+                    height: 11,
+                    slot: 110,
+                    era: 6,
+                },
+                ancestor: Some("aa".to_string()),
+                txs: vec![ChainSyncTx {
+                    hash: "cc".to_string(),
+                    cbor: vec![0x84],
+                }],
+            },
+            tip: tip.clone(),
+        };
+
+        assert_eq!(
+            step_json(&step),
+            json!({
+                "direction": "forward",
+                "block": {
+                    "type": "praos",
+                    "era": "conway",
+                    "id": "bb",
+                    "ancestor": "aa",
+                    "height": 11,
+                    "slot": 110,
+                    "transactions": [{ "id": "cc", "cbor": "84" }],
+                },
+                "tip": { "slot": 200, "id": "ff", "height": 20 },
+            })
+        );
+
+        let step = ChainSyncStep::RollBackward { point: None, tip };
+
+        assert_eq!(
+            step_json(&step),
+            json!({
+                "direction": "backward",
+                "point": "origin",
+                "tip": { "slot": 200, "id": "ff", "height": 20 },
+            })
+        );
+    }
+}
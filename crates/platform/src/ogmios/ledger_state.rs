@@ -0,0 +1,322 @@
+//! `queryLedgerState/*` and `queryNetwork/*`, with the same node queries as
+//! the REST endpoints.
+
+use super::{RpcError, RpcResult, Session, chain_sync::point_json, parse_params};
+use bf_common::pagination::{Order, Pagination, ParamParts};
+use bf_node::{
+    chain_sync::ChainPoint,
+    era_history::{EraBound, EraHistory},
+    sync_progress::NodeInfo,
+};
+use chrono::SecondsFormat;
+use serde::Deserialize;
+use serde_json::{Map, Value, json};
+
+#[derive(Deserialize, Debug)]
+struct UtxoParams {
+    addresses: Vec<String>,
+}
+
+impl Session {
+    pub(crate) async fn query_ledger_state(&mut self, query: &str, params: Value) -> RpcResult {
+        match query {
+            "epoch" => Ok(json!(self.node_info().await?.epoch)),
+            "tip" => Ok(tip_json(&self.node_info().await?)),
+            "eraStart" => {
+                let era_history = self.era_history().await?;
+                let era = era_history.eras.last().ok_or_else(|| {
+                    RpcError::new(super::INTERNAL_ERROR, "The era history is empty")
+                })?;
+
+                Ok(bound_json(&era.start))
+            },
+            "eraSummaries" => Ok(era_summaries_json(&self.era_history().await?)),
+            "protocolParameters" => {
+                let node = self.node.clone();
+
+                // XXX: Axum must not abort Ouroboros protocols in the middle, hence a separate Tokio task:
+                let pparams = tokio::spawn(async move {
+                    let mut node = node.get().await?;
+                    node.protocol_parameters_ogmios().await
+                })
+                .await
+                .expect("protocol_parameters_ogmios panic!")?;
+
+                Ok(pparams)
+            },
+            "stakePools" => {
+                let node = self.node.clone();
+
+                // XXX: Axum must not abort Ouroboros protocols in the middle, hence a separate Tokio task:
+                let pools = tokio::spawn(async move {
+                    let mut node = node.get().await?;
+                    node.pools(&all_pages()).await
+                })
+                .await
+                .expect("pools panic!")?;
+
+                Ok(Value::Object(
+                    pools
+                        .into_iter()
+                        .map(|id| (id.clone(), json!({ "id": id })))
+                        .collect(),
+                ))
+            },
+            "utxo" => {
+                let params: UtxoParams = parse_params(params).map_err(|_| {
+                    RpcError::invalid_params("Only querying UTxOs by `addresses` is supported")
+                })?;
+
+                let mut utxos = Vec::new();
+
+                for address in params.addresses {
+                    let node = self.node.clone();
+
+                    // XXX: Axum must not abort Ouroboros protocols in the middle, hence a separate Tokio task:
+                    let response = tokio::spawn(async move {
+                        let mut node = node.get().await?;
+                        node.addresses_utxos(&address, &all_pages()).await
+                    })
+                    .await
+                    .expect("addresses_utxos panic!")?;
+
+                    let response = serde_json::to_value(response).map_err(|e| {
+                        RpcError::new(
+                            super::INTERNAL_ERROR,
+                            format!("Failed to convert UTxOs: {e}"),
+                        )
+                    })?;
+
+                    if let Value::Array(response) = response {
+                        utxos.extend(response.iter().map(utxo_json));
+                    }
+                }
+
+                Ok(Value::Array(utxos))
+            },
+            _ => Err(RpcError::new(
+                super::METHOD_NOT_FOUND,
+                format!("Unknown method: queryLedgerState/{query}"),
+            )),
+        }
+    }
+
+    pub(crate) async fn query_network(&mut self, query: &str) -> RpcResult {
+        match query {
+            "tip" => Ok(tip_json(&self.node_info().await?)),
+            "blockHeight" => match self.chain_follower.tip().await {
+                Some(block) => Ok(json!(block.height)),
+                None => Err(RpcError::new(
+                    super::INTERNAL_ERROR,
+                    "The chain tip is not known yet, please retry",
+                )),
+            },
+            "startTime" => Ok(json!(
+                self.era_history()
+                    .await?
+                    .system_start
+                    .to_rfc3339_opts(SecondsFormat::Secs, true)
+            )),
+            _ => Err(RpcError::new(
+                super::METHOD_NOT_FOUND,
+                format!("Unknown method: queryNetwork/{query}"),
+            )),
+        }
+    }
+
+    async fn node_info(&self) -> Result<NodeInfo, RpcError> {
+        let node = self.node.clone();
+
+        // XXX: Axum must not abort Ouroboros protocols in the middle, hence a separate Tokio task:
+        let info = tokio::spawn(async move {
+            let mut node = node.get().await?;
+            node.sync_progress().await
+        })
+        .await
+        .expect("sync_progress panic!")?;
+
+        Ok(info)
+    }
+
+    async fn era_history(&self) -> Result<EraHistory, RpcError> {
+        let node = self.node.clone();
+
+        // XXX: Axum must not abort Ouroboros protocols in the middle, hence a separate Tokio task:
+        let era_history = tokio::spawn(async move {
+            let mut node = node.get().await?;
+            node.era_history().await
+        })
+        .await
+        .expect("era_history panic!")?;
+
+        Ok(era_history)
+    }
+}
+
+/// Ogmios has no pagination.
+fn all_pages() -> Pagination {
+    Pagination {
+        page: 1,
+        count: i32::MAX,
+        order: Order::Asc,
+        from: ParamParts {
+            height: None,
+            index: None,
+        },
+        to: ParamParts {
+            height: None,
+            index: None,
+        },
+    }
+}
+
+fn tip_json(info: &NodeInfo) -> Value {
+    if info.block.is_empty() {
+        point_json(None)
+    } else {
+        point_json(Some(&ChainPoint {
+            hash: info.block.clone(),
+            slot: info.slot,
+        }))
+    }
+}
+
+fn bound_json(bound: &EraBound) -> Value {
+    json!({
+        "time": { "seconds": bound.time_ms / 1000 },
+        "slot": bound.slot,
+        "epoch": bound.epoch,
+    })
+}
+
+/// **Note**: the node sends the safe zone of every era, but we don’t keep it,
+/// so `safeZone` is missing.
+fn era_summaries_json(era_history: &EraHistory) -> Value {
+    era_history
+        .eras
+        .iter()
+        .map(|era| {
+            json!({
+                "start": bound_json(&era.start),
+                "end": era.end.as_ref().map(bound_json),
+                "parameters": {
+                    "epochLength": era.epoch_length,
+                    "slotLength": { "milliseconds": era.slot_length_ms },
+                },
+            })
+        })
+        .collect()
+}
+
+/// Converts a UTxO from the Blockfrost format.
+///
+/// **Note**: reference scripts are missing, because we only have their hashes.
+fn utxo_json(utxo: &Value) -> Value {
+    let mut value = Map::new();
+
+    for amount in utxo["amount"].as_array().into_iter().flatten() {
+        let quantity = amount["quantity"]
+            .as_str()
+            .and_then(|q| q.parse::<u64>().ok())
+            .unwrap_or_default();
+
+        match amount["unit"].as_str() {
+            Some("lovelace") => {
+                value.insert("ada".to_string(), json!({ "lovelace": quantity }));
+            },
+            Some(unit) if unit.len() >= 56 => {
+                let (policy, asset_name) = unit.split_at(56);
+
+                if let Value::Object(assets) =
+                    value.entry(policy.to_string()).or_insert_with(|| json!({}))
+                {
+                    assets.insert(asset_name.to_string(), json!(quantity));
+                }
+            },
+            _ => {},
+        }
+    }
+
+    let mut result = json!({
+        "transaction": { "id": utxo["tx_hash"] },
+        "index": utxo["output_index"],
+        "address": utxo["address"],
+        "value": value,
+    });
+
+    if !utxo["inline_datum"].is_null() {
+        result["datum"] = utxo["inline_datum"].clone();
+    } else if !utxo["data_hash"].is_null() {
+        result["datumHash"] = utxo["data_hash"].clone();
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bf_node::era_history::EraSummary;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_utxo_json() {
+        let utxo = json!({
+            "address": "addr_test1vq",
+            "tx_hash": "aa",
+            "tx_index": 1,
+            "output_index": 1,
+            "amount": [
+                { "unit": "lovelace", "quantity": "1500000" },
+                {
+                    "unit": "b0d07d45fe9514f80213f4020e5a61241458be626841cde717cb38a74e4d4950",
+                    "quantity": "7",
+                },
+            ],
+            "block": "",
+            "data_hash": "dd",
+            "inline_datum": null,
+            "reference_script_hash": null,
+        });
+
+        assert_eq!(
+            utxo_json(&utxo),
+            json!({
+                "transaction": { "id": "aa" },
+                "index": 1,
+                "address": "addr_test1vq",
+                "value": {
+                    "ada": { "lovelace": 1_500_000 },
+                    "b0d07d45fe9514f80213f4020e5a61241458be626841cde717cb38a7": { "4e4d4950": 7 },
+                },
+                "datumHash": "dd",
+            })
+        );
+    }
+
+    #[test]
+    fn test_era_summaries_json() {
+        let era_history = EraHistory {
+            system_start: chrono::DateTime::UNIX_EPOCH,
+            eras: vec![EraSummary {
+                start: EraBound {
+                    time_ms: 1_728_000_000,
+                    slot: 86_400,
+                    epoch: 4,
+                },
+                end: None,
+                epoch_length: 432_000,
+                slot_length_ms: 1000,
+            }],
+        };
+
+        assert_eq!(
+            era_summaries_json(&era_history),
+            json!([{
+                "start": { "time": { "seconds": 1_728_000 }, "slot": 86_400, "epoch": 4 },
+                "end": null,
+                "parameters": { "epochLength": 432_000, "slotLength": { "milliseconds": 1000 } },
+            }])
+        );
+    }
+}
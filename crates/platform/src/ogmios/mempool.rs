@@ -0,0 +1,145 @@
+//! `acquireMempool`, `nextTransaction`, `hasTransaction`, `sizeOfMempool` and
+//! `releaseMempool`.
+//!
+//! The whole snapshot is read at acquisition, so that the N2C local-tx-monitor
+//! mini-protocol never stays in the middle of a session between requests.
+
+use super::{RpcError, RpcResult, Session, parse_params};
+use bf_node::mempool::MempoolTx;
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+/// Ogmios v6 error code for mempool requests before `acquireMempool`.
+const MEMPOOL_NOT_ACQUIRED: i64 = 4000;
+
+/// A snapshot acquired by a session.
+pub(crate) struct AcquiredMempool {
+    capacity_in_bytes: u32,
+    size_in_bytes: u32,
+    txs: Vec<MempoolTx>,
+    /// Index of the transaction for the next `nextTransaction`.
+    next: usize,
+}
+
+#[derive(Deserialize, Debug)]
+struct NextTransactionParams {
+    #[serde(default)]
+    fields: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct HasTransactionParams {
+    id: String,
+}
+
+impl Session {
+    pub(crate) async fn acquire_mempool(&mut self) -> RpcResult {
+        let node = self.node.clone();
+
+        // XXX: Axum must not abort Ouroboros protocols in the middle, hence a separate Tokio task:
+        let snapshot = tokio::spawn(async move {
+            let mut node = node.get().await?;
+            node.mempool_snapshot().await
+        })
+        .await
+        .expect("mempool_snapshot panic!")?;
+
+        self.mempool = Some(AcquiredMempool {
+            capacity_in_bytes: snapshot.capacity_in_bytes,
+            size_in_bytes: snapshot.size_in_bytes,
+            txs: snapshot.txs,
+            next: 0,
+        });
+
+        Ok(json!({ "acquired": "mempool", "slot": snapshot.slot }))
+    }
+
+    /// **Note**: with `fields: "all"`, transactions only have their `id` and
+    /// `cbor`, not the full Ogmios JSON.
+    pub(crate) fn next_transaction(&mut self, params: Value) -> RpcResult {
+        let params: NextTransactionParams = parse_params(params)?;
+
+        let mempool = self.mempool.as_mut().ok_or_else(not_acquired)?;
+
+        let transaction = match mempool.txs.get(mempool.next) {
+            Some(tx) if params.fields.as_deref() == Some("all") => {
+                json!({ "id": tx.hash, "cbor": hex::encode(&tx.cbor) })
+            },
+            Some(tx) => json!({ "id": tx.hash }),
+            None => Value::Null,
+        };
+
+        mempool.next = (mempool.next + 1).min(mempool.txs.len());
+
+        Ok(json!({ "transaction": transaction }))
+    }
+
+    pub(crate) fn has_transaction(&mut self, params: Value) -> RpcResult {
+        let params: HasTransactionParams = parse_params(params)?;
+
+        let mempool = self.mempool.as_ref().ok_or_else(not_acquired)?;
+
+        Ok(json!(mempool.txs.iter().any(|tx| tx.hash == params.id)))
+    }
+
+    pub(crate) fn size_of_mempool(&self) -> RpcResult {
+        let mempool = self.mempool.as_ref().ok_or_else(not_acquired)?;
+
+        Ok(size_json(mempool))
+    }
+
+    pub(crate) fn release_mempool(&mut self) -> RpcResult {
+        self.mempool.take().ok_or_else(not_acquired)?;
+
+        Ok(json!({ "released": "mempool" }))
+    }
+}
+
+fn not_acquired() -> RpcError {
+    RpcError::new(
+        MEMPOOL_NOT_ACQUIRED,
+        "The mempool has to be acquired first with acquireMempool",
+    )
+}
+
+fn size_json(mempool: &AcquiredMempool) -> Value {
+    json!({
+        "maxCapacity": { "bytes": mempool.capacity_in_bytes },
+        "currentSize": { "bytes": mempool.size_in_bytes },
+        "transactions": { "count": mempool.txs.len() },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_size_json() {
+        let mempool = AcquiredMempool {
+            capacity_in_bytes: 178_176,
+            size_in_bytes: 1_234,
+            txs: vec![
+                MempoolTx {
+                    hash: "aa".repeat(32),
+                    cbor: vec![0x84],
+                },
+                MempoolTx {
+                    hash: "bb".repeat(32),
+                    cbor: vec![0x84],
+                },
+            ],
+            next: 0,
+        };
+
+        assert_eq!(
+            size_json(&mempool),
+            json!({
+                "maxCapacity": { "bytes": 178_176 },
+                "currentSize": { "bytes": 1_234 },
+                "transactions": { "count": 2 },
+            })
+        );
+    }
+}
@@ -0,0 +1,148 @@
+//! `submitTransaction` and `evaluateTransaction`, with the same node calls as
+//! `/tx/submit` and `/utils/txs/evaluate`.
+
+use super::{INTERNAL_ERROR, RpcError, RpcResult, Session, parse_params};
+use crate::api::{
+    tx::submit::root::decode_rejection,
+    utils::txs::evaluate::{EvaluateQuery, to_ogmios_response},
+};
+use bf_node::transactions::TxSubmitError;
+use metrics::counter;
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+/// Ogmios v6 error code for transactions rejected by the ledger. We don’t
+/// distinguish between specific ledger rules, the `data` has the details.
+const SUBMIT_TRANSACTION_FAILURE: i64 = 3000;
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct TransactionParams {
+    transaction: Transaction,
+    #[serde(default)]
+    additional_utxo: Vec<Value>,
+}
+
+#[derive(Deserialize, Debug)]
+struct Transaction {
+    cbor: String,
+}
+
+impl TransactionParams {
+    fn parse(params: Value) -> Result<Vec<u8>, RpcError> {
+        let params: Self = parse_params(params)?;
+
+        if !params.additional_utxo.is_empty() {
+            return Err(RpcError::invalid_params(
+                "additionalUtxo is not supported, use /utils/txs/evaluate/utxos instead",
+            ));
+        }
+
+        hex::decode(&params.transaction.cbor)
+            .map_err(|e| RpcError::invalid_params(format!("Invalid transaction CBOR: {e}")))
+    }
+}
+
+impl Session {
+    pub(crate) async fn submit_transaction(&mut self, params: Value) -> RpcResult {
+        let tx = TransactionParams::parse(params)?;
+        let tracked_tx = tx.clone();
+        let node = self.node.clone();
+
+        // XXX: Axum must not abort Ouroboros protocols in the middle, hence a separate Tokio task:
+        let response = tokio::spawn(async move {
+            let response = node.submit_transaction(tx).await;
+
+            if response.is_ok() {
+                counter!("tx_submit_success").increment(1)
+            } else {
+                counter!("tx_submit_failure").increment(1)
+            }
+
+            response
+        })
+        .await
+        .expect("submit_transaction panic!");
+
+        match response {
+            Ok(tx_hash) => {
+                if let Err(err) = self.tx_tracker.track(tracked_tx).await {
+                    tracing::warn!("Failed to track transaction {tx_hash}: {}", err.message);
+                }
+
+                Ok(json!({ "transaction": { "id": tx_hash } }))
+            },
+            Err(TxSubmitError::Rejected { display, cbor }) => {
+                let data = decode_rejection(&self.state, &cbor)
+                    .await
+                    .unwrap_or_else(|| json!(display));
+
+                Err(RpcError::new(
+                    SUBMIT_TRANSACTION_FAILURE,
+                    "The transaction was rejected by the node",
+                )
+                .with_data(data))
+            },
+            Err(TxSubmitError::PhaseOne(data)) => Err(RpcError::new(
+                SUBMIT_TRANSACTION_FAILURE,
+                "The transaction failed the phase-1 checks",
+            )
+            .with_data(data)),
+            Err(
+                TxSubmitError::Connection(err)
+                | TxSubmitError::Interrupted(err)
+                | TxSubmitError::Other(err),
+            ) => Err(err.into()),
+        }
+    }
+
+    pub(crate) async fn evaluate_transaction(&mut self, params: Value) -> RpcResult {
+        let tx = TransactionParams::parse(params)?;
+        let node = self.node.clone();
+
+        // XXX: Axum must not abort Ouroboros protocols in the middle, hence a separate Tokio task:
+        let result = tokio::spawn(async move {
+            let mut node = node.get().await?;
+            node.evaluate_transaction(tx, Vec::new()).await
+        })
+        .await
+        .expect("evaluate_transaction panic!")?;
+
+        // The REST endpoint already speaks Ogmios v6, we only unwrap its envelope:
+        let mut response = to_ogmios_response(result, &EvaluateQuery { version: Some(6) });
+
+        match response.get_mut("result") {
+            Some(result) => Ok(result.take()),
+            None => {
+                let error = &response["error"];
+                let message = match &error["message"] {
+                    Value::String(message) => message.clone(),
+                    other => other.to_string(),
+                };
+
+                let mut rpc_error =
+                    RpcError::new(error["code"].as_i64().unwrap_or(INTERNAL_ERROR), message);
+                rpc_error.data = error.get("data").cloned();
+
+                Err(rpc_error)
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(json!({ "transaction": { "cbor": "84a0" } }), Ok(vec![0x84, 0xa0]))]
+    #[case(json!({ "transaction": { "cbor": "84a0" }, "additionalUtxo": [] }), Ok(vec![0x84, 0xa0]))]
+    #[case(json!({ "transaction": { "cbor": "84a0" }, "additionalUtxo": [{}] }), Err(()))]
+    #[case(json!({ "transaction": { "cbor": "zz" } }), Err(()))]
+    #[case(json!({ "transaction": "84a0" }), Err(()))]
+    #[case(Value::Null, Err(()))]
+    fn test_transaction_params(#[case] params: Value, #[case] expected: Result<Vec<u8>, ()>) {
+        assert_eq!(TransactionParams::parse(params).map_err(|_| ()), expected);
+    }
+}
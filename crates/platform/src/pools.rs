@@ -2,6 +2,8 @@ use bech32::{Bech32, Hrp};
 use bf_common::errors::BlockfrostError;
 use serde::Deserialize;
 
+const POOL_HASH_LENGTH: usize = 28;
+
 #[derive(Deserialize, Clone)]
 pub struct PoolsPath {
     pub pool_id: String,
@@ -21,10 +23,12 @@ impl PoolData {
         Ok(Self { pool_id })
     }
 
+    /// Accepts a pool id in bech32 or hex, i.e. the 28-byte hash of the pool’s
+    /// cold key, and returns it in bech32.
     pub fn validate_and_convert_pool(input: &str) -> Option<String> {
         if hex::decode(input).is_ok() {
             let bytes = match hex::decode(input) {
-                Ok(bytes) => bytes,
+                Ok(bytes) if bytes.len() == POOL_HASH_LENGTH => bytes,
                 _ => return None,
             };
             let hrp = Hrp::parse("pool").ok()?;
@@ -35,9 +39,9 @@ impl PoolData {
 
             Some(pool_id)
         } else {
-            let (hrp, _) = bech32::decode(input).ok()?;
+            let (hrp, bytes) = bech32::decode(input).ok()?;
 
-            if hrp.as_str() == "pool" {
+            if hrp.as_str() == "pool" && bytes.len() == POOL_HASH_LENGTH {
                 return Some(input.to_string());
             }
 
@@ -60,6 +64,21 @@ mod tests {
         "addr1qyw8xfunw6lhzzzsdrx5ze6j8ayxjhecv4ty5jtaey5jvwquwvnexa4lwyy9q6xdg9n4y06gd90nse2kffyhmjffycuq405jv6",
         None
     )]
+    #[case(
+        "Short pool Bech32",
+        "pool1pu5jlj4q9w9jlxeu370a3c9myx47md5j5m2str0naunsrja7gj",
+        None
+    )]
+    #[case(
+        "Short pool Hex",
+        "0f292fcaa02b8b2f9b3c8f9fd8e0bb21abedb692a6d5058df3ef27",
+        None
+    )]
+    #[case(
+        "Long pool Hex",
+        "0f292fcaa02b8b2f9b3c8f9fd8e0bb21abedb692a6d5058df3ef273500",
+        None
+    )]
     #[case("Invalid pool", "stonks_pool", None)]
     fn test_validate_and_convert_pool(
         #[case] description: &str,
@@ -0,0 +1,61 @@
+use bf_common::errors::BlockfrostError;
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+pub struct ProposalsPath {
+    pub tx_hash: String,
+    pub cert_index: String,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ProposalData {
+    pub tx_hash: String,
+    pub cert_index: u32,
+}
+
+impl ProposalData {
+    pub fn from_path(path: ProposalsPath) -> Result<Self, BlockfrostError> {
+        if path.tx_hash.len() != 64 || hex::decode(&path.tx_hash).is_err() {
+            return Err(BlockfrostError::custom_400(
+                "Missing or malformed tx_hash.".to_string(),
+            ));
+        }
+
+        let cert_index = path.cert_index.parse::<u32>().map_err(|_| {
+            BlockfrostError::custom_400("params/cert_index must be integer".to_string())
+        })?;
+
+        Ok(Self {
+            tx_hash: path.tx_hash.to_lowercase(),
+            cert_index,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    const TX_HASH: &str = "2dd15e0ef6e6a17841cb9541c27724072ce4d4b79b91e58432fbaa32d9572531";
+
+    #[rstest]
+    #[case(TX_HASH, "0", Ok(ProposalData { tx_hash: TX_HASH.to_string(), cert_index: 0 }))]
+    #[case(TX_HASH, "12", Ok(ProposalData { tx_hash: TX_HASH.to_string(), cert_index: 12 }))]
+    #[case(TX_HASH, "-1", Err(BlockfrostError::custom_400("params/cert_index must be integer".to_string())))]
+    #[case(TX_HASH, "abc", Err(BlockfrostError::custom_400("params/cert_index must be integer".to_string())))]
+    #[case("2dd15e", "0", Err(BlockfrostError::custom_400("Missing or malformed tx_hash.".to_string())))]
+    fn test_from_path(
+        #[case] tx_hash: &str,
+        #[case] cert_index: &str,
+        #[case] expected: Result<ProposalData, BlockfrostError>,
+    ) {
+        let path = ProposalsPath {
+            tx_hash: tx_hash.to_string(),
+            cert_index: cert_index.to_string(),
+        };
+
+        assert_eq!(ProposalData::from_path(path), expected);
+    }
+}
@@ -2,13 +2,20 @@ pub mod metrics;
 pub mod routes;
 pub mod state;
 use crate::{
-    config::Config, genesis::GenesisRegistry, health_monitor, icebreakers::api::IcebreakersAPI,
+    config::{Config, Mode},
+    genesis::GenesisRegistry,
+    health_monitor,
+    icebreakers::api::IcebreakersAPI,
     middlewares::errors::error_middleware,
 };
 use axum::{Extension, Router, middleware::from_fn};
-use bf_common::errors::{AppError, BlockfrostError};
+use bf_common::{
+    errors::{AppError, BlockfrostError},
+    route_groups::RouteGroup,
+};
 use bf_data_node::client::DataNode;
-use bf_node::pool::NodePool;
+use bf_error_decoder::external::ExternalDecoder;
+use bf_node::{chain_sync::ChainFollower, pool::NodePool, tx_tracker::TxTracker};
 use metrics::{setup_metrics_recorder, spawn_process_collector};
 use routes::{hidden::get_hidden_api_routes, nest_routes, regular::get_regular_api_routes};
 use state::{ApiPrefix, AppState};
@@ -48,7 +55,7 @@ pub async fn build(
 
         NodePool::new(
             network_magic,
-            config.node_socket_path.to_string(),
+            config.node_socket_paths.clone(),
             config.max_pool_connections,
         )?
     };
@@ -60,6 +67,42 @@ pub async fn build(
         .map(|dn| DataNode::new(&dn.endpoint, dn.request_timeout))
         .transpose()?;
 
+    // External decoder of transaction rejections, optional, because we can still
+    // fall back to our own display of the errors
+    let error_decoder = spawn_error_decoder().await;
+
+    let route_groups = config.route_groups();
+
+    if config.mode == Mode::Compact && data_node.is_some() {
+        tracing::warn!(
+            "--mode compact with --data-node serves all data node endpoints, like --mode full"
+        );
+    }
+
+    let serves = |group| route_groups.contains(&group);
+
+    // Lifecycle of transactions submitted through us, only needed if we accept
+    // submissions at all:
+    let needs_tx_tracker = serves(RouteGroup::TxSubmit) || serves(RouteGroup::Ogmios);
+
+    // Chain follower, for streaming blocks and rollbacks, for the tracker, and
+    // for the totals of `/epochs/latest`. It keeps a node connection of its
+    // own, so let’s not spawn it needlessly:
+    let chain_follower =
+        (needs_tx_tracker || serves(RouteGroup::ChainStream) || serves(RouteGroup::LedgerState))
+            .then(|| ChainFollower::spawn(node_conn_pool.clone()));
+
+    let tx_tracker = chain_follower
+        .as_ref()
+        .filter(|_| needs_tx_tracker)
+        .map(|chain_follower| {
+            TxTracker::spawn(
+                node_conn_pool.clone(),
+                chain_follower.clone(),
+                config.resubmit_on_rollback,
+            )
+        });
+
     // Health monitor
     let health_monitor =
         health_monitor::HealthMonitor::spawn(node_conn_pool.clone(), data_node.clone()).await;
@@ -72,7 +115,7 @@ pub async fn build(
 
     // API routes that are always under / (and also under the UUID prefix, if we use it)
     let regular_api_routes = get_regular_api_routes(!config.no_metrics);
-    let hidden_api_routes = get_hidden_api_routes(!config.no_metrics);
+    let hidden_api_routes = get_hidden_api_routes(!config.no_metrics, &route_groups);
 
     // Nest under the UUID prefix
     let api_routes = nest_routes(&api_prefix, regular_api_routes, hidden_api_routes);
@@ -81,6 +124,7 @@ pub async fn build(
     let app_state = AppState {
         config: config.clone(),
         data_node,
+        error_decoder,
     };
 
     // Add layers
@@ -92,6 +136,14 @@ pub async fn build(
             .layer(from_fn(error_middleware))
             .fallback(BlockfrostError::not_found());
 
+        if let Some(chain_follower) = chain_follower {
+            routes = routes.layer(Extension(chain_follower));
+        }
+
+        if let Some(tx_tracker) = tx_tracker {
+            routes = routes.layer(Extension(tx_tracker));
+        }
+
         if let Some(prom_handler) = metrics_handle {
             routes = routes.layer(Extension(prom_handler));
         }
@@ -112,3 +164,16 @@ pub async fn build(
         api_prefix,
     ))
 }
+
+async fn spawn_error_decoder() -> Option<ExternalDecoder> {
+    let result = match ExternalDecoder::spawn() {
+        Ok(decoder) => decoder.startup_sanity_test().await.map(|()| decoder),
+        Err(err) => Err(err.to_string()),
+    };
+
+    result
+        .inspect_err(|err| {
+            tracing::warn!("Transaction rejections will not be decoded to JSON: {err}");
+        })
+        .ok()
+}
@@ -59,6 +59,18 @@ fn internal_setup() -> Arc<RwLock<PrometheusHandle>> {
     );
     counter!("cardano_node_connections_failed").absolute(0);
 
+    describe_counter!(
+        "cardano_node_query_cache_hits",
+        "Number of local-state queries answered from the cache"
+    );
+    counter!("cardano_node_query_cache_hits").absolute(0);
+
+    describe_counter!(
+        "cardano_node_query_cache_misses",
+        "Number of local-state queries that had to be sent to the node"
+    );
+    counter!("cardano_node_query_cache_misses").absolute(0);
+
     describe_counter!(
         "tx_submit_success",
         "Number of transactions that were successfully submitted"
@@ -1,13 +1,16 @@
-use crate::config::Config;
+use crate::{config::Config, health_monitor::HealthMonitor};
 use axum::extract::State;
 use bf_common::errors::BlockfrostError;
 use bf_data_node::client::DataNode;
+use bf_error_decoder::external::ExternalDecoder;
 use std::sync::Arc;
 
 #[derive(Clone)]
 pub struct AppState {
     pub config: Arc<Config>,
     pub data_node: Option<DataNode>,
+    /// Decodes node rejections into cardano-submit-api JSON; `None` if it failed to start.
+    pub error_decoder: Option<ExternalDecoder>,
 }
 
 impl AppState {
@@ -16,6 +19,15 @@ impl AppState {
             BlockfrostError::internal_server_error("Data node is not configured".to_string())
         })
     }
+
+    /// Returns the data node only if it’s configured and currently healthy.
+    /// Otherwise, handlers with a node-backed path should fall back to it.
+    pub async fn healthy_data_node(&self, health_monitor: &HealthMonitor) -> Option<&DataNode> {
+        match &self.data_node {
+            Some(data_node) if health_monitor.is_data_node_healthy().await => Some(data_node),
+            _ => None,
+        }
+    }
 }
 
 pub type AppStateExt = State<AppState>;
@@ -1,6 +1,92 @@
+use bf_api_provider::types::TxsContentRequiredSignersInner;
+use bf_common::errors::BlockfrostError;
+use pallas_codec::minicbor::{self, Decoder, data::Type};
 use serde::Deserialize;
+use serde_json::{Value, json};
 
 #[derive(Deserialize)]
 pub struct TxsPath {
     pub hash: String,
 }
+
+/// Transaction body key of `required_signers`.
+const REQUIRED_SIGNERS_KEY: u64 = 14;
+
+/// Reads the required signers (key hashes under field 14 of the transaction
+/// body) from a hex-encoded transaction.
+pub fn required_signers_from_cbor(
+    cbor: &str,
+) -> Result<TxsContentRequiredSignersInner, BlockfrostError> {
+    let bytes = hex::decode(cbor)?;
+
+    let signers = decode_required_signers(&bytes).map_err(|e| {
+        BlockfrostError::internal_server_error(format!("Failed to decode transaction: {e}"))
+    })?;
+
+    let response = signers
+        .iter()
+        .map(|signer| json!({ "witness_hash": hex::encode(signer) }))
+        .collect();
+
+    serde_json::from_value(Value::Array(response)).map_err(|e| {
+        BlockfrostError::internal_server_error(format!("Failed to convert required signers: {e}"))
+    })
+}
+
+fn decode_required_signers(tx: &[u8]) -> Result<Vec<Vec<u8>>, minicbor::decode::Error> {
+    let mut d = Decoder::new(tx);
+
+    d.array()?;
+
+    // Byron transactions have no body map and no required signers:
+    let Some(len) = (match d.datatype()? {
+        Type::Map => d.map()?,
+        _ => return Ok(vec![]),
+    }) else {
+        return Err(minicbor::decode::Error::message(
+            "indefinite transaction body",
+        ));
+    };
+
+    for _ in 0..len {
+        if d.u64()? != REQUIRED_SIGNERS_KEY {
+            d.skip()?;
+            continue;
+        }
+
+        // A set since Conway:
+        if d.datatype()? == Type::Tag {
+            d.tag()?;
+        }
+
+        return d
+            .array_iter::<minicbor::bytes::ByteVec>()?
+            .map(|signer| signer.map(|s| s.to_vec()))
+            .collect();
+    }
+
+    Ok(vec![])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::without_signers("82a20001020380", vec![])]
+    #[case::with_signers(
+        &["82a3000102030e82581c", &"aa".repeat(28), "581c", &"bb".repeat(28), "80"].concat(),
+        vec![vec![0xaa; 28], vec![0xbb; 28]],
+    )]
+    #[case::with_set_tag(
+        &["82a10ed9010281581c", &"cc".repeat(28), "80"].concat(),
+        vec![vec![0xcc; 28]],
+    )]
+    fn test_decode_required_signers(#[case] tx: &str, #[case] expected: Vec<Vec<u8>>) {
+        let tx = hex::decode(tx).unwrap();
+
+        assert_eq!(decode_required_signers(&tx).unwrap(), expected);
+    }
+}
@@ -773,7 +773,7 @@ in
               --server-port "$platform_port" \
               --log-level info \
               --node-socket-path "''${CARDANO_NODE_SOCKET_PATH}" \
-              --mode compact \
+              --mode full \
               --secret 'unused-unused' \
               --reward-address "$reward_address" \
               --gateway-url "$gateway_url" \